@@ -0,0 +1,799 @@
+use chrono::NaiveDate;
+use clap::{command, Arg, ArgAction, ArgMatches, Command};
+use railists::data_source::parse_purchase_date;
+use railists::domain::catalog::{
+    categories::Category,
+    rolling_stocks::{DccInterface, Epoch},
+};
+use railists::domain::collecting::{
+    wish_lists::{Availability, PricingStrategy},
+    Price,
+};
+use rust_decimal::Decimal;
+
+pub fn get_matches() -> ArgMatches {
+    let file_arg = Arg::new("file")
+        .short('f')
+        .long("file")
+        .required(true)
+        .value_name("file name")
+        .help("The file name, or - to read the standard input (required)");
+
+    let files_arg = Arg::new("file")
+        .short('f')
+        .long("file")
+        .required(true)
+        .action(ArgAction::Append)
+        .value_delimiter(',')
+        .value_name("file name")
+        .help("The file names, repeatable or comma separated, or - to read the standard input (required)");
+
+    let reject_duplicates_arg = Arg::new("reject-duplicates")
+        .long("reject-duplicates")
+        .action(ArgAction::SetTrue)
+        .help("Skip the items already loaded from a previous file (same brand and item number)");
+
+    let description_width_arg = Arg::new("description-width")
+        .long("description-width")
+        .value_parser(clap::value_parser!(usize))
+        .default_value("50")
+        .value_name("chars")
+        .help("The max width for the descriptions (0 means no truncation)");
+
+    let category_arg = Arg::new("category")
+        .long("category")
+        .value_parser(clap::value_parser!(Category))
+        .action(ArgAction::Append)
+        .value_name("CATEGORY")
+        .help("List only the items with this category (like locomotives or passenger-cars), repeatable");
+
+    let top_arg = Arg::new("top")
+        .long("top")
+        .value_parser(clap::value_parser!(usize))
+        .value_name("N")
+        .help("Show only the N groups with the highest value, the others are summed together");
+
+    let from_date_arg = Arg::new("from")
+        .long("from")
+        .value_parser(parse_date)
+        .value_name("YYYY-MM-DD")
+        .help("Include only the items purchased on this date or later");
+
+    let to_date_arg = Arg::new("to")
+        .long("to")
+        .value_parser(parse_date)
+        .value_name("YYYY-MM-DD")
+        .help("Include only the items purchased on this date or earlier");
+
+    let include_sold_arg = Arg::new("include-sold")
+        .long("include-sold")
+        .action(ArgAction::SetTrue)
+        .help("Include the items already sold");
+
+    let collection_ls_subcommand = Command::new("list")
+        .alias("l")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(description_width_arg.clone())
+        .arg(category_arg.clone())
+        .arg(
+            Arg::new("epoch")
+                .long("epoch")
+                .value_parser(clap::value_parser!(Epoch))
+                .value_name("EPOCH")
+                .help("List only the items with a rolling stock overlapping this epoch (like IV or IV/V); sub-eras like IVa match their main era"),
+        )
+        .arg(
+            Arg::new("min-price")
+                .long("min-price")
+                .value_parser(clap::value_parser!(Price))
+                .value_name("PRICE")
+                .help("List only the items purchased at this price or more"),
+        )
+        .arg(
+            Arg::new("max-price")
+                .long("max-price")
+                .value_parser(clap::value_parser!(Price))
+                .value_name("PRICE")
+                .help("List only the items purchased at this price or less"),
+        )
+        .arg(from_date_arg.clone())
+        .arg(to_date_arg.clone())
+        .arg(
+            Arg::new("sort-by")
+                .long("sort-by")
+                .value_parser(["brand", "price", "date", "count"])
+                .default_value("brand")
+                .help("Sort the items by brand and item number, price, purchase date or count"),
+        )
+        .arg(
+            Arg::new("tag")
+                .long("tag")
+                .action(ArgAction::Append)
+                .value_name("TAG")
+                .help("List only the items with this tag (ignoring case), repeatable"),
+        )
+        .arg(
+            Arg::new("show-storage")
+                .long("show-storage")
+                .action(ArgAction::SetTrue)
+                .help("Include the storage location column"),
+        )
+        .arg(
+            Arg::new("prices")
+                .long("prices")
+                .value_name("file name")
+                .help("The wish list with the reference prices, to show the current value and its change from the price paid"),
+        )
+        .arg(
+            Arg::new("desc")
+                .long("desc")
+                .action(ArgAction::SetTrue)
+                .help("Sort the items in descending order"),
+        )
+        .arg(include_sold_arg.clone())
+        .about("List the collection elements");
+
+    let group_by_arg = Arg::new("group-by")
+        .long("group-by")
+        .value_parser(["shop"])
+        .value_name("GROUP")
+        .help("Group the statistics by shop");
+
+    let sort_shops_arg = Arg::new("sort")
+        .long("sort")
+        .value_parser(["name", "total"])
+        .default_value("name")
+        .requires("group-by")
+        .help("Sort the shops by name or by total (descending)");
+
+    let collection_stats_subcommand = Command::new("stats")
+        .alias("s")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(group_by_arg.clone())
+        .arg(sort_shops_arg.clone())
+        .arg(from_date_arg)
+        .arg(to_date_arg)
+        .arg(
+            Arg::new("histogram")
+                .long("histogram")
+                .action(ArgAction::SetTrue)
+                .help("Show the number of items for each price range"),
+        )
+        .arg(include_sold_arg.clone().help(
+            "Include the items already sold, keeping the statistics by purchase year unchanged by the later sales",
+        ))
+        .about("Calculate the collection statistics");
+
+    let collection_depot_subcommand = Command::new("depot")
+        .alias("d")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(
+            Arg::new("export-jmri")
+                .long("export-jmri")
+                .required(false)
+                .value_name("file name")
+                .help("Export the depot as JMRI roster to this file"),
+        )
+        .arg(
+            Arg::new("owner")
+                .long("owner")
+                .required(false)
+                .value_name("name")
+                .help("The locomotives owner for the JMRI roster"),
+        )
+        .arg(
+            Arg::new("include-trains")
+                .long("include-trains")
+                .action(ArgAction::SetTrue)
+                .help("Include the trains and railcars with a decoder in the depot"),
+        )
+        .arg(
+            Arg::new("railway")
+                .long("railway")
+                .required(false)
+                .value_name("railway")
+                .help("Select only the locomotives for this railway"),
+        )
+        .arg(
+            Arg::new("epoch")
+                .long("epoch")
+                .required(false)
+                .value_parser(clap::value_parser!(Epoch))
+                .value_name("epoch")
+                .help("Select only the locomotives for this epoch"),
+        )
+        .arg(
+            Arg::new("with-decoder")
+                .long("with-decoder")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("without-decoder")
+                .help("Select only the locomotives with a decoder"),
+        )
+        .arg(
+            Arg::new("without-decoder")
+                .long("without-decoder")
+                .action(ArgAction::SetTrue)
+                .help("Select only the locomotives without a decoder"),
+        )
+        .arg(
+            Arg::new("dcc-interface")
+                .long("dcc-interface")
+                .value_parser(clap::value_parser!(DccInterface))
+                .value_name("INTERFACE")
+                .help("Select only the locomotives with this dcc interface"),
+        )
+        .arg(
+            Arg::new("unique")
+                .long("unique")
+                .action(ArgAction::SetTrue)
+                .help("Remove the duplicated locomotives"),
+        )
+        .arg(
+            Arg::new("summary")
+                .long("summary")
+                .action(ArgAction::SetTrue)
+                .help("Count the locomotives still to equip with a decoder"),
+        )
+        .arg(
+            Arg::new("only-missing-decoder")
+                .long("only-missing-decoder")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("summary")
+                .help("List the locomotives without a decoder by dcc interface, as decoder shopping list"),
+        )
+        .arg(
+            Arg::new("check-addresses")
+                .long("check-addresses")
+                .action(ArgAction::SetTrue)
+                .help("Report locomotives sharing the same dcc address"),
+        )
+        .arg(include_sold_arg.clone())
+        .about("Extract the depot information for locomotives");
+
+    let collection_count_subcommand = Command::new("count")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(include_sold_arg.clone())
+        .about("Print the number of items and rolling stocks, by category (one key: value per line)");
+
+    let collection_sales_subcommand = Command::new("sales")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .about("List the sold items with their realized gains or losses, per item and per year");
+
+    let collection_roster_subcommand = Command::new("roster")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(category_arg.clone().help(
+            "List only the rolling stocks with this category (like locomotives or passenger-cars), repeatable",
+        ))
+        .about("List every rolling stock in the collection, by railway");
+
+    let collection_top_subcommand = Command::new("top")
+        .arg(file_arg.clone())
+        .arg(include_sold_arg.clone())
+        .arg(
+            Arg::new("number")
+                .short('n')
+                .long("number")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("10")
+                .value_name("N")
+                .help("The number of purchases to list"),
+        )
+        .arg(category_arg.clone().help(
+            "List only the items with this category (like locomotives or passenger-cars), repeatable",
+        ))
+        .arg(
+            Arg::new("output")
+                .long("output")
+                .value_parser(["table", "csv"])
+                .default_value("table")
+                .help("Print a table or write csv records to the standard output"),
+        )
+        .about("List the most expensive purchases in the collection");
+
+    let collection_by_scale_subcommand = Command::new("by-scale")
+        .arg(file_arg.clone())
+        .arg(include_sold_arg.clone())
+        .arg(
+            Arg::new("sort")
+                .long("sort")
+                .value_parser(["name", "ratio"])
+                .default_value("name")
+                .help("Sort the scales by name or by ratio"),
+        )
+        .arg(top_arg.clone())
+        .about("Calculate the collection statistics by scale");
+
+    let collection_by_railway_subcommand = Command::new("by-railway")
+        .arg(file_arg.clone())
+        .arg(include_sold_arg.clone())
+        .arg(top_arg.clone())
+        .about("Calculate the collection statistics by railway");
+
+    let collection_by_brand_subcommand = Command::new("by-brand")
+        .arg(file_arg.clone())
+        .arg(include_sold_arg.clone())
+        .arg(top_arg.clone())
+        .about("Calculate the collection statistics by brand");
+
+    let collection_heatmap_subcommand = Command::new("heatmap")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(
+            Arg::new("by")
+                .long("by")
+                .value_parser(["spend", "count"])
+                .default_value("spend")
+                .help("Shade the months by amount spent or by number of items"),
+        )
+        .about("Show the purchases by month across the years");
+
+    let collection_timeline_subcommand = Command::new("timeline")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(include_sold_arg.clone())
+        .arg(
+            Arg::new("monthly")
+                .long("monthly")
+                .action(ArgAction::SetTrue)
+                .help("Show the amounts spent by month instead of by year"),
+        )
+        .arg(
+            Arg::new("from")
+                .long("from")
+                .value_parser(clap::value_parser!(i32))
+                .value_name("YYYY")
+                .help("Start the timeline from this year"),
+        )
+        .arg(
+            Arg::new("to")
+                .long("to")
+                .value_parser(clap::value_parser!(i32))
+                .value_name("YYYY")
+                .help("End the timeline with this year"),
+        )
+        .about("Show the amounts spent over time, with the cumulative total");
+
+    let collection_valuation_subcommand = Command::new("valuation")
+        .arg(file_arg.clone())
+        .arg(
+            Arg::new("method")
+                .long("method")
+                .value_parser(["purchase", "replacement", "market"])
+                .default_value("purchase")
+                .help("The method to estimate the collection value"),
+        )
+        .arg(
+            Arg::new("rate")
+                .long("rate")
+                .value_parser(clap::value_parser!(Decimal))
+                .value_name("PERCENT")
+                .default_value("2")
+                .help("The yearly rate for the replacement method"),
+        )
+        .arg(
+            Arg::new("multiplier")
+                .long("multiplier")
+                .value_name("CATEGORY=FACTOR")
+                .action(ArgAction::Append)
+                .help("The market multiplier for a category (L, P, F or T)"),
+        )
+        .about("Estimate the collection value");
+
+    let collection_market_value_subcommand = Command::new("market-value")
+        .arg(file_arg.clone())
+        .arg(
+            Arg::new("wishlist")
+                .short('w')
+                .long("wishlist")
+                .required(true)
+                .value_name("file name")
+                .help("The wish list with the reference prices (the max price for each item)"),
+        )
+        .about("Compare the price paid with the current value from the wish list prices, ie for insurance");
+
+    let collection_tags_subcommand = Command::new("tags")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .about("List the tags used in the collection, with their items count");
+
+    let collection_locate_subcommand = Command::new("locate")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(
+            Arg::new("query")
+                .required_unless_present("missing-storage")
+                .help("The text to search in descriptions and road numbers (ignoring case)"),
+        )
+        .arg(
+            Arg::new("missing-storage")
+                .long("missing-storage")
+                .action(ArgAction::SetTrue)
+                .help("List only the items without a storage location"),
+        )
+        .about("Find where the collection items are stored");
+
+    let collection_show_subcommand = Command::new("show")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(Arg::new("brand").long("brand").help(
+            "The item brand, required only when the item number is not unique",
+        ))
+        .arg(
+            Arg::new("item-number")
+                .long("item-number")
+                .required(true)
+                .help("The item number"),
+        )
+        .about("Show the details for a collection item, including its notes");
+
+    let collection_validate_subcommand = Command::new("validate")
+        .arg(file_arg.clone())
+        .about("Check the collection for duplicated items and data issues");
+
+    let collection_search_subcommand = Command::new("search")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(description_width_arg.clone())
+        .arg(
+            Arg::new("query")
+                .required(true)
+                .help("The text to search (ignoring case)"),
+        )
+        .about(
+            "Search the collection items by description, road number or livery",
+        );
+
+    let collection_duplicates_subcommand = Command::new("duplicates")
+        .arg(file_arg.clone())
+        .arg(
+            Arg::new("by-road-number")
+                .long("by-road-number")
+                .action(ArgAction::SetTrue)
+                .help("Group the locomotives by class name and road number"),
+        )
+        .about("Find the items purchased more than once");
+
+    let collection_warranties_subcommand = Command::new("warranties")
+        .arg(file_arg.clone())
+        .about("List the collection items still under warranty");
+
+    let collection_check_subcommand = Command::new("check")
+        .arg(file_arg.clone())
+        .arg(
+            Arg::new("threshold")
+                .long("threshold")
+                .value_parser(clap::value_parser!(u8).range(1..100))
+                .default_value("10")
+                .value_name("percent")
+                .help("Max share of items for a minority power method"),
+        )
+        .about("Check the collection for inconsistent power methods");
+
+    let collection_merge_subcommand = Command::new("merge")
+        .arg(files_arg.clone())
+        .arg(reject_duplicates_arg.clone())
+        .arg(
+            Arg::new("report-collisions")
+                .long("report-collisions")
+                .action(ArgAction::SetTrue)
+                .help("List the item numbers shared by different brands"),
+        )
+        .about("Merge collection files, reporting the duplicated items");
+
+    let collection_diff_subcommand = Command::new("diff")
+        .arg(file_arg.clone())
+        .arg(
+            Arg::new("other")
+                .long("other")
+                .required(true)
+                .value_name("file name")
+                .help("The newer collection file, compared with the first one"),
+        )
+        .about("List the items added, removed and changed between two collection files");
+
+    let collection_csv_subcommand = Command::new("csv")
+        .alias("c")
+        .arg(file_arg.clone())
+        .arg(
+            Arg::new("output-file")
+                .short('o')
+                .long("output")
+                .value_name("file name")
+                .help("The output file name (the standard output when missing)"),
+        )
+        .arg(
+            Arg::new("combined-price")
+                .long("combined-price")
+                .action(ArgAction::SetTrue)
+                .help("Write the price and its currency in a single column"),
+        )
+        .arg(
+            Arg::new("numeric-prices")
+                .long("numeric-prices")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("combined-price")
+                .help("Write the raw price amounts in a single column, without currency"),
+        )
+        .about("Export the collection as csv file");
+
+    let collection_insurance_report_subcommand =
+        Command::new("insurance-report")
+            .arg(file_arg.clone())
+            .arg(
+                Arg::new("output-file")
+                    .short('o')
+                    .long("output")
+                    .value_name("file name")
+                    .help("The output file name (the standard output when missing)"),
+            )
+            .arg(
+                Arg::new("markup")
+                    .long("markup")
+                    .value_parser(clap::value_parser!(Decimal))
+                    .value_name("FACTOR")
+                    .help("Scale the purchase price by this factor (like 1.2) for the items without a replacement value"),
+            )
+            .about("Export the purchase prices and the replacement values as csv file, for the insurance");
+
+    let collection_json_subcommand = Command::new("json")
+        .arg(file_arg.clone())
+        .arg(
+            Arg::new("output-file")
+                .short('o')
+                .long("output")
+                .value_name("file name")
+                .help("The output file name (the standard output when missing)"),
+        )
+        .arg(
+            Arg::new("pretty")
+                .long("pretty")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(["compact", "ndjson"])
+                .help("Indent the json output (the default for an interactive terminal)"),
+        )
+        .arg(
+            Arg::new("compact")
+                .long("compact")
+                .action(ArgAction::SetTrue)
+                .help("Write the json output on a single line (the default when piping)"),
+        )
+        .arg(
+            Arg::new("ndjson")
+                .long("ndjson")
+                .action(ArgAction::SetTrue)
+                .help("Write one compact json document per item and line"),
+        )
+        .about("Export the collection as json file");
+
+    let collection_subcommand = Command::new("collection")
+        .alias("c")
+        .subcommand(collection_ls_subcommand)
+        .subcommand(collection_csv_subcommand)
+        .subcommand(collection_insurance_report_subcommand)
+        .subcommand(collection_json_subcommand)
+        .subcommand(collection_stats_subcommand)
+        .subcommand(collection_count_subcommand)
+        .subcommand(collection_depot_subcommand)
+        .subcommand(collection_roster_subcommand)
+        .subcommand(collection_by_scale_subcommand)
+        .subcommand(collection_by_railway_subcommand)
+        .subcommand(collection_by_brand_subcommand)
+        .subcommand(collection_heatmap_subcommand)
+        .subcommand(collection_timeline_subcommand)
+        .subcommand(collection_top_subcommand)
+        .subcommand(collection_valuation_subcommand)
+        .subcommand(collection_sales_subcommand)
+        .subcommand(collection_market_value_subcommand)
+        .subcommand(collection_warranties_subcommand)
+        .subcommand(collection_validate_subcommand)
+        .subcommand(collection_duplicates_subcommand)
+        .subcommand(collection_search_subcommand)
+        .subcommand(collection_check_subcommand)
+        .subcommand(collection_merge_subcommand)
+        .subcommand(collection_diff_subcommand)
+        .subcommand(collection_tags_subcommand)
+        .subcommand(collection_show_subcommand)
+        .subcommand(collection_locate_subcommand)
+        .about("Manage model railway collections");
+
+    let wishlist_ls_subcommand = Command::new("list")
+        .alias("l")
+        .arg(file_arg.clone())
+        .arg(description_width_arg.clone())
+        .arg(category_arg.clone())
+        .arg(
+            Arg::new("priority")
+                .long("priority")
+                .value_name("PRIORITY")
+                .conflicts_with("group-by")
+                .help("List only the items with this priority (high, normal or low)"),
+        )
+        .arg(
+            Arg::new("group-by")
+                .long("group-by")
+                .value_parser(["priority"])
+                .help("List the items in a section for each priority"),
+        )
+        .arg(
+            Arg::new("availability")
+                .long("availability")
+                .value_parser(clap::value_parser!(Availability))
+                .action(ArgAction::Append)
+                .value_name("AVAILABILITY")
+                .help("List only the items with this availability (announced, available, sold-out or discontinued), repeatable"),
+        )
+        .about("List the wishlist elements");
+
+    let wishlist_budget_subcommand = Command::new("budget")
+        .alias("b")
+        .arg(file_arg.clone())
+        .arg(
+            Arg::new("plain")
+                .long("plain")
+                .action(ArgAction::SetTrue)
+                .help("Print the budget as plain lines instead of a table"),
+        )
+        .arg(
+            Arg::new("pricing")
+                .long("pricing")
+                .value_parser(clap::value_parser!(PricingStrategy))
+                .default_value("max")
+                .value_name("PRICING")
+                .help("Price each item with its lowest, highest or average price (min, max or average)"),
+        )
+        .arg(
+            Arg::new("limit")
+                .long("limit")
+                .value_parser(clap::value_parser!(Price))
+                .value_name("PRICE")
+                .conflicts_with("plain")
+                .help("List the items fitting within this budget, by priority and then by price"),
+        )
+        .arg(
+            Arg::new("include-unavailable")
+                .long("include-unavailable")
+                .action(ArgAction::SetTrue)
+                .conflicts_with("limit")
+                .help("Include the items sold out or discontinued in the budget"),
+        )
+        .about("Calculate the wishlist required budget");
+
+    let wishlist_stats_subcommand = Command::new("stats")
+        .alias("s")
+        .arg(file_arg.clone())
+        .arg(group_by_arg)
+        .arg(sort_shops_arg)
+        .about("Calculate the wishlist statistics by category and priority");
+
+    let wishlist_purchase_subcommand = Command::new("purchase")
+        .visible_alias("buy")
+        .arg(file_arg.clone())
+        .arg(
+            Arg::new("collection")
+                .long("collection")
+                .visible_alias("into")
+                .required(true)
+                .value_name("file name")
+                .help("The collection file to add the item to"),
+        )
+        .arg(
+            Arg::new("brand")
+                .long("brand")
+                .help("The brand for the purchased item, required only when the item number is not unique"),
+        )
+        .arg(
+            Arg::new("item-number")
+                .long("item-number")
+                .required(true)
+                .help("The item number for the purchased item"),
+        )
+        .arg(
+            Arg::new("shop")
+                .long("shop")
+                .required(true)
+                .help("The shop where the item was purchased"),
+        )
+        .arg(
+            Arg::new("date")
+                .long("date")
+                .required(true)
+                .value_parser(parse_purchase_date)
+                .value_name("YYYY-MM-DD")
+                .help("The purchase date (also DD/MM/YYYY or DD.MM.YYYY)"),
+        )
+        .arg(
+            Arg::new("price")
+                .long("price")
+                .required(true)
+                .value_parser(clap::value_parser!(Price))
+                .help("The purchase price"),
+        )
+        .about("Move a purchased wishlist item into a collection");
+
+    let wishlist_prices_subcommand = Command::new("prices")
+        .arg(file_arg.clone())
+        .arg(Arg::new("brand").long("brand").help(
+            "The item brand, required only when the item number is not unique",
+        ))
+        .arg(
+            Arg::new("item-number")
+                .long("item-number")
+                .required(true)
+                .help("The item number"),
+        )
+        .about("Print the price history for a wish list item, sorted by date");
+
+    let wishlist_check_subcommand = Command::new("check")
+        .arg(file_arg.clone())
+        .arg(
+            Arg::new("collection")
+                .long("collection")
+                .required(true)
+                .value_name("file name")
+                .help("The collection file with the items already owned"),
+        )
+        .arg(
+            Arg::new("by-road-number")
+                .long("by-road-number")
+                .action(ArgAction::SetTrue)
+                .help("Report also the locomotives with the class name and road number of an owned one"),
+        )
+        .about("Report the wish list items already in the collection");
+
+    let wishlist_subcommand = Command::new("wishlist")
+        .alias("w")
+        .subcommand(wishlist_ls_subcommand)
+        .subcommand(wishlist_budget_subcommand)
+        .subcommand(wishlist_stats_subcommand)
+        .subcommand(wishlist_prices_subcommand)
+        .subcommand(wishlist_check_subcommand)
+        .subcommand(wishlist_purchase_subcommand)
+        .about("Manage model railway wishlist");
+
+    // let migrate_subcommand = SubCommand::with_name("migrate")
+    //     .arg(
+    //         Arg::with_name("file")
+    //             .short("f")
+    //             .long("file")
+    //             .takes_value(true)
+    //             .required(true)
+    //             .value_name("file name")
+    //             .help("The file name (required)"),
+    //     )
+    //     .about("Migrate yaml file");
+
+    command!()
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Model railway collection manager")
+        .author(env!("CARGO_PKG_AUTHORS"))
+        .arg(
+            Arg::new("quiet")
+                .short('q')
+                .long("quiet")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Print only the tables, without the summary lines"),
+        )
+        .arg(
+            Arg::new("strict")
+                .long("strict")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Validate every field in the collection files before loading them"),
+        )
+        .subcommand(collection_subcommand)
+        .subcommand(wishlist_subcommand)
+        .get_matches()
+}
+
+/// Parses a date in the YYYY-MM-DD format
+fn parse_date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}' (expected YYYY-MM-DD)", value))
+}
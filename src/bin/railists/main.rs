@@ -0,0 +1,1773 @@
+//! The `railists` command line application.
+mod cli;
+
+use chrono::{Local, NaiveDate};
+use clap::ArgMatches;
+use heck::ToTitleCase;
+use railists::data_source::DataSource;
+use railists::domain::catalog::catalog_items::CatalogItem;
+use railists::domain::catalog::categories::Category;
+use railists::domain::catalog::rolling_stocks::{DccInterface, Epoch};
+use railists::domain::collecting::{
+    collections::{
+        Collection, CollectionCount, CollectionDiff, CollectionItem,
+        CollectionStats, CollectionStatsByBrand, CollectionStatsByRailway,
+        CollectionStatsByScale, Depot, DepotFilter, MergeReport, PurchasedInfo,
+        PurchasesByMonth, Roster, SalesReport, SortItemsBy, SpendingTimeline,
+        TopPurchases,
+    },
+    shops::ShopStats,
+    valuations::{
+        CollectionValuation, CurrentValueReport, InsuranceReport,
+        MarketValuation, PurchaseValuation, ReferencePrices,
+        ReplacementValuation, ValuationMethod,
+    },
+    wish_lists::{
+        Availability, BudgetPlan, PricingStrategy, Priority, WishListBudget,
+        WishListItem, WishListStats,
+    },
+    Price,
+};
+use railists::export::{self, csv::PriceColumns, json::JsonStyle};
+use railists::tables::{self, AsTable, HeatmapMetric};
+use rust_decimal::Decimal;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::process;
+
+fn main() {
+    // the warnings (like the duplicated items in the merged collections) are
+    // shown by default, RUST_LOG can still change the level
+    pretty_env_logger::formatted_builder()
+        .filter_level(log::LevelFilter::Warn)
+        .parse_default_env()
+        .init();
+
+    let matches = cli::get_matches();
+    match matches.subcommand() {
+        Some(("collection", cmd_args)) => match cmd_args.subcommand() {
+            Some(("list", subc_args)) => {
+                let c =
+                    without_sold_items(load_collections(subc_args), subc_args);
+                let mut c = purchased_within(c, subc_args);
+                let categories = categories(subc_args);
+                let epoch = subc_args.get_one::<Epoch>("epoch");
+                let min_price = subc_args.get_one::<Price>("min-price");
+                let max_price = subc_args.get_one::<Price>("max-price");
+                if let (Some(min), Some(max)) = (min_price, max_price) {
+                    if min.amount() > max.amount() {
+                        eprintln!(
+                            "The min price ({}) cannot be greater than the max price ({})",
+                            min, max
+                        );
+                        process::exit(1);
+                    }
+                }
+
+                let tags: Vec<&String> = subc_args
+                    .get_many::<String>("tag")
+                    .map(|values| values.collect())
+                    .unwrap_or_default();
+
+                let sort_by = match subc_args
+                    .get_one::<String>("sort-by")
+                    .map(|s| s.as_str())
+                {
+                    Some("price") => SortItemsBy::Price,
+                    Some("date") => SortItemsBy::Date,
+                    Some("count") => SortItemsBy::Count,
+                    _ => SortItemsBy::Brand,
+                };
+
+                let reference_prices =
+                    subc_args.get_one::<String>("prices").map(|filename| {
+                        let wish_list = DataSource::new(filename)
+                            .wish_list()
+                            .expect("Unable to load the wishlist");
+                        ReferencePrices::from_wish_list(&wish_list)
+                    });
+
+                c.sort_items_by(sort_by, subc_args.get_flag("desc"));
+                let items = c.get_items().iter().filter(|it| {
+                    matches_category(&categories, it.catalog_item())
+                        && matches_epoch(epoch, it.catalog_item())
+                        && matches_price(
+                            min_price,
+                            max_price,
+                            it.purchased_info().price(),
+                        )
+                        && tags.iter().all(|tag| it.has_tag(tag))
+                });
+                let description_width = description_width(subc_args);
+                let show_storage = subc_args.get_flag("show-storage");
+                let table = match &reference_prices {
+                    Some(reference_prices) => {
+                        tables::collection_items_table_with_prices(
+                            items,
+                            description_width,
+                            show_storage,
+                            reference_prices,
+                        )
+                    }
+                    None => tables::collection_items_table(
+                        items,
+                        description_width,
+                        show_storage,
+                    ),
+                };
+                table.printstd();
+            }
+            Some(("csv", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = data_source
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let writer: Box<dyn Write> =
+                    match subc_args.get_one::<String>("output-file") {
+                        Some(output_filename) => Box::new(BufWriter::new(
+                            File::create(output_filename)
+                                .expect("Unable to create the csv file"),
+                        )),
+                        None => Box::new(io::stdout().lock()),
+                    };
+                let price_columns = if subc_args.get_flag("combined-price") {
+                    PriceColumns::Combined
+                } else if subc_args.get_flag("numeric-prices") {
+                    PriceColumns::Numeric
+                } else {
+                    PriceColumns::Split
+                };
+                export::csv::write_collection(writer, &c, price_columns)
+                    .expect("Error during csv export");
+            }
+            Some(("count", subc_args)) => {
+                let c = load_collections(subc_args);
+                let count = if include_sold(subc_args) {
+                    CollectionCount::from_purchase_history(&c)
+                } else {
+                    CollectionCount::from_collection(&c)
+                };
+
+                let mut out = io::stdout();
+                write_count(&mut out, &count)
+                    .expect("Unable to write the counts");
+            }
+            Some(("sales", subc_args)) => {
+                let c = load_collections(subc_args);
+
+                let report = or_exit(SalesReport::from_collection(&c));
+                if report.is_empty() {
+                    println!("No sold items");
+                    return;
+                }
+
+                report.to_table().printstd();
+                tables::sales_by_year_table(&report).printstd();
+            }
+            Some(("insurance-report", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = data_source
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let markup = subc_args.get_one::<Decimal>("markup").copied();
+                let report =
+                    or_exit(InsuranceReport::from_collection(&c, markup));
+                if !report.unknown_value().is_empty() {
+                    eprintln!(
+                        "{} item(s) without a price or a replacement value, left out of the totals",
+                        report.unknown_value().len()
+                    );
+                }
+
+                let writer: Box<dyn Write> =
+                    match subc_args.get_one::<String>("output-file") {
+                        Some(output_filename) => Box::new(BufWriter::new(
+                            File::create(output_filename)
+                                .expect("Unable to create the csv file"),
+                        )),
+                        None => Box::new(io::stdout().lock()),
+                    };
+                export::csv::write_insurance_report(writer, &report)
+                    .expect("Error during csv export");
+            }
+            Some(("json", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = data_source
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let writer: Box<dyn Write> =
+                    match subc_args.get_one::<String>("output-file") {
+                        Some(output_filename) => Box::new(BufWriter::new(
+                            File::create(output_filename)
+                                .expect("Unable to create the json file"),
+                        )),
+                        None => Box::new(io::stdout().lock()),
+                    };
+                if subc_args.get_flag("ndjson") {
+                    export::json::write_collection_items(writer, &c)
+                        .expect("Error during json export");
+                } else {
+                    let style = JsonStyle::from_flags(
+                        subc_args.get_flag("pretty"),
+                        subc_args.get_flag("compact"),
+                    );
+                    export::json::write_collection(writer, &c, style)
+                        .expect("Error during json export");
+                }
+            }
+            Some(("stats", subc_args)) => {
+                let c =
+                    purchased_within(load_collections(subc_args), subc_args);
+
+                if let Some("shop") =
+                    subc_args.get_one::<String>("group-by").map(|s| s.as_str())
+                {
+                    let c = without_sold_items(c, subc_args);
+                    if c.is_empty() {
+                        println!("Collection is empty");
+                        return;
+                    }
+                    let stats = or_exit(ShopStats::from_collection(&c));
+                    write_shop_stats(stats, subc_args);
+                    return;
+                }
+
+                let stats = if include_sold(subc_args) {
+                    or_exit(CollectionStats::from_purchase_history(&c))
+                } else {
+                    or_exit(CollectionStats::from_collection(&c))
+                };
+                if stats.is_empty() {
+                    println!("Collection is empty");
+                    return;
+                }
+                write_stats(&mut io::stdout(), &stats, quiet(subc_args))
+                    .expect("Unable to print the collection stats");
+                if subc_args.get_flag("histogram") {
+                    stats.price_distribution().to_table().printstd();
+                }
+            }
+            Some(("by-scale", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = without_sold_items(
+                    data_source
+                        .collection()
+                        .expect("Unable to load collection"),
+                    subc_args,
+                );
+
+                let mut stats =
+                    or_exit(CollectionStatsByScale::from_collection(&c));
+                if let Some("ratio") =
+                    subc_args.get_one::<String>("sort").map(|s| s.as_str())
+                {
+                    stats.sort_by_ratio();
+                }
+                if let Some(n) = subc_args.get_one::<usize>("top") {
+                    stats.keep_top(*n);
+                }
+
+                let table = stats.to_table();
+                table.printstd();
+            }
+            Some(("by-railway", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = without_sold_items(
+                    data_source
+                        .collection()
+                        .expect("Unable to load collection"),
+                    subc_args,
+                );
+
+                let mut stats =
+                    or_exit(CollectionStatsByRailway::from_collection(&c));
+                if let Some(n) = subc_args.get_one::<usize>("top") {
+                    stats.keep_top(*n);
+                }
+
+                let table = stats.to_table();
+                table.printstd();
+            }
+            Some(("top", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = without_sold_items(
+                    data_source
+                        .collection()
+                        .expect("Unable to load collection"),
+                    subc_args,
+                );
+
+                let n = *subc_args.get_one::<usize>("number").unwrap();
+                let top = or_exit(TopPurchases::from_collection(
+                    &c,
+                    n,
+                    &categories(subc_args),
+                ));
+
+                match subc_args.get_one::<String>("output").map(|s| s.as_str())
+                {
+                    Some("csv") => {
+                        export::csv::write_top_purchases(io::stdout(), &top)
+                            .expect("Error during csv export")
+                    }
+                    _ => top.to_table().printstd(),
+                }
+            }
+            Some(("by-brand", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = without_sold_items(
+                    data_source
+                        .collection()
+                        .expect("Unable to load collection"),
+                    subc_args,
+                );
+
+                let mut stats =
+                    or_exit(CollectionStatsByBrand::from_collection(&c));
+                if let Some(n) = subc_args.get_one::<usize>("top") {
+                    stats.keep_top(*n);
+                }
+
+                let table = stats.to_table();
+                table.printstd();
+            }
+            Some(("heatmap", subc_args)) => {
+                let c = load_collections(subc_args);
+
+                let metric =
+                    match subc_args.get_one::<String>("by").map(|s| s.as_str())
+                    {
+                        Some("count") => HeatmapMetric::Count,
+                        _ => HeatmapMetric::Spend,
+                    };
+
+                let purchases = or_exit(PurchasesByMonth::from_collection(&c));
+                let table = tables::heatmap_table(&purchases, metric);
+                table.printstd();
+                if !quiet(subc_args) {
+                    println!("{}", tables::heatmap_legend(&purchases, metric));
+                }
+            }
+            Some(("timeline", subc_args)) => {
+                let c =
+                    without_sold_items(load_collections(subc_args), subc_args);
+                let timeline = or_exit(SpendingTimeline::from_collection(
+                    &c,
+                    subc_args.get_flag("monthly"),
+                    subc_args.get_one::<i32>("from").copied(),
+                    subc_args.get_one::<i32>("to").copied(),
+                ));
+                timeline.to_table().printstd();
+            }
+            Some(("valuation", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = data_source
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let method: Box<dyn ValuationMethod> = match subc_args
+                    .get_one::<String>("method")
+                    .map(|s| s.as_str())
+                {
+                    Some("replacement") => {
+                        let rate = *subc_args
+                            .get_one::<Decimal>("rate")
+                            .expect("rate has a default value");
+                        Box::new(ReplacementValuation::new(
+                            rate,
+                            Local::now().date_naive(),
+                        ))
+                    }
+                    Some("market") => {
+                        let mut method = MarketValuation::new();
+                        for it in subc_args
+                            .get_many::<String>("multiplier")
+                            .unwrap_or_default()
+                        {
+                            match parse_multiplier(it) {
+                                Some((category, value)) => {
+                                    method =
+                                        method.with_multiplier(category, value);
+                                }
+                                None => {
+                                    eprintln!("Invalid multiplier: {}", it);
+                                    process::exit(1);
+                                }
+                            }
+                        }
+                        Box::new(method)
+                    }
+                    _ => Box::new(PurchaseValuation),
+                };
+
+                let valuation = or_exit(CollectionValuation::from_collection(
+                    &c,
+                    method.as_ref(),
+                ));
+                write_valuation(
+                    &mut io::stdout(),
+                    &valuation,
+                    quiet(subc_args),
+                )
+                .expect("Unable to print the collection valuation");
+            }
+            Some(("market-value", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = data_source
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let wish_list_filename = subc_args
+                    .get_one::<String>("wishlist")
+                    .expect("wishlist file is required");
+                let wish_list = DataSource::new(wish_list_filename)
+                    .wish_list()
+                    .expect("Unable to load the wishlist");
+
+                let reference_prices =
+                    ReferencePrices::from_wish_list(&wish_list);
+                let report = or_exit(CurrentValueReport::from_collection(
+                    &c,
+                    &reference_prices,
+                ));
+                report.to_table().printstd();
+            }
+            Some(("warranties", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = data_source
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let items = c.items_under_warranty(&Local::now().date_naive());
+                if items.is_empty() {
+                    println!("No items under warranty");
+                    return;
+                }
+
+                let table = tables::warranties_table(&items);
+                table.printstd();
+            }
+            Some(("validate", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source = DataSource::new(filename);
+                let errors = match data_source.validate_collection() {
+                    Ok(errors) => errors,
+                    Err(e) => {
+                        eprintln!("Invalid collection file: {}", e);
+                        process::exit(1);
+                    }
+                };
+                if !errors.is_empty() {
+                    for e in errors.iter() {
+                        println!("{}", e);
+                    }
+                    println!("{} error(s) found", errors.len());
+                    process::exit(1);
+                }
+
+                let c = data_source
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let duplicates = c.find_duplicates();
+                for (catalog_item, count) in duplicates.iter() {
+                    println!(
+                        "Duplicated item: {} {} ({} times)",
+                        catalog_item.brand(),
+                        catalog_item.item_number(),
+                        count
+                    );
+                }
+
+                let inconsistencies = c.power_method_inconsistencies(10);
+                for it in inconsistencies.iter() {
+                    for item in it.items() {
+                        println!(
+                            "Inconsistent power method: {} {} is {} ({} is the majority in {})",
+                            item.catalog_item().brand(),
+                            item.catalog_item().item_number(),
+                            it.minority(),
+                            it.majority(),
+                            it.scale()
+                        );
+                    }
+                }
+
+                if duplicates.is_empty() && inconsistencies.is_empty() {
+                    println!("No issues found");
+                }
+                if !duplicates.is_empty() {
+                    process::exit(1);
+                }
+            }
+            Some(("search", subc_args)) => {
+                let query = subc_args
+                    .get_one::<String>("query")
+                    .expect("query is required");
+                let mut c = load_collections(subc_args);
+                c.sort_items();
+
+                let matches = c.search(query);
+                if !quiet(subc_args) {
+                    println!("{} item(s) matching '{}'", matches.len(), query);
+                }
+
+                let table = tables::search_results_table(
+                    &matches,
+                    description_width(subc_args),
+                );
+                table.printstd();
+            }
+            Some(("locate", subc_args)) => {
+                let mut c = load_collections(subc_args);
+                c.sort_items();
+
+                let missing_storage = subc_args.get_flag("missing-storage");
+                let items: Vec<&CollectionItem> = match subc_args
+                    .get_one::<String>("query")
+                {
+                    Some(query) => {
+                        c.search(query).into_iter().map(|m| m.item()).collect()
+                    }
+                    None => c.get_items().iter().collect(),
+                };
+                let items: Vec<&CollectionItem> = items
+                    .into_iter()
+                    .filter(|it| !missing_storage || it.storage().is_none())
+                    .collect();
+
+                tables::locate_table(&items).printstd();
+            }
+            Some(("tags", subc_args)) => {
+                let c = load_collections(subc_args);
+                tables::tags_table(&c.tags()).printstd();
+            }
+            Some(("show", subc_args)) => {
+                let brand = subc_args.get_one::<String>("brand");
+                let item_number = subc_args
+                    .get_one::<String>("item-number")
+                    .expect("item number is required");
+                let c = load_collections(subc_args);
+
+                let items: Vec<_> = c
+                    .get_items()
+                    .iter()
+                    .filter(|it| {
+                        let ci = it.catalog_item();
+                        ci.item_number().value() == item_number
+                            && brand.map_or(true, |b| {
+                                ci.brand().name().eq_ignore_ascii_case(b)
+                            })
+                    })
+                    .collect();
+                if items.is_empty() {
+                    eprintln!(
+                        "Item {} not found in the collection",
+                        item_number
+                    );
+                    process::exit(1);
+                }
+
+                for it in items {
+                    tables::collection_item_details_table(it).printstd();
+                }
+            }
+            Some(("duplicates", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = data_source
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let mut found = false;
+                if subc_args.get_flag("by-road-number") {
+                    for it in c.duplicated_locomotives() {
+                        found = true;
+                        println!(
+                            "{} {} ({} times)",
+                            it.class_name(),
+                            it.road_number(),
+                            it.items().len()
+                        );
+                        for item in it.items() {
+                            let ci = item.catalog_item();
+                            println!(
+                                "  {} {}, {}",
+                                ci.brand(),
+                                ci.item_number(),
+                                purchase_line(item.purchased_info())
+                            );
+                        }
+                    }
+                } else {
+                    for group in c.duplicated_items() {
+                        found = true;
+                        let ci = group[0].catalog_item();
+                        println!(
+                            "{} {} ({} times)",
+                            ci.brand(),
+                            ci.item_number(),
+                            group.len()
+                        );
+                        for item in group {
+                            println!(
+                                "  {}",
+                                purchase_line(item.purchased_info())
+                            );
+                        }
+                    }
+                }
+
+                if found {
+                    process::exit(1);
+                }
+                println!("No duplicates found");
+            }
+            Some(("check", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let threshold =
+                    subc_args.get_one::<u8>("threshold").copied().unwrap_or(10);
+                let data_source =
+                    DataSource::new(filename).with_strict(strict(subc_args));
+                let c = data_source
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let inconsistencies = c.power_method_inconsistencies(threshold);
+                if inconsistencies.is_empty() {
+                    println!("No inconsistent power methods");
+                }
+
+                for it in inconsistencies {
+                    println!(
+                        "{}: {} {} item(s) out of {} ({} is the majority)",
+                        it.scale(),
+                        it.items().len(),
+                        it.minority(),
+                        it.number_of_items(),
+                        it.majority()
+                    );
+                    for item in it.items() {
+                        println!("  - {}", item.catalog_item());
+                    }
+                }
+            }
+            Some(("diff", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("collection file is required");
+                let other_filename = subc_args
+                    .get_one::<String>("other")
+                    .expect("other collection file is required");
+
+                let old = DataSource::new(filename)
+                    .with_strict(strict(subc_args))
+                    .collection()
+                    .expect("Unable to load collection");
+                let new = DataSource::new(other_filename)
+                    .with_strict(strict(subc_args))
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let diff = CollectionDiff::between(&old, &new);
+                if diff.is_empty() {
+                    println!("No differences");
+                    return;
+                }
+
+                let mut out = io::stdout();
+                write_diff(&mut out, &diff).expect("Unable to write the diff");
+            }
+            Some(("merge", subc_args)) => {
+                let mut filenames = subc_args
+                    .get_many::<String>("file")
+                    .expect("collection file is required");
+
+                let first =
+                    filenames.next().expect("collection file is required");
+                let mut c = DataSource::new(first)
+                    .with_strict(strict(subc_args))
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let mut duplicates = 0;
+                let mut collisions = Vec::new();
+                for filename in filenames {
+                    let other = DataSource::new(filename)
+                        .with_strict(strict(subc_args))
+                        .collection()
+                        .expect("Unable to load collection");
+
+                    let report = merge(&mut c, other, subc_args);
+                    for it in report.duplicates() {
+                        eprintln!(
+                            "Duplicated item: {} {} ({} from {})",
+                            it.brand(),
+                            it.item_number(),
+                            duplicate_outcome(&report),
+                            filename
+                        );
+                    }
+                    duplicates += report.duplicates().len();
+                    collisions.extend(report.collisions().iter().map(|it| {
+                        format!(
+                            "Item number {} used by {} and {}",
+                            it.item_number(),
+                            it.brand(),
+                            it.other_brand()
+                        )
+                    }));
+                }
+
+                if !quiet(subc_args) {
+                    if reject_duplicates(subc_args) {
+                        println!("{} duplicate(s) skipped", duplicates);
+                    } else {
+                        println!("{} duplicate(s) kept", duplicates);
+                    }
+                }
+
+                if subc_args.get_flag("report-collisions") {
+                    if collisions.is_empty() {
+                        println!("No item number collisions");
+                    }
+                    for collision in collisions {
+                        println!("{}", collision);
+                    }
+                }
+
+                let table = c.to_table();
+                table.printstd();
+            }
+            Some(("depot", subc_args)) => {
+                let c =
+                    without_sold_items(load_collections(subc_args), subc_args);
+                let mut depot = if subc_args.get_flag("include-trains") {
+                    Depot::from_collection_with_trains(&c)
+                } else {
+                    Depot::from_collection(&c)
+                };
+
+                let mut filter = DepotFilter::new();
+                if let Some(railway) = subc_args.get_one::<String>("railway") {
+                    filter = filter.with_railway(railway);
+                }
+                if let Some(epoch) = subc_args.get_one::<Epoch>("epoch") {
+                    filter = filter.with_epoch(epoch.clone());
+                }
+                if subc_args.get_flag("with-decoder") {
+                    filter = filter.with_decoder(true);
+                } else if subc_args.get_flag("without-decoder") {
+                    filter = filter.with_decoder(false);
+                }
+                if let Some(dcc_interface) =
+                    subc_args.get_one::<DccInterface>("dcc-interface")
+                {
+                    filter = filter.with_dcc_interface(*dcc_interface);
+                }
+                depot.filter(&filter);
+
+                if depot.is_empty() {
+                    println!("no locomotives match");
+                    return;
+                }
+
+                if subc_args.get_flag("unique") {
+                    let duplicates = depot.dedup();
+                    if !quiet(subc_args) {
+                        eprintln!("{} duplicate(s) collapsed", duplicates);
+                    }
+                }
+
+                if subc_args.get_flag("check-addresses") {
+                    let conflicts = depot.address_conflicts();
+                    if conflicts.is_empty() {
+                        println!("No dcc address conflicts");
+                        return;
+                    }
+
+                    for (address, cards) in conflicts {
+                        println!("Address {} is used by:", address);
+                        for card in cards {
+                            println!(
+                                "  - {} ({} {})",
+                                card.road_number(),
+                                card.brand(),
+                                card.item_number()
+                            );
+                        }
+                    }
+                    process::exit(1);
+                }
+
+                if subc_args.get_flag("only-missing-decoder") {
+                    let list = depot.decoder_shopping_list();
+                    if list.total() == 0 {
+                        println!("No locomotives to equip");
+                        return;
+                    }
+
+                    let groups = list
+                        .by_interface()
+                        .iter()
+                        .map(|(dcc, cards)| (dcc.to_string(), cards.as_slice()))
+                        .chain(std::iter::once((
+                            String::from("interface unknown — check model"),
+                            list.unknown_socket(),
+                        )))
+                        .filter(|(_, cards)| !cards.is_empty());
+                    for (group, cards) in groups {
+                        println!("{} ({})", group, cards.len());
+                        for card in cards {
+                            println!(
+                                "  - {} {} ({} {})",
+                                card.class_name(),
+                                card.road_number(),
+                                card.brand(),
+                                card.item_number()
+                            );
+                        }
+                    }
+                    println!("Total: {} decoder(s) to buy", list.total());
+                    return;
+                }
+
+                if subc_args.get_flag("summary") {
+                    let summary = depot.decoder_summary();
+                    if summary.total() == 0 {
+                        println!("No locomotives to equip");
+                        return;
+                    }
+
+                    for (dcc_interface, count) in summary.by_interface() {
+                        println!("{}: {} to equip", dcc_interface, count);
+                    }
+                    if summary.unknown_socket() > 0 {
+                        println!(
+                            "unknown socket: {} to equip",
+                            summary.unknown_socket()
+                        );
+                    }
+                    return;
+                }
+
+                if let Some(roster_file) =
+                    subc_args.get_one::<String>("export-jmri")
+                {
+                    let owner = subc_args
+                        .get_one::<String>("owner")
+                        .map(|s| s.as_str())
+                        .unwrap_or_default();
+
+                    let file = File::create(roster_file)
+                        .expect("Unable to create the roster file");
+                    export::jmri::write_roster(
+                        BufWriter::new(file),
+                        depot.locomotives(),
+                        owner,
+                    )
+                    .expect("Error during JMRI roster export");
+
+                    println!(
+                        "{} locomotive(s) exported to {}",
+                        depot.len(),
+                        roster_file
+                    );
+                    return;
+                }
+
+                write_depot(&mut io::stdout(), &depot, quiet(subc_args))
+                    .expect("Unable to print the depot");
+            }
+            Some(("roster", subc_args)) => {
+                let c = load_collections(subc_args);
+                let mut roster = Roster::from_collection(&c);
+                roster.filter_by_category(&categories(subc_args));
+
+                write_roster(&mut io::stdout(), &roster, quiet(subc_args))
+                    .expect("Unable to print the roster");
+            }
+            _ => {}
+        },
+        Some(("wishlist", cmd_args)) => match cmd_args.subcommand() {
+            Some(("list", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("wishlist file is required");
+
+                let data_source = DataSource::new(filename);
+                let mut wish_list = data_source
+                    .wish_list()
+                    .expect("Unable to load the wishlist");
+                let categories = categories(subc_args);
+                let availabilities: Vec<Availability> = subc_args
+                    .get_many::<Availability>("availability")
+                    .map(|values| values.copied().collect())
+                    .unwrap_or_default();
+                let selected = |it: &&WishListItem| {
+                    matches_category(&categories, it.catalog_item())
+                        && (availabilities.is_empty()
+                            || it
+                                .availability()
+                                .is_some_and(|a| availabilities.contains(&a)))
+                };
+
+                if !quiet(subc_args) {
+                    println!(
+                        "{} (version {}, modified at {})",
+                        wish_list.name(),
+                        wish_list.version(),
+                        wish_list.modified_date().format("%Y-%m-%d %H:%M:%S")
+                    );
+                }
+
+                if let Some(priority) = subc_args.get_one::<String>("priority")
+                {
+                    let priority = match priority.parse::<Priority>() {
+                        Ok(priority) => priority,
+                        Err(e) => {
+                            eprintln!("{}", e);
+                            process::exit(1);
+                        }
+                    };
+
+                    wish_list.sort_items();
+                    let table = tables::wish_list_items_table(
+                        wish_list.items_by_priority(priority).filter(selected),
+                        description_width(subc_args),
+                    );
+                    table.printstd();
+                } else if subc_args.get_one::<String>("group-by").is_some() {
+                    wish_list.sort_items();
+                    for priority in Priority::values() {
+                        let items: Vec<&WishListItem> = wish_list
+                            .items_by_priority(priority)
+                            .filter(selected)
+                            .collect();
+                        let min = or_exit(Price::try_sum(
+                            items
+                                .iter()
+                                .filter_map(|it| it.latest_price_range())
+                                .map(|(min, _)| min.price().clone()),
+                        ));
+                        let max = or_exit(Price::try_sum(
+                            items
+                                .iter()
+                                .filter_map(|it| it.latest_price_range())
+                                .map(|(_, max)| max.price().clone()),
+                        ));
+
+                        println!(
+                            "{} priority ({} item(s))",
+                            priority,
+                            items.len()
+                        );
+                        let table = tables::wish_list_items_table(
+                            items,
+                            description_width(subc_args),
+                        );
+                        table.printstd();
+                        println!("Subtotal: from {} to {}", min, max);
+                        println!();
+                    }
+                } else {
+                    wish_list.sort_items();
+                    let table = tables::wish_list_items_table(
+                        wish_list.get_items().iter().filter(selected),
+                        description_width(subc_args),
+                    );
+                    table.printstd();
+                }
+            }
+            Some(("budget", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("wishlist file is required");
+
+                let data_source = DataSource::new(filename);
+                let wish_list = data_source
+                    .wish_list()
+                    .expect("Unable to load the wishlist");
+
+                if wish_list.is_empty() {
+                    println!("Wish list is empty");
+                    return;
+                }
+
+                let pricing = *subc_args
+                    .get_one::<PricingStrategy>("pricing")
+                    .expect("pricing has a default value");
+
+                if let Some(limit) = subc_args.get_one::<Price>("limit") {
+                    let plan = or_exit(BudgetPlan::from_wish_list(
+                        &wish_list, limit, pricing,
+                    ));
+                    plan.to_table().printstd();
+                    return;
+                }
+
+                let budget =
+                    or_exit(WishListBudget::from_wish_list_with_pricing(
+                        &wish_list,
+                        pricing,
+                        subc_args.get_flag("include-unavailable"),
+                    ));
+
+                if !subc_args.get_flag("plain") {
+                    budget.to_table().printstd();
+                    print_unavailable(&budget);
+                    return;
+                }
+
+                // the same columns as the table: the selected strategy is
+                // always included, so that its total is the overall budget
+                let mut pricings =
+                    vec![PricingStrategy::Min, PricingStrategy::Max];
+                if budget.pricing() == PricingStrategy::Average {
+                    pricings.push(PricingStrategy::Average);
+                }
+
+                print!("{:10}", "Priority");
+                for pricing in &pricings {
+                    print!(" {:>12}", pricing.to_string().to_title_case());
+                }
+                println!();
+                let mut totals = vec![Decimal::ZERO; pricings.len()];
+                for (priority, label) in [
+                    (Priority::High, "High......"),
+                    (Priority::Normal, "Normal...."),
+                    (Priority::Low, "Low......."),
+                ] {
+                    print!("{}", label);
+                    for (pricing, total) in
+                        pricings.iter().zip(totals.iter_mut())
+                    {
+                        let value = budget.by_priority_with(priority, *pricing);
+                        *total += value;
+                        print!(" {:>8} {}", value, budget.currency());
+                    }
+                    println!();
+                }
+                print!("Total.....");
+                for total in totals {
+                    print!(" {:>8} {}", total, budget.currency());
+                }
+                println!();
+                print_unavailable(&budget);
+            }
+            Some(("stats", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("wishlist file is required");
+
+                let data_source = DataSource::new(filename);
+                let wish_list = data_source
+                    .wish_list()
+                    .expect("Unable to load the wishlist");
+
+                if wish_list.is_empty() {
+                    println!("Wish list is empty");
+                    return;
+                }
+
+                if let Some("shop") =
+                    subc_args.get_one::<String>("group-by").map(|s| s.as_str())
+                {
+                    let stats = or_exit(ShopStats::from_wish_list(&wish_list));
+                    write_shop_stats(stats, subc_args);
+                    return;
+                }
+
+                let stats = or_exit(WishListStats::from_wish_list(&wish_list));
+                stats.to_table().printstd();
+            }
+            Some(("prices", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("wishlist file is required");
+                let item_number = subc_args
+                    .get_one::<String>("item-number")
+                    .expect("item number is required");
+
+                let wish_list = DataSource::new(filename)
+                    .wish_list()
+                    .expect("Unable to load the wishlist");
+                let items: Vec<&WishListItem> = wish_list
+                    .find_by_item_number(item_number)
+                    .into_iter()
+                    .filter(|it| {
+                        subc_args.get_one::<String>("brand").map_or(true, |b| {
+                            it.catalog_item()
+                                .brand()
+                                .name()
+                                .eq_ignore_ascii_case(b)
+                        })
+                    })
+                    .collect();
+
+                let item = match items.as_slice() {
+                    [item] => item,
+                    [] => {
+                        eprintln!(
+                            "Item number {} not found in the wish list",
+                            item_number
+                        );
+                        process::exit(1);
+                    }
+                    _ => {
+                        eprintln!(
+                            "Item number {} is used by more than one brand, please provide the --brand",
+                            item_number
+                        );
+                        process::exit(1);
+                    }
+                };
+
+                if item.prices().is_empty() {
+                    println!(
+                        "No prices for {} {}",
+                        item.catalog_item().brand(),
+                        item_number
+                    );
+                    return;
+                }
+                tables::price_history_table(item).printstd();
+                if let Some((first, latest)) = item.price_trend() {
+                    let difference =
+                        latest.price().amount() - first.price().amount();
+                    let sign =
+                        if difference > Decimal::ZERO { "+" } else { "" };
+                    println!(
+                        "Trend: from {} to {} ({}{})",
+                        first.price(),
+                        latest.price(),
+                        sign,
+                        Price::new(difference, latest.price().currency())
+                    );
+                }
+            }
+            Some(("check", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("wishlist file is required");
+                let collection_filename = subc_args
+                    .get_one::<String>("collection")
+                    .expect("collection file is required");
+
+                let wish_list = DataSource::new(filename)
+                    .wish_list()
+                    .expect("Unable to load the wishlist");
+                let collection = DataSource::new(collection_filename)
+                    .collection()
+                    .expect("Unable to load collection");
+
+                let matches = wish_list.already_owned(
+                    &collection,
+                    subc_args.get_flag("by-road-number"),
+                );
+                if matches.is_empty() {
+                    println!("No wish list items already owned");
+                    return;
+                }
+
+                tables::owned_matches_table(&matches).printstd();
+                process::exit(1);
+            }
+            Some(("purchase", subc_args)) => {
+                let filename = subc_args
+                    .get_one::<String>("file")
+                    .expect("wishlist file is required");
+                let collection_filename = subc_args
+                    .get_one::<String>("collection")
+                    .expect("collection file is required");
+                let item_number = subc_args
+                    .get_one::<String>("item-number")
+                    .expect("item number is required");
+                let shop = subc_args
+                    .get_one::<String>("shop")
+                    .expect("shop is required");
+                let date = *subc_args
+                    .get_one::<NaiveDate>("date")
+                    .expect("date is required");
+                let price = subc_args
+                    .get_one::<Price>("price")
+                    .cloned()
+                    .expect("price is required");
+
+                let purchased_info = PurchasedInfo::new(shop, date, price);
+                let data_source = DataSource::new(filename);
+
+                let brand = match subc_args.get_one::<String>("brand") {
+                    Some(brand) => brand.to_owned(),
+                    None => {
+                        let wish_list = data_source
+                            .wish_list()
+                            .expect("Unable to load the wishlist");
+                        let items = wish_list.find_by_item_number(item_number);
+                        match items.as_slice() {
+                            [item] => {
+                                item.catalog_item().brand().name().to_owned()
+                            }
+                            [] => {
+                                eprintln!(
+                                    "Item number {} not found in the wish list",
+                                    item_number
+                                );
+                                process::exit(1);
+                            }
+                            _ => {
+                                eprintln!(
+                                    "Item number {} is used by more than one brand, please provide the --brand",
+                                    item_number
+                                );
+                                process::exit(1);
+                            }
+                        }
+                    }
+                };
+                let brand = brand.as_str();
+
+                if let Err(e) = data_source.purchase(
+                    &DataSource::new(collection_filename),
+                    brand,
+                    item_number,
+                    &purchased_info,
+                ) {
+                    eprintln!("Unable to purchase the item: {}", e);
+                    process::exit(1);
+                }
+
+                println!(
+                    "{} {} moved to {}",
+                    brand, item_number, collection_filename
+                );
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+}
+
+/// Loads the collections from all the files, merging them in a single one.
+/// The items already included are reported with a warning, and skipped only
+/// with `--reject-duplicates`.
+fn load_collections(args: &ArgMatches) -> Collection {
+    let mut filenames = args
+        .get_many::<String>("file")
+        .expect("collection file is required");
+
+    let first = filenames.next().expect("collection file is required");
+    let mut c = DataSource::new(first)
+        .with_strict(strict(args))
+        .collection()
+        .expect("Unable to load collection");
+
+    for filename in filenames {
+        let other = DataSource::new(filename)
+            .with_strict(strict(args))
+            .collection()
+            .expect("Unable to load collection");
+
+        let report = merge(&mut c, other, args);
+        for it in report.duplicates() {
+            log::warn!(
+                "Duplicated item: {} {} ({} from {})",
+                it.brand(),
+                it.item_number(),
+                duplicate_outcome(&report),
+                filename
+            );
+        }
+        for it in report.collisions() {
+            log::warn!(
+                "Item number {} used by both {} and {} (kept both, from {})",
+                it.item_number(),
+                it.brand(),
+                it.other_brand(),
+                filename
+            );
+        }
+    }
+
+    c
+}
+
+/// Tells whether the duplicated items were kept or skipped by the merge
+fn duplicate_outcome(report: &MergeReport) -> &'static str {
+    if report.duplicates_skipped() {
+        "skipped"
+    } else {
+        "kept"
+    }
+}
+
+fn reject_duplicates(args: &ArgMatches) -> bool {
+    args.get_flag("reject-duplicates")
+}
+
+/// Merges the other collection, skipping the duplicated items only when
+/// `--reject-duplicates` is set
+fn merge(
+    c: &mut Collection,
+    other: Collection,
+    args: &ArgMatches,
+) -> MergeReport {
+    if reject_duplicates(args) {
+        c.merge_rejecting_duplicates(other)
+    } else {
+        c.merge(other)
+    }
+}
+
+/// Returns the purchase date, shop and price on a single line
+fn purchase_line(purchased_info: &PurchasedInfo) -> String {
+    format!(
+        "{} {} {}",
+        purchased_info.purchased_date().format("%Y-%m-%d"),
+        purchased_info.shop(),
+        purchased_info
+            .price()
+            .map(|p| p.to_string())
+            .unwrap_or_else(|| String::from("without price"))
+    )
+}
+
+/// Parses a category multiplier, like "L=1.2"
+fn parse_multiplier(value: &str) -> Option<(Category, Decimal)> {
+    let (symbol, factor) = value.split_once('=')?;
+
+    let mut chars = symbol.trim().chars();
+    let category = chars.next().and_then(Category::from_symbol)?;
+    if chars.next().is_some() {
+        return None;
+    }
+
+    let factor = factor.trim().parse::<Decimal>().ok()?;
+    Some((category, factor))
+}
+
+/// Returns the value, or exits printing the error (like for prices in
+/// different currencies that cannot be summed)
+fn or_exit<T, E: std::fmt::Display>(result: Result<T, E>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
+}
+
+/// Checks whether the summary lines before the tables must be omitted
+fn quiet(args: &ArgMatches) -> bool {
+    args.get_flag("quiet")
+}
+
+/// Checks whether the collection files must be validated before loading them
+fn strict(args: &ArgMatches) -> bool {
+    args.get_flag("strict")
+}
+
+/// The categories to filter the listed items (empty means every category)
+fn categories(args: &ArgMatches) -> Vec<Category> {
+    args.get_many::<Category>("category")
+        .map(|values| values.copied().collect())
+        .unwrap_or_default()
+}
+
+fn matches_category(categories: &[Category], item: &CatalogItem) -> bool {
+    categories.is_empty() || categories.contains(&item.category())
+}
+
+fn matches_epoch(epoch: Option<&Epoch>, item: &CatalogItem) -> bool {
+    match epoch {
+        Some(epoch) => item.overlaps_epoch(epoch),
+        None => true,
+    }
+}
+
+/// Checks whether the price amount is within the bounds, the missing bounds
+/// are open-ended and the items without price match only when both are missing
+fn matches_price(
+    min: Option<&Price>,
+    max: Option<&Price>,
+    price: Option<&Price>,
+) -> bool {
+    match price {
+        Some(price) => {
+            let amount = price.amount();
+            !matches!(min, Some(min) if amount < min.amount())
+                && !matches!(max, Some(max) if amount > max.amount())
+        }
+        None => min.is_none() && max.is_none(),
+    }
+}
+
+/// Checks whether the sold items must be included
+fn include_sold(args: &ArgMatches) -> bool {
+    args.get_flag("include-sold")
+}
+
+/// Removes the sold items from the collection, unless `--include-sold` is set
+fn without_sold_items(collection: Collection, args: &ArgMatches) -> Collection {
+    if include_sold(args) {
+        collection
+    } else {
+        collection.without_sold()
+    }
+}
+
+/// Keeps only the items purchased within the `--from` and `--to` dates (both
+/// included), exiting when the dates are not a valid range
+fn purchased_within(collection: Collection, args: &ArgMatches) -> Collection {
+    let from = args.get_one::<NaiveDate>("from");
+    let to = args.get_one::<NaiveDate>("to");
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            eprintln!(
+                "The from date ({}) cannot be after the to date ({})",
+                from, to
+            );
+            process::exit(1);
+        }
+    }
+
+    collection.filtered(|it| {
+        matches_date(from, to, it.purchased_info().purchased_date())
+    })
+}
+
+/// Checks whether the date is within the bounds (both included), the missing
+/// bounds are open-ended
+fn matches_date(
+    from: Option<&NaiveDate>,
+    to: Option<&NaiveDate>,
+    date: &NaiveDate,
+) -> bool {
+    !matches!(from, Some(from) if date < from)
+        && !matches!(to, Some(to) if date > to)
+}
+
+fn description_width(args: &ArgMatches) -> usize {
+    args.get_one::<usize>("description-width")
+        .copied()
+        .unwrap_or(tables::DEFAULT_DESCRIPTION_WIDTH)
+}
+
+/// Prints the items sold out or discontinued, left out of the budget
+fn print_unavailable(budget: &WishListBudget) {
+    let unavailable = budget.unavailable();
+    if unavailable.number_of_items() > 0 {
+        println!(
+            "Sold out or discontinued: {} item(s), from {} to {}, not included",
+            unavailable.number_of_items(),
+            Price::new(unavailable.min_budget(), budget.currency()),
+            Price::new(unavailable.max_budget(), budget.currency())
+        );
+    }
+}
+
+/// Writes the number of items and rolling stocks, one `key: value` per line
+fn write_count<W: Write>(
+    out: &mut W,
+    count: &CollectionCount,
+) -> io::Result<()> {
+    writeln!(out, "items: {}", count.number_of_items())?;
+    writeln!(out, "rolling-stocks: {}", count.number_of_rolling_stocks())?;
+    for (category, count) in count.by_category() {
+        writeln!(out, "{}: {}", category.name(), count)?;
+    }
+    Ok(())
+}
+
+/// Writes the added, removed and changed items, in three labeled sections
+fn write_diff<W: Write>(out: &mut W, diff: &CollectionDiff) -> io::Result<()> {
+    writeln!(out, "Added ({}):", diff.added().len())?;
+    for it in diff.added() {
+        writeln!(out, "  + {}", it.catalog_item())?;
+    }
+    writeln!(out, "Removed ({}):", diff.removed().len())?;
+    for it in diff.removed() {
+        writeln!(out, "  - {}", it.catalog_item())?;
+    }
+    writeln!(out, "Changed ({}):", diff.changed().len())?;
+    for it in diff.changed() {
+        writeln!(out, "  ~ {}", it.new_item().catalog_item())?;
+        for change in it.changes() {
+            writeln!(out, "      {}", change)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_stats<W: Write>(
+    out: &mut W,
+    stats: &CollectionStats,
+    quiet: bool,
+) -> io::Result<()> {
+    if !quiet {
+        let price = |amount: Decimal| Price::new(amount, stats.currency());
+        writeln!(out, "Total value........... {}", price(stats.total_value()))?;
+        if let (Some(average), Some(median)) =
+            (stats.average_price(), stats.median_price())
+        {
+            writeln!(out, "Average price......... {}", price(average))?;
+            writeln!(out, "Median price.......... {}", price(median))?;
+        }
+        if let (Some(min), Some(max)) = (stats.min_price(), stats.max_price()) {
+            writeln!(
+                out,
+                "Min/max price......... {:.2} / {}",
+                price(min).rounded().amount(),
+                price(max)
+            )?;
+        }
+        writeln!(out, "Rolling stocks/sets... {}", stats.size())?;
+        writeln!(
+            out,
+            "Total length.......... {:.2} m",
+            Decimal::new(i64::from(stats.total_length_mm()), 3)
+        )?;
+        if let Some(average) = stats.average_length_mm() {
+            writeln!(
+                out,
+                "Average length........ {:.2} m",
+                average / Decimal::from(1000)
+            )?;
+        }
+        if let Some((description, length)) = stats.longest_item() {
+            writeln!(
+                out,
+                "Longest item.......... {:.2} m ({})",
+                Decimal::new(i64::from(length.value()), 3),
+                description
+            )?;
+        }
+        if stats.number_of_items_without_price() > 0 {
+            writeln!(
+                out,
+                "Without price......... {} item(s), not included in the values",
+                stats.number_of_items_without_price()
+            )?;
+        }
+    }
+
+    stats.to_table().print(out)?;
+    Ok(())
+}
+
+fn write_valuation<W: Write>(
+    out: &mut W,
+    valuation: &CollectionValuation,
+    quiet: bool,
+) -> io::Result<()> {
+    if !quiet {
+        writeln!(
+            out,
+            "Total value ({})... {}",
+            valuation.method(),
+            Price::new(valuation.total_value(), valuation.currency())
+        )?;
+    }
+
+    valuation.to_table().print(out)?;
+    Ok(())
+}
+
+fn write_depot<W: Write>(
+    out: &mut W,
+    depot: &Depot,
+    quiet: bool,
+) -> io::Result<()> {
+    if !quiet {
+        writeln!(out, "{} locomotive(s)", depot.len())?;
+    }
+
+    depot.to_table().print(out)?;
+    Ok(())
+}
+
+/// Prints the shop statistics, sorted as requested by the `--sort` option
+fn write_shop_stats(mut stats: ShopStats, args: &ArgMatches) {
+    if let Some("total") = args.get_one::<String>("sort").map(|s| s.as_str()) {
+        stats.sort_by_total();
+    }
+    stats.to_table().printstd();
+}
+
+fn write_roster<W: Write>(
+    out: &mut W,
+    roster: &Roster,
+    quiet: bool,
+) -> io::Result<()> {
+    if !quiet {
+        writeln!(out, "{} rolling stock(s)", roster.len())?;
+    }
+
+    roster.to_table().print(out)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use railists::domain::collecting::collections::Collection;
+
+    fn load_collection() -> Collection {
+        DataSource::new("tests/data/collection.yaml")
+            .collection()
+            .expect("Unable to load collection")
+    }
+
+    fn output<F>(f: F) -> String
+    where
+        F: FnOnce(&mut Vec<u8>) -> io::Result<()>,
+    {
+        let mut out = Vec::new();
+        f(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn it_should_match_prices_within_the_range() {
+        let price = |s: &str| s.parse::<Price>().unwrap();
+        let min = price("100");
+        let max = price("300,00");
+
+        assert!(matches_price(Some(&min), Some(&max), Some(&price("100"))));
+        assert!(matches_price(Some(&min), Some(&max), Some(&price("300"))));
+        assert!(!matches_price(
+            Some(&min),
+            Some(&max),
+            Some(&price("99,99"))
+        ));
+        assert!(!matches_price(
+            Some(&min),
+            Some(&max),
+            Some(&price("300,01"))
+        ));
+        assert!(matches_price(None, Some(&max), Some(&price("10"))));
+        assert!(matches_price(Some(&min), None, Some(&price("1000"))));
+        assert!(matches_price(None, None, Some(&price("0"))));
+    }
+
+    #[test]
+    fn it_should_match_dates_within_the_range() {
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let from = date("2022-01-01");
+        let to = date("2022-12-31");
+
+        assert!(matches_date(Some(&from), Some(&to), &date("2022-01-01")));
+        assert!(matches_date(Some(&from), Some(&to), &date("2022-12-31")));
+        assert!(!matches_date(Some(&from), Some(&to), &date("2021-12-31")));
+        assert!(!matches_date(Some(&from), Some(&to), &date("2023-01-01")));
+        assert!(matches_date(None, Some(&to), &date("2010-01-01")));
+        assert!(matches_date(Some(&from), None, &date("2030-01-01")));
+    }
+
+    #[test]
+    fn it_should_print_the_summary_lines_before_the_tables() {
+        let c = load_collection();
+
+        let stats = output(|out| {
+            write_stats(
+                out,
+                &CollectionStats::from_collection(&c).unwrap(),
+                false,
+            )
+        });
+        assert!(stats.starts_with("Total value........... 424.50 EUR"));
+        assert!(stats.contains("Median price.......... 150.00 EUR"));
+        assert!(stats.contains("Min/max price......... 45.50 / 229.00 EUR"));
+
+        let depot =
+            output(|out| write_depot(out, &Depot::from_collection(&c), false));
+        assert!(depot.starts_with("2 locomotive(s)"));
+
+        let roster = output(|out| {
+            write_roster(out, &Roster::from_collection(&c), false)
+        });
+        assert!(roster.starts_with("3 rolling stock(s)"));
+    }
+
+    #[test]
+    fn it_should_omit_the_summary_lines_when_quiet() {
+        let c = load_collection();
+
+        let stats = output(|out| {
+            write_stats(
+                out,
+                &CollectionStats::from_collection(&c).unwrap(),
+                true,
+            )
+        });
+        assert!(!stats.contains("Total value"));
+        assert!(!stats.contains("Rolling stocks/sets"));
+        assert!(stats.starts_with("+--"));
+
+        let depot =
+            output(|out| write_depot(out, &Depot::from_collection(&c), true));
+        assert!(!depot.contains("locomotive(s)"));
+        assert!(depot.starts_with("+--"));
+
+        let valuation = output(|out| {
+            write_valuation(
+                out,
+                &CollectionValuation::from_collection(&c, &PurchaseValuation)
+                    .unwrap(),
+                true,
+            )
+        });
+        assert!(!valuation.contains("Total value"));
+    }
+}
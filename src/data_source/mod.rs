@@ -1,36 +1,215 @@
+mod validation;
 mod yaml_collections;
 mod yaml_rolling_stocks;
 mod yaml_wish_lists;
 
+pub use validation::ValidationError;
+pub use yaml_collections::parse_purchase_date;
+
 use crate::domain::collecting::{
-    collections::Collection, wish_lists::WishList,
+    collections::{Collection, PurchasedInfo},
+    wish_lists::WishList,
 };
+use anyhow::Context;
+use chrono::Local;
+use serde::Serialize;
 use std::convert::TryFrom;
 use std::fs;
-use yaml_collections::YamlCollection;
+use std::io::{self, Read};
+use yaml_collections::{YamlCollection, YamlCollectionItem, YamlPurchaseInfo};
 use yaml_wish_lists::YamlWishList;
 
 #[derive(Debug)]
 pub struct DataSource {
     filename: String,
+    strict: bool,
 }
 
 impl DataSource {
+    const MODIFIED_AT_FORMAT: &'static str = "%Y-%m-%d %H:%M:%S";
+
+    /// The file name to read the document from the standard input.
+    pub const STDIN: &'static str = "-";
+
     pub fn new(filename: &str) -> Self {
         DataSource {
             filename: filename.to_owned(),
+            strict: false,
         }
     }
 
+    /// When strict, the collection files are fully validated before loading
+    /// them, failing with every problem found instead of the first one.
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
     pub fn wish_list(&self) -> anyhow::Result<WishList> {
-        let contents = fs::read_to_string(self.filename.clone())?;
-        let yaml_wish_list: YamlWishList = serde_yaml::from_str(&contents)?;
-        WishList::try_from(yaml_wish_list)
+        let load = || {
+            let contents = self.read_to_string()?;
+            let yaml_wish_list: YamlWishList = serde_yaml::from_str(&contents)?;
+            WishList::try_from(yaml_wish_list)
+        };
+
+        load().with_context(|| {
+            format!("Unable to load the wish list from {}", self.source_name())
+        })
     }
 
+    /// Loads the collection from the file, or from the standard input when the
+    /// file name is `-`.
     pub fn collection(&self) -> anyhow::Result<Collection> {
-        let contents = fs::read_to_string(self.filename.clone())?;
-        let yaml_collection: YamlCollection = serde_yaml::from_str(&contents)?;
+        if self.is_stdin() {
+            let contents = self.read_to_string()?;
+            return self.collection_from_reader(contents.as_bytes());
+        }
+
+        let file = fs::File::open(&self.filename).with_context(|| {
+            format!("Unable to load the collection from {}", self.source_name())
+        })?;
+        self.collection_from_reader(io::BufReader::new(file))
+    }
+
+    /// Loads the collection from the reader, the file name is used only to
+    /// report the validation errors.
+    pub fn collection_from_reader<R: io::Read>(
+        &self,
+        reader: R,
+    ) -> anyhow::Result<Collection> {
+        if !self.strict {
+            return Collection::from_yaml_reader(reader);
+        }
+
+        let yaml_collection: YamlCollection = serde_yaml::from_reader(reader)?;
+        let errors = validation::validate_collection(&yaml_collection);
+        if !errors.is_empty() {
+            let messages: Vec<String> =
+                errors.iter().map(|e| e.to_string()).collect();
+            bail!(
+                "{} validation error(s) in {}:\n{}",
+                errors.len(),
+                self.source_name(),
+                messages.join("\n")
+            );
+        }
         Collection::try_from(yaml_collection)
     }
+
+    /// Checks the collection file, returning every problem found in its elements.
+    pub fn validate_collection(&self) -> anyhow::Result<Vec<ValidationError>> {
+        let contents = self.read_to_string()?;
+        let yaml_collection: YamlCollection = serde_yaml::from_str(&contents)?;
+        Ok(validation::validate_collection(&yaml_collection))
+    }
+
+    /// Moves the item with the given brand and item number from this wish list
+    /// into the collection, bumping the version for both files.
+    ///
+    /// The two files are updated all-or-nothing: both documents are validated
+    /// and written to temporary files before any of the original files is
+    /// replaced, and the collection is restored when the wish list cannot be
+    /// replaced.
+    ///
+    /// Both files are written again from their parsed documents, so the
+    /// comments are dropped and the keys follow the order of the document
+    /// fields.
+    pub fn purchase(
+        &self,
+        collection: &DataSource,
+        brand: &str,
+        item_number: &str,
+        purchased_info: &PurchasedInfo,
+    ) -> anyhow::Result<()> {
+        let contents = fs::read_to_string(self.filename.clone())?;
+        let mut yaml_wish_list: YamlWishList = serde_yaml::from_str(&contents)?;
+        let collection_contents =
+            fs::read_to_string(collection.filename.clone())?;
+        let mut yaml_collection: YamlCollection =
+            serde_yaml::from_str(&collection_contents)?;
+
+        let item = yaml_wish_list.remove_item(brand, item_number).ok_or_else(
+            || {
+                anyhow!(
+                    "Item {} {} not found in the wish list",
+                    brand,
+                    item_number
+                )
+            },
+        )?;
+        yaml_collection
+            .elements
+            .push(YamlCollectionItem::from_wish_list_item(
+                item,
+                YamlPurchaseInfo::from(purchased_info),
+            ));
+
+        let modified_at = Local::now()
+            .format(DataSource::MODIFIED_AT_FORMAT)
+            .to_string();
+        yaml_wish_list.version = yaml_wish_list.version.saturating_add(1);
+        yaml_wish_list.modified_at = modified_at.clone();
+        yaml_collection.version = yaml_collection.version.saturating_add(1);
+        yaml_collection.modified_at = modified_at;
+
+        WishList::try_from(yaml_wish_list.clone())?;
+        Collection::try_from(yaml_collection.clone())?;
+
+        let collection_tmp = collection.write_tmp(&yaml_collection)?;
+        let wish_list_tmp = match self.write_tmp(&yaml_wish_list) {
+            Ok(tmp_filename) => tmp_filename,
+            Err(e) => {
+                let _ = fs::remove_file(&collection_tmp);
+                return Err(e);
+            }
+        };
+
+        if let Err(e) = fs::rename(&collection_tmp, &collection.filename) {
+            let _ = fs::remove_file(&collection_tmp);
+            let _ = fs::remove_file(&wish_list_tmp);
+            return Err(e.into());
+        }
+        if let Err(e) = fs::rename(&wish_list_tmp, &self.filename) {
+            let _ = fs::remove_file(&wish_list_tmp);
+            fs::write(&collection.filename, collection_contents)?;
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    fn is_stdin(&self) -> bool {
+        self.filename == DataSource::STDIN
+    }
+
+    /// The file name, or "standard input" when reading from it
+    fn source_name(&self) -> &str {
+        if self.is_stdin() {
+            "standard input"
+        } else {
+            &self.filename
+        }
+    }
+
+    /// Reads the whole file, or the whole standard input when the file name is `-`.
+    fn read_to_string(&self) -> io::Result<String> {
+        if self.is_stdin() {
+            let mut contents = String::new();
+            io::stdin().read_to_string(&mut contents)?;
+            Ok(contents)
+        } else {
+            fs::read_to_string(&self.filename)
+        }
+    }
+
+    /// Writes the document to a temporary file next to the original one,
+    /// returning the temporary file name.
+    fn write_tmp<T: Serialize>(&self, document: &T) -> anyhow::Result<String> {
+        let contents = serde_yaml::to_string(document)?;
+        let tmp_filename = format!("{}.tmp", self.filename);
+        if let Err(e) = fs::write(&tmp_filename, contents) {
+            let _ = fs::remove_file(&tmp_filename);
+            return Err(e.into());
+        }
+        Ok(tmp_filename)
+    }
 }
@@ -0,0 +1,207 @@
+//! This module contains the validation for the collection files, it collects every
+//! problem in the file instead of stopping at the first one.
+use super::yaml_collections::{
+    parse_purchase_date, YamlCollection, YamlCollectionItem,
+};
+use super::yaml_rolling_stocks::{parse_category, YamlRollingStock};
+use crate::domain::catalog::{
+    catalog_items::{DeliveryDate, ItemNumber, PowerMethod},
+    categories::{
+        Category, FreightCarType, LocomotiveType, PassengerCarType, TrainType,
+    },
+    railways::Railway,
+    rolling_stocks::{
+        Control, DccAddress, DccInterface, Epoch, LengthOverBuffer,
+        ServiceLevel,
+    },
+    scales::Scale,
+};
+use crate::domain::collecting::{collections::Condition, Price};
+
+use chrono::NaiveDateTime;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A problem found validating a collection file.
+#[derive(Debug, PartialEq, Eq, Error)]
+#[error("element #{index}, field '{field}': {message}")]
+pub struct ValidationError {
+    index: usize,
+    field: String,
+    message: String,
+}
+
+impl ValidationError {
+    pub fn new(index: usize, field: &str, message: &str) -> Self {
+        ValidationError {
+            index,
+            field: field.to_owned(),
+            message: message.to_owned(),
+        }
+    }
+
+    /// The (1-based) index for the collection element
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The field name, nested fields are separated by '.'
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Returns all the problems found in the collection document, the document-level
+/// fields are reported with index 0.
+pub fn validate_collection(
+    collection: &YamlCollection,
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    if let Err(e) = NaiveDateTime::parse_from_str(
+        &collection.modified_at,
+        "%Y-%m-%d %H:%M:%S",
+    ) {
+        errors.push(ValidationError::new(0, "modifiedAt", &e.to_string()));
+    }
+
+    for (ind, item) in collection.elements.iter().enumerate() {
+        validate_item(ind + 1, item, &mut errors);
+    }
+
+    errors
+}
+
+fn validate_item(
+    index: usize,
+    item: &YamlCollectionItem,
+    errors: &mut Vec<ValidationError>,
+) {
+    let mut check = |field: &str, result: Result<(), String>| {
+        if let Err(message) = result {
+            errors.push(ValidationError::new(index, field, &message));
+        }
+    };
+
+    check("itemNumber", parse(ItemNumber::new(&item.item_number)));
+    check(
+        "powerMethod",
+        parse(item.power_method.parse::<PowerMethod>()),
+    );
+    check(
+        "scale",
+        Scale::from_name(&item.scale)
+            .map(|_| ())
+            .ok_or_else(|| format!("Unknown scale '{}'", item.scale)),
+    );
+    if let Some(delivery_date) = &item.delivery_date {
+        check("deliveryDate", parse(delivery_date.parse::<DeliveryDate>()));
+    }
+
+    if let Some(replacement_value) = &item.replacement_value {
+        check(
+            "replacementValue",
+            parse(replacement_value.parse::<Price>()),
+        );
+    }
+
+    let purchase = &item.purchase_info;
+    check(
+        "purchaseInfo.date",
+        parse(parse_purchase_date(&purchase.date)),
+    );
+    if let Some(price) = &purchase.price {
+        check("purchaseInfo.price", parse(price.parse::<Price>()));
+    }
+    if let Some(condition) = &purchase.condition {
+        check(
+            "purchaseInfo.condition",
+            parse(condition.parse::<Condition>()),
+        );
+    }
+
+    if let Some(sold_info) = &item.sold_info {
+        check("soldInfo.date", parse(parse_purchase_date(&sold_info.date)));
+        if let Some(price) = &sold_info.price {
+            check("soldInfo.price", parse(price.parse::<Price>()));
+        }
+    }
+
+    for (ind, rs) in item.rolling_stocks.iter().enumerate() {
+        for (field, message) in validate_rolling_stock(rs) {
+            check(&format!("rollingStocks[{}].{}", ind, field), Err(message));
+        }
+    }
+}
+
+fn validate_rolling_stock(
+    rs: &YamlRollingStock,
+) -> Vec<(&'static str, String)> {
+    let mut errors = Vec::new();
+    let mut check = |field: &'static str, result: Result<(), String>| {
+        if let Err(message) = result {
+            errors.push((field, message));
+        }
+    };
+
+    check("epoch", parse(rs.epoch.parse::<Epoch>()));
+    match &rs.country {
+        Some(country) => check(
+            "country",
+            parse(Railway::with_country(&rs.railway, country)),
+        ),
+        None => check("railway", parse(rs.railway.parse::<Railway>())),
+    }
+
+    let sub_category = rs.sub_category.as_deref();
+    match parse_category(&rs.category) {
+        Ok(Category::Locomotives) => check(
+            "subCategory",
+            sub_category
+                .ok_or_else(|| String::from("Locomotive type is required"))
+                .and_then(|it| parse(it.parse::<LocomotiveType>())),
+        ),
+        Ok(Category::Trains) => {
+            if let Some(it) = sub_category {
+                check("subCategory", parse(it.parse::<TrainType>()));
+            }
+        }
+        Ok(Category::PassengerCars) => {
+            if let Some(it) = sub_category {
+                check("subCategory", parse(it.parse::<PassengerCarType>()));
+            }
+        }
+        Ok(Category::FreightCars) => {
+            if let Some(it) = sub_category {
+                check("subCategory", parse(it.parse::<FreightCarType>()));
+            }
+        }
+        Err(e) => check("category", Err(e.to_string())),
+    }
+
+    if let Some(control) = &rs.control {
+        check("control", parse(control.parse::<Control>()));
+    }
+    if let Some(dcc_interface) = &rs.dcc_interface {
+        check("dccInterface", parse(dcc_interface.parse::<DccInterface>()));
+    }
+    if let Some(dcc_address) = rs.dcc_address {
+        check("dccAddress", parse(DccAddress::new(dcc_address)));
+    }
+    if let Some(length) = rs.length {
+        check("length", parse(LengthOverBuffer::try_new(length)));
+    }
+    if let Some(service_level) = &rs.service_level {
+        check("serviceLevel", parse(ServiceLevel::from_str(service_level)));
+    }
+
+    errors
+}
+
+fn parse<T, E: ToString>(result: Result<T, E>) -> Result<(), String> {
+    result.map(|_| ()).map_err(|e| e.to_string())
+}
@@ -1,7 +1,10 @@
+use anyhow::Context;
 use chrono::{NaiveDate, NaiveDateTime};
 use std::convert::TryFrom;
+use std::io::Read;
 
 use super::yaml_rolling_stocks::YamlRollingStock;
+use super::yaml_wish_lists::YamlWishListItem;
 use crate::domain::{
     catalog::{
         brands::Brand,
@@ -10,12 +13,14 @@ use crate::domain::{
         scales::Scale,
     },
     collecting::{
-        collections::{Collection, PurchasedInfo},
+        collections::{
+            Collection, CollectionItem, Condition, PurchasedInfo, SoldInfo,
+        },
         Price,
     },
 };
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct YamlCollection {
     pub version: u8,
     pub description: String,
@@ -24,7 +29,7 @@ pub struct YamlCollection {
     pub elements: Vec<YamlCollectionItem>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct YamlCollectionItem {
     pub brand: String,
     #[serde(rename = "itemNumber")]
@@ -33,20 +38,118 @@ pub struct YamlCollectionItem {
     #[serde(rename = "powerMethod")]
     pub power_method: String,
     pub scale: String,
-    #[serde(rename = "deliveryDate")]
+    #[serde(rename = "deliveryDate", skip_serializing_if = "Option::is_none")]
     pub delivery_date: Option<String>,
     pub count: u8,
     #[serde(rename = "rollingStocks")]
     pub rolling_stocks: Vec<YamlRollingStock>,
     #[serde(rename = "purchaseInfo")]
     pub purchase_info: YamlPurchaseInfo,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<String>,
+    #[serde(
+        rename = "replacementValue",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub replacement_value: Option<String>,
+    #[serde(
+        rename = "soldInfo",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub sold_info: Option<YamlSoldInfo>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct YamlSoldInfo {
+    pub date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buyer: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct YamlPurchaseInfo {
     pub date: String,
-    pub price: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price: Option<String>,
     pub shop: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub condition: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<String>,
+    #[serde(
+        rename = "warrantyMonths",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub warranty_months: Option<u8>,
+}
+
+impl YamlCollectionItem {
+    /// Creates a new collection item for a purchased wish list item
+    pub fn from_wish_list_item(
+        item: YamlWishListItem,
+        purchase_info: YamlPurchaseInfo,
+    ) -> Self {
+        YamlCollectionItem {
+            brand: item.brand,
+            item_number: item.item_number,
+            description: item.description,
+            power_method: item.power_method.to_string(),
+            scale: item.scale,
+            delivery_date: item.delivery_date.map(|dd| dd.to_string()),
+            count: item.count,
+            rolling_stocks: item.rolling_stocks,
+            purchase_info,
+            notes: None,
+            tags: Vec::new(),
+            storage: None,
+            replacement_value: None,
+            sold_info: None,
+        }
+    }
+}
+
+impl From<&PurchasedInfo> for YamlPurchaseInfo {
+    fn from(value: &PurchasedInfo) -> Self {
+        YamlPurchaseInfo {
+            date: value
+                .purchased_date()
+                .format(PURCHASE_DATE_FORMATS[0])
+                .to_string(),
+            price: value.price().map(|p| p.to_string()),
+            shop: value.shop().to_owned(),
+            condition: value.condition().map(|c| c.to_string()),
+            receipt: value.receipt().map(|r| r.to_owned()),
+            warranty_months: value.warranty_months(),
+        }
+    }
+}
+
+/// The accepted formats for the purchase dates, the first one is used on export.
+const PURCHASE_DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%d/%m/%Y", "%d.%m.%Y"];
+
+/// Parses a date, trying each of the accepted purchase date formats in turn.
+pub(crate) fn parse_date(value: &str) -> Option<NaiveDate> {
+    PURCHASE_DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+}
+
+/// Parses a purchase date, trying each of the accepted formats in turn.
+pub fn parse_purchase_date(value: &str) -> anyhow::Result<NaiveDate> {
+    parse_date(value).ok_or_else(|| {
+            anyhow!(
+                "Invalid purchase date '{}' (expected YYYY-MM-DD, DD/MM/YYYY or DD.MM.YYYY)",
+                value
+            )
+        })
 }
 
 impl std::convert::TryFrom<YamlCollection> for Collection {
@@ -57,28 +160,106 @@ impl std::convert::TryFrom<YamlCollection> for Collection {
             &value.modified_at,
             "%Y-%m-%d %H:%M:%S",
         )
-        .unwrap();
+        .with_context(|| {
+            format!(
+                "Invalid modifiedAt value '{}' (expected YYYY-MM-DD HH:MM:SS)",
+                value.modified_at
+            )
+        })?;
 
         let mut collection =
             Collection::new(&value.description, value.version, modified_date);
 
         for item in value.elements {
-            let purchased_info = YamlCollection::parse_purchase_info(
-                item.purchase_info.clone(),
-            )?;
-            let catalog_item = YamlCollection::parse_catalog_item(item)?;
+            let element = format!("{} {}", item.brand, item.item_number);
+
+            let purchased_info =
+                YamlCollection::parse_purchase_info(item.purchase_info.clone())
+                    .with_context(|| {
+                        format!("Invalid purchase info for item {}", element)
+                    })?;
+            let notes = item.notes.clone();
+            let tags = item.tags.clone();
+            let storage = item.storage.clone();
+            let replacement_value = item
+                .replacement_value
+                .as_ref()
+                .map(|value| value.parse::<Price>().map_err(|e| anyhow!(e)))
+                .transpose()
+                .with_context(|| {
+                    format!("Invalid replacement value for item {}", element)
+                })?;
+            let sold_info = item
+                .sold_info
+                .clone()
+                .map(|sold_info| {
+                    YamlCollection::parse_sold_info(sold_info, &purchased_info)
+                })
+                .transpose()
+                .with_context(|| {
+                    format!("Invalid sold info for item {}", element)
+                })?;
+            let catalog_item = YamlCollection::parse_catalog_item(item)
+                .with_context(|| format!("Invalid item {}", element))?;
 
-            collection.add_item(catalog_item, purchased_info)
+            let mut collection_item =
+                CollectionItem::new(catalog_item, purchased_info)
+                    .with_tags(tags);
+            if let Some(notes) = notes {
+                collection_item = collection_item.with_notes(&notes);
+            }
+            if let Some(storage) = storage {
+                collection_item = collection_item.with_storage(&storage);
+            }
+            if let Some(replacement_value) = replacement_value {
+                collection_item =
+                    collection_item.with_replacement_value(replacement_value);
+            }
+            if let Some(sold_info) = sold_info {
+                collection_item = collection_item.with_sold_info(sold_info);
+            }
+            collection.add_collection_item(collection_item)
         }
 
         Ok(collection)
     }
 }
 
+impl Collection {
+    /// Loads the collection from a reader with its YAML document.
+    pub fn from_yaml_reader<R: Read>(reader: R) -> anyhow::Result<Collection> {
+        let yaml_collection: YamlCollection = serde_yaml::from_reader(reader)?;
+        Collection::try_from(yaml_collection)
+    }
+
+    /// Loads the collection from its YAML document.
+    pub fn from_yaml_str(value: &str) -> anyhow::Result<Collection> {
+        let yaml_collection: YamlCollection = serde_yaml::from_str(value)?;
+        Collection::try_from(yaml_collection)
+    }
+}
+
 impl YamlCollection {
     fn parse_catalog_item(
         elem: YamlCollectionItem,
     ) -> anyhow::Result<CatalogItem> {
+        let scale = Scale::from_name(&elem.scale).ok_or_else(|| {
+            anyhow!(
+                "Unknown scale '{}' for item {} {}",
+                elem.scale,
+                elem.brand,
+                elem.item_number
+            )
+        })?;
+
+        let power_method = elem
+            .power_method
+            .parse::<PowerMethod>()
+            .map_err(|e| anyhow!(e))
+            .with_context(|| {
+                format!("Invalid power method '{}'", elem.power_method)
+            })?;
+
         let mut rolling_stocks: Vec<RollingStock> = Vec::new();
         for rs in elem.rolling_stocks {
             let rolling_stock = RollingStock::try_from(rs)?;
@@ -92,13 +273,11 @@ impl YamlCollection {
 
         let catalog_item = CatalogItem::new(
             Brand::new(&elem.brand),
-            ItemNumber::new(&elem.item_number).expect("Invalid item number"),
+            ItemNumber::new(&elem.item_number).map_err(|e| anyhow!(e))?,
             elem.description,
             rolling_stocks,
-            elem.power_method
-                .parse::<PowerMethod>()
-                .expect("Invalid power method"),
-            Scale::from_name(&elem.scale).unwrap(),
+            power_method,
+            scale,
             delivery_date,
             elem.count,
         );
@@ -106,16 +285,56 @@ impl YamlCollection {
         Ok(catalog_item)
     }
 
+    fn parse_sold_info(
+        elem: YamlSoldInfo,
+        purchased_info: &PurchasedInfo,
+    ) -> anyhow::Result<SoldInfo> {
+        let sold_date = parse_purchase_date(&elem.date)?;
+        if &sold_date < purchased_info.purchased_date() {
+            return Err(anyhow!(
+                "The sold date ({}) cannot be before the purchase date ({})",
+                sold_date,
+                purchased_info.purchased_date()
+            ));
+        }
+
+        let mut sold_info = match elem.price {
+            Some(price) => {
+                let price = price.parse::<Price>().map_err(|e| anyhow!(e))?;
+                SoldInfo::new(sold_date, price)
+            }
+            None => SoldInfo::without_price(sold_date),
+        };
+        if let Some(buyer) = elem.buyer {
+            sold_info = sold_info.with_buyer(&buyer);
+        }
+        Ok(sold_info)
+    }
+
     fn parse_purchase_info(
         elem: YamlPurchaseInfo,
     ) -> anyhow::Result<PurchasedInfo> {
-        let purchased_date =
-            NaiveDate::parse_from_str(&elem.date, "%Y-%m-%d").unwrap();
+        let purchased_date = parse_purchase_date(&elem.date)?;
 
-        let price = elem.price.parse::<Price>();
-
-        let purchased_info =
-            PurchasedInfo::new(&elem.shop, purchased_date, price.unwrap());
+        let mut purchased_info = match elem.price {
+            Some(price) => {
+                let price = price.parse::<Price>().map_err(|e| anyhow!(e))?;
+                PurchasedInfo::new(&elem.shop, purchased_date, price)
+            }
+            None => PurchasedInfo::without_price(&elem.shop, purchased_date),
+        };
+        if let Some(condition) = elem.condition {
+            let condition =
+                condition.parse::<Condition>().map_err(|e| anyhow!(e))?;
+            purchased_info = purchased_info.with_condition(condition);
+        }
+        if let Some(receipt) = elem.receipt {
+            purchased_info = purchased_info.with_receipt(&receipt);
+        }
+        if let Some(warranty_months) = elem.warranty_months {
+            purchased_info =
+                purchased_info.with_warranty_months(warranty_months);
+        }
         Ok(purchased_info)
     }
 }
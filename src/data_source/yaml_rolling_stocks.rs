@@ -1,105 +1,221 @@
+use anyhow::Context;
+
 use crate::domain::catalog::{
-    categories::{FreightCarType, LocomotiveType, PassengerCarType, TrainType},
+    categories::{
+        Category, FreightCarType, LocomotiveType, PassengerCarType, TrainType,
+    },
     railways::Railway,
     rolling_stocks::{
-        Control, DccInterface, Epoch, LengthOverBuffer, RollingStock,
-        ServiceLevel,
+        Control, DccAddress, DccInterface, Decoder, Epoch, LengthOverBuffer,
+        RollingStock, RollingStockBuilder, ServiceLevel,
     },
 };
+use std::str;
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct YamlRollingStock {
     #[serde(rename = "typeName")]
     pub type_name: String,
-    #[serde(rename = "roadNumber")]
+    #[serde(rename = "roadNumber", skip_serializing_if = "Option::is_none")]
     pub road_number: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub series: Option<String>,
     pub railway: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
     pub epoch: String,
     #[serde(default)]
     pub category: String,
-    #[serde(rename = "subCategory")]
+    #[serde(rename = "subCategory", skip_serializing_if = "Option::is_none")]
     pub sub_category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub depot: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub length: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub livery: Option<String>,
-    #[serde(rename = "serviceLevel")]
+    #[serde(rename = "serviceLevel", skip_serializing_if = "Option::is_none")]
     pub service_level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub control: Option<String>,
-    #[serde(rename = "dccInterface")]
+    #[serde(rename = "dccInterface", skip_serializing_if = "Option::is_none")]
     pub dcc_interface: Option<String>,
+    #[serde(rename = "dccAddress", skip_serializing_if = "Option::is_none")]
+    pub dcc_address: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoder: Option<YamlDecoder>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct YamlDecoder {
+    pub maker: String,
+    pub model: String,
+    #[serde(default)]
+    pub sound: bool,
+}
+
+/// Parses an optional field value: a missing value is `None`, while a value
+/// that cannot be parsed is an error naming the field.
+fn parse_optional<T>(
+    value: Option<String>,
+    field: &str,
+) -> anyhow::Result<Option<T>>
+where
+    T: str::FromStr<Err = &'static str>,
+{
+    value
+        .map(|v| {
+            v.parse::<T>()
+                .map_err(|e| anyhow!("Invalid {} '{}': {}", field, v, e))
+        })
+        .transpose()
+}
+
+/// Parses the rolling stock category with the YAML values only (like
+/// "PASSENGER_CAR"), the plural forms accepted on the command line are not
+/// valid in the files.
+pub(crate) fn parse_category(value: &str) -> anyhow::Result<Category> {
+    match value {
+        "LOCOMOTIVE" => Ok(Category::Locomotives),
+        "TRAIN" => Ok(Category::Trains),
+        "PASSENGER_CAR" => Ok(Category::PassengerCars),
+        "FREIGHT_CAR" => Ok(Category::FreightCars),
+        _ => Err(anyhow!(
+            "Invalid category '{}' (expected one of: LOCOMOTIVE, TRAIN, PASSENGER_CAR, FREIGHT_CAR)",
+            value
+        )),
+    }
 }
 
 impl std::convert::TryFrom<YamlRollingStock> for RollingStock {
     type Error = anyhow::Error;
 
     fn try_from(value: YamlRollingStock) -> Result<Self, Self::Error> {
-        let length_over_buffer = value.length.map(LengthOverBuffer::new);
-        let control = value.control.and_then(|c| c.parse::<Control>().ok());
-        let dcc_interface = value
-            .dcc_interface
-            .and_then(|dcc| dcc.parse::<DccInterface>().ok());
+        let control = parse_optional::<Control>(value.control, "control")?;
+        let dcc_interface = parse_optional::<DccInterface>(
+            value.dcc_interface,
+            "dccInterface",
+        )?;
+        let dcc_address = value
+            .dcc_address
+            .map(DccAddress::new)
+            .transpose()
+            .map_err(|e| anyhow!(e))?;
+        let length = value
+            .length
+            .map(LengthOverBuffer::try_new)
+            .transpose()
+            .map_err(|e| anyhow!(e))?;
 
         let epoch = value.epoch.parse::<Epoch>()?;
+        let railway = match value.country {
+            Some(country) => Railway::with_country(&value.railway, &country)
+                .map_err(|e| anyhow!(e))?,
+            None => value.railway.parse::<Railway>().map_err(|e| anyhow!(e))?,
+        };
 
-        match value.category.as_str() {
-            "LOCOMOTIVE" => Ok(RollingStock::new_locomotive(
-                value.type_name,
-                value.road_number.unwrap_or_default(),
-                value.series,
-                Railway::new(&value.railway),
-                epoch,
-                value
-                    .sub_category
-                    .and_then(|c| c.parse::<LocomotiveType>().ok())
-                    .unwrap(),
-                value.depot,
-                value.livery,
-                length_over_buffer,
-                control,
-                dcc_interface,
-            )),
-            "TRAIN" => Ok(RollingStock::new_train(
-                value.type_name,
-                value.road_number,
-                1,
-                Railway::new(&value.railway),
-                epoch,
-                value.sub_category.and_then(|c| c.parse::<TrainType>().ok()),
-                value.depot,
-                value.livery,
-                length_over_buffer,
-                control,
-                dcc_interface,
-            )),
-            "PASSENGER_CAR" => Ok(RollingStock::new_passenger_car(
-                value.type_name,
-                value.road_number,
-                Railway::new(&value.railway),
-                epoch,
-                value
-                    .sub_category
-                    .and_then(|c| c.parse::<PassengerCarType>().ok()),
-                value
-                    .service_level
-                    .and_then(|sl| sl.parse::<ServiceLevel>().ok()),
-                value.depot,
-                value.livery,
-                length_over_buffer,
-            )),
-            "FREIGHT_CAR" => Ok(RollingStock::new_freight_car(
-                value.type_name,
-                value.road_number,
-                Railway::new(&value.railway),
-                epoch,
-                value
-                    .sub_category
-                    .and_then(|c| c.parse::<FreightCarType>().ok()),
-                value.depot,
-                value.livery,
-                length_over_buffer,
-            )),
-            _ => Err(anyhow!("Invalid rolling stock type")),
+        let type_name = &value.type_name;
+        let category = parse_category(&value.category).with_context(|| {
+            format!("Invalid category for rolling stock {}", type_name)
+        })?;
+        let mut builder = match category {
+            Category::Locomotives => {
+                let sub_category = value.sub_category.ok_or_else(|| {
+                    anyhow!("Missing subCategory for locomotive {}", type_name)
+                })?;
+                let locomotive_type =
+                    sub_category.parse::<LocomotiveType>().map_err(|e| {
+                        anyhow!(
+                            "Invalid subCategory '{}' for locomotive {}: {}",
+                            sub_category,
+                            type_name,
+                            e
+                        )
+                    })?;
+                RollingStockBuilder::locomotive()
+                    .with_class_name(&value.type_name)
+                    .with_road_number(
+                        value.road_number.as_deref().unwrap_or_default(),
+                    )
+                    .with_locomotive_type(locomotive_type)
+            }
+            Category::Trains => {
+                let mut builder = RollingStockBuilder::train()
+                    .with_type_name(&value.type_name);
+                if let Some(train_type) = parse_optional::<TrainType>(
+                    value.sub_category,
+                    "subCategory",
+                )? {
+                    builder = builder.with_train_type(train_type);
+                }
+                builder
+            }
+            Category::PassengerCars => {
+                let mut builder = RollingStockBuilder::passenger_car()
+                    .with_type_name(&value.type_name);
+                if let Some(passenger_car_type) =
+                    parse_optional::<PassengerCarType>(
+                        value.sub_category,
+                        "subCategory",
+                    )?
+                {
+                    builder =
+                        builder.with_passenger_car_type(passenger_car_type);
+                }
+                if let Some(service_level) = parse_optional::<ServiceLevel>(
+                    value.service_level,
+                    "serviceLevel",
+                )? {
+                    builder = builder.with_service_level(service_level);
+                }
+                builder
+            }
+            Category::FreightCars => {
+                let mut builder = RollingStockBuilder::freight_car()
+                    .with_type_name(&value.type_name);
+                if let Some(freight_car_type) = parse_optional::<FreightCarType>(
+                    value.sub_category,
+                    "subCategory",
+                )? {
+                    builder = builder.with_freight_car_type(freight_car_type);
+                }
+                builder
+            }
+        };
+
+        builder = builder.with_railway(railway).with_epoch(epoch);
+        if let Some(road_number) = value.road_number {
+            builder = builder.with_road_number(&road_number);
+        }
+        if let Some(series) = value.series {
+            builder = builder.with_series(&series);
+        }
+        if let Some(depot) = value.depot {
+            builder = builder.with_depot(&depot);
+        }
+        if let Some(livery) = value.livery {
+            builder = builder.with_livery(&livery);
+        }
+        if let Some(length) = length {
+            builder = builder.with_length_over_buffer(length);
         }
+        if let Some(control) = control {
+            builder = builder.with_control(control);
+        }
+        if let Some(dcc_interface) = dcc_interface {
+            builder = builder.with_dcc_interface(dcc_interface);
+        }
+        if let Some(dcc_address) = dcc_address {
+            builder = builder.with_dcc_address(dcc_address);
+        }
+        if let Some(decoder) = value.decoder {
+            builder = builder.with_decoder(Decoder::new(
+                &decoder.maker,
+                &decoder.model,
+                decoder.sound,
+            ));
+        }
+
+        Ok(builder.build()?)
     }
 }
@@ -6,15 +6,21 @@ use crate::domain::{
         scales::Scale,
     },
     collecting::{
-        wish_lists::{PriceInfo, Priority, WishList, WishListItem},
+        wish_lists::{
+            AddItemStatus, Availability, PriceInfo, Priority, WishList,
+            WishListItem,
+        },
         Price,
     },
 };
+use anyhow::Context;
+use chrono::NaiveDateTime;
 use std::convert::TryFrom;
 
+use super::yaml_collections::parse_date;
 use super::yaml_rolling_stocks::YamlRollingStock;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct YamlWishList {
     pub name: String,
     #[serde(rename = "modifiedAt")]
@@ -23,54 +29,102 @@ pub struct YamlWishList {
     pub elements: Vec<YamlWishListItem>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct YamlWishListItem {
     pub brand: String,
     #[serde(rename = "itemNumber")]
     pub item_number: String,
     pub description: String,
     #[serde(rename = "powerMethod")]
-    pub power_method: String,
+    pub power_method: PowerMethod,
     pub scale: String,
-    #[serde(rename = "deliveryDate")]
-    pub delivery_date: Option<String>,
+    #[serde(rename = "deliveryDate", skip_serializing_if = "Option::is_none")]
+    pub delivery_date: Option<DeliveryDate>,
     pub count: u8,
-    pub priority: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
     #[serde(rename = "rollingStocks")]
     pub rolling_stocks: Vec<YamlRollingStock>,
     #[serde(default = "Vec::new")]
     pub prices: Vec<YamlPrice>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub availability: Option<Availability>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct YamlPrice {
     pub shop: String,
-    pub price: String,
+    pub price: Price,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+impl YamlPrice {
+    fn parse_price_info(&self) -> anyhow::Result<PriceInfo> {
+        let price_info = PriceInfo::new(&self.shop, self.price.clone());
+        match &self.date {
+            Some(date) => {
+                let date = parse_date(date).ok_or_else(|| {
+                    anyhow!(
+                        "Invalid price date '{}' (expected YYYY-MM-DD, DD/MM/YYYY or DD.MM.YYYY)",
+                        date
+                    )
+                })?;
+                Ok(price_info.with_date(date))
+            }
+            None => Ok(price_info),
+        }
+    }
 }
 
 impl std::convert::TryFrom<YamlWishList> for WishList {
     type Error = anyhow::Error;
 
     fn try_from(value: YamlWishList) -> Result<Self, Self::Error> {
-        let mut wish_list = WishList::new(&value.name, value.version);
+        let modified_date = NaiveDateTime::parse_from_str(
+            &value.modified_at,
+            "%Y-%m-%d %H:%M:%S",
+        )
+        .map_err(|e| {
+            anyhow!(
+                "Invalid modifiedAt value '{}' (expected YYYY-MM-DD HH:MM:SS): {}",
+                value.modified_at,
+                e
+            )
+        })?;
+
+        let mut wish_list =
+            WishList::new(&value.name, value.version, modified_date);
 
         for item in value.elements {
-            let mut prices: Vec<PriceInfo> = Vec::new();
+            let element = format!("{} {}", item.brand, item.item_number);
+            let prices = item
+                .prices
+                .iter()
+                .map(YamlPrice::parse_price_info)
+                .collect::<anyhow::Result<Vec<PriceInfo>>>()
+                .with_context(|| {
+                    format!("Invalid prices for item {}", element)
+                })?;
+            let priority = item.priority.unwrap_or_default();
+            let availability = item.availability;
+            let catalog_item = YamlWishList::parse_catalog_item(item)
+                .with_context(|| format!("Invalid item {}", element))?;
 
-            for p in item.prices.iter() {
-                let price = p.price.parse::<Price>().unwrap();
-                let pi = PriceInfo::new(&p.shop, price);
-                prices.push(pi);
+            let mut wish_list_item =
+                WishListItem::new(catalog_item, priority, prices);
+            if let Some(availability) = availability {
+                wish_list_item = wish_list_item.with_availability(availability);
             }
 
-            let priority = if let Some(p) = item.priority.clone() {
-                p.parse::<Priority>()?
-            } else {
-                Default::default()
-            };
-            let catalog_item = YamlWishList::parse_catalog_item(item)?;
-
-            wish_list.add_item(catalog_item, priority, prices);
+            if wish_list.add_wish_list_item(wish_list_item)
+                == AddItemStatus::Merged
+            {
+                warn!(
+                    "Duplicated item {} in the wish list {}, merged with the first one",
+                    element, value.name
+                );
+            }
         }
 
         Ok(wish_list)
@@ -78,30 +132,45 @@ impl std::convert::TryFrom<YamlWishList> for WishList {
 }
 
 impl YamlWishList {
+    /// Removes the item with the given brand (ignoring case) and item number.
+    pub fn remove_item(
+        &mut self,
+        brand: &str,
+        item_number: &str,
+    ) -> Option<YamlWishListItem> {
+        let index = self.elements.iter().position(|it| {
+            it.brand.eq_ignore_ascii_case(brand)
+                && it.item_number == item_number
+        })?;
+        Some(self.elements.remove(index))
+    }
+
     fn parse_catalog_item(
         elem: YamlWishListItem,
     ) -> anyhow::Result<CatalogItem> {
+        let scale = Scale::from_name(&elem.scale).ok_or_else(|| {
+            anyhow!(
+                "Unknown scale '{}' for item {} {}",
+                elem.scale,
+                elem.brand,
+                elem.item_number
+            )
+        })?;
+
         let mut rolling_stocks: Vec<RollingStock> = Vec::new();
         for rs in elem.rolling_stocks {
             let rolling_stock = RollingStock::try_from(rs)?;
             rolling_stocks.push(rolling_stock);
         }
 
-        let mut delivery_date = None;
-        if let Some(dd) = elem.delivery_date {
-            delivery_date = Some(dd.parse::<DeliveryDate>()?);
-        }
-
         let catalog_item = CatalogItem::new(
             Brand::new(&elem.brand),
-            ItemNumber::new(&elem.item_number).expect("Invalid item number"),
+            ItemNumber::new(&elem.item_number).map_err(|e| anyhow!(e))?,
             elem.description,
             rolling_stocks,
-            elem.power_method
-                .parse::<PowerMethod>()
-                .expect("Invalid power method"),
-            Scale::from_name(&elem.scale).unwrap(),
-            delivery_date,
+            elem.power_method,
+            scale,
+            elem.delivery_date,
             elem.count,
         );
 
@@ -2,7 +2,7 @@
 use std::fmt;
 
 /// A model railways manufacturer.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct Brand(String);
 
 impl Brand {
@@ -1,3 +1,4 @@
+use heck::ToShoutySnakeCase;
 use itertools::Itertools;
 use std::cmp;
 use std::fmt;
@@ -41,7 +42,7 @@ impl fmt::Display for ItemNumber {
 pub type Quarter = u8;
 pub type Year = i32;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DeliveryDate {
     ByYear(Year),
     ByQuarter(Year, Quarter),
@@ -132,6 +133,8 @@ impl fmt::Display for DeliveryDate {
     }
 }
 
+impl_serde_with_str!(DeliveryDate, "delivery date");
+
 #[derive(Debug, Error)]
 #[allow(clippy::enum_variant_names)]
 pub enum DeliveryDateParseError {
@@ -146,29 +149,37 @@ pub enum DeliveryDateParseError {
 }
 
 // The power methods for the model.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
 pub enum PowerMethod {
     /// Direct current.
     DC,
 
     /// Alternating current (Maerklin).
     AC,
+
+    /// Trix Express, the three-rail system with direct current.
+    TrixExpress,
 }
 
 impl fmt::Display for PowerMethod {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:?}", self)
+        let s = format!("{:?}", self);
+        write!(f, "{}", s.to_shouty_snake_case())
     }
 }
 
+impl_serde_with_str!(PowerMethod, "power method");
+
 impl str::FromStr for PowerMethod {
     type Err = &'static str;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
+        match s.to_uppercase().as_str() {
             "DC" => Ok(PowerMethod::DC),
             "AC" => Ok(PowerMethod::AC),
-            _ => Err("Invalid value for power methods [allowed: 'AC' or 'DC']"),
+            "TRIX_EXPRESS" => Ok(PowerMethod::TrixExpress),
+            _ => Err("Invalid value for power methods [allowed: 'AC', 'DC' or 'TRIX_EXPRESS']"),
         }
     }
 }
@@ -266,6 +277,23 @@ impl CatalogItem {
         self.count
     }
 
+    /// Checks whether any rolling stock for this catalog item overlaps the
+    /// given epoch.
+    pub fn overlaps_epoch(&self, epoch: &Epoch) -> bool {
+        self.rolling_stocks
+            .iter()
+            .any(|rs| rs.epoch().overlaps(epoch))
+    }
+
+    /// Returns the epochs for the rolling stocks, without repetitions.
+    pub fn epochs(&self) -> Vec<Epoch> {
+        self.rolling_stocks
+            .iter()
+            .map(|rs| rs.epoch().clone())
+            .unique()
+            .collect()
+    }
+
     pub fn description(&self) -> &str {
         &self.description
     }
@@ -354,11 +382,50 @@ mod tests {
             assert!(pm.is_ok());
             assert_eq!("AC", pm.unwrap().to_string());
         }
+
+        #[test]
+        fn it_should_parse_lowercase_strings_as_power_methods() {
+            assert_eq!(Ok(PowerMethod::DC), "dc".parse::<PowerMethod>());
+            assert_eq!(
+                Ok(PowerMethod::TrixExpress),
+                "trix_express".parse::<PowerMethod>()
+            );
+            assert!("3-rail".parse::<PowerMethod>().is_err());
+        }
+
+        #[test]
+        fn it_should_display_the_trix_express_power_method() {
+            let pm = PowerMethod::TrixExpress;
+            assert_eq!("TRIX_EXPRESS", pm.to_string());
+            assert_eq!(Ok(pm), pm.to_string().parse::<PowerMethod>());
+        }
+
+        #[test]
+        fn it_should_serialize_power_methods_as_strings() {
+            let json = serde_json::to_string(&PowerMethod::DC).unwrap();
+            assert_eq!("\"DC\"", json);
+            assert_eq!(
+                PowerMethod::DC,
+                serde_json::from_str::<PowerMethod>(&json).unwrap()
+            );
+        }
     }
 
     mod delivery_date_tests {
         use super::*;
 
+        #[test]
+        fn it_should_serialize_delivery_dates_as_strings() {
+            let dd = DeliveryDate::ByQuarter(2020, 1);
+
+            let json = serde_json::to_string(&dd).unwrap();
+            assert_eq!("\"2020/Q1\"", json);
+            assert_eq!(
+                dd,
+                serde_json::from_str::<DeliveryDate>(&json).unwrap()
+            );
+        }
+
         #[test]
         fn it_should_parse_string_as_delivery_dates() {
             let dd1 = "2020/Q1".parse::<DeliveryDate>();
@@ -392,53 +459,53 @@ mod tests {
             categories::{FreightCarType, LocomotiveType, PassengerCarType},
             railways::Railway,
             rolling_stocks::{
-                Control, DccInterface, LengthOverBuffer, ServiceLevel,
+                Control, DccInterface, LengthOverBuffer, RollingStockBuilder,
+                ServiceLevel,
             },
         };
 
         use super::*;
 
         fn new_locomotive() -> RollingStock {
-            RollingStock::new_locomotive(
-                String::from("E.656"),
-                String::from("E.656 210"),
-                Some(String::from("1a serie")),
-                Railway::new("FS"),
-                Epoch::IV,
-                LocomotiveType::ElectricLocomotive,
-                Some(String::from("Milano Centrale")),
-                Some(String::from("blu/grigio")),
-                Some(LengthOverBuffer::new(210)),
-                Some(Control::DccReady),
-                Some(DccInterface::Nem652),
-            )
+            RollingStockBuilder::locomotive()
+                .with_class_name("E.656")
+                .with_road_number("E.656 210")
+                .with_series("1a serie")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_locomotive_type(LocomotiveType::ElectricLocomotive)
+                .with_depot("Milano Centrale")
+                .with_livery("blu/grigio")
+                .with_length_over_buffer(LengthOverBuffer::new(210))
+                .with_control(Control::DccReady)
+                .with_dcc_interface(DccInterface::Nem652)
+                .build()
+                .unwrap()
         }
 
         fn new_passenger_car() -> RollingStock {
-            RollingStock::new_passenger_car(
-                String::from("UIC-Z"),
-                None,
-                Railway::new("FS"),
-                Epoch::IV,
-                Some(PassengerCarType::OpenCoach),
-                Some(ServiceLevel::FirstClass),
-                None,
-                Some(String::from("bandiera")),
-                Some(LengthOverBuffer::new(303)),
-            )
+            RollingStockBuilder::passenger_car()
+                .with_type_name("UIC-Z")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_passenger_car_type(PassengerCarType::OpenCoach)
+                .with_service_level(ServiceLevel::FirstClass)
+                .with_livery("bandiera")
+                .with_length_over_buffer(LengthOverBuffer::new(303))
+                .build()
+                .unwrap()
         }
 
         fn new_freight_car() -> RollingStock {
-            RollingStock::new_freight_car(
-                String::from("Gbhs"),
-                None,
-                Railway::new("FS"),
-                Epoch::V,
-                Some(FreightCarType::SwingRoofWagon),
-                None,
-                Some(String::from("marrone")),
-                Some(LengthOverBuffer::new(122)),
-            )
+            RollingStockBuilder::freight_car()
+                .with_type_name("Gbhs")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::V)
+                .with_freight_car_type(FreightCarType::SwingRoofWagon)
+                .with_livery("marrone")
+                .with_length_over_buffer(LengthOverBuffer::new(122))
+                .build()
+                .unwrap()
         }
 
         fn new_locomotive_catalog_item() -> CatalogItem {
@@ -525,6 +592,21 @@ mod tests {
             assert_eq!(Category::PassengerCars, item2.category());
         }
 
+        #[test]
+        fn it_should_check_whether_catalog_items_overlap_an_epoch() {
+            let item = new_set_catalog_item();
+
+            assert!(item.overlaps_epoch(&Epoch::IVa));
+            assert!(item.overlaps_epoch(&Epoch::V));
+            assert!(!item.overlaps_epoch(&Epoch::III));
+        }
+
+        #[test]
+        fn it_should_return_the_epochs_without_repetitions() {
+            let item = new_set_catalog_item();
+            assert_eq!(vec![Epoch::IV, Epoch::V], item.epochs());
+        }
+
         #[test]
         fn it_should_produce_string_representations_from_catalog_items() {
             let item = new_locomotive_catalog_item();
@@ -2,6 +2,7 @@ use std::fmt;
 use std::str;
 
 use heck::ToShoutySnakeCase;
+use thiserror::Error;
 
 /// The enumeration of the model categories.
 #[derive(Debug, PartialEq, PartialOrd, Eq, Ord, Clone, Copy)]
@@ -34,6 +35,66 @@ impl Category {
             Category::Trains => Category::TRAIN_SYMBOL,
         }
     }
+
+    /// Returns the category name, in its plural form (like "passenger-cars").
+    pub fn name(&self) -> &'static str {
+        match &self {
+            Category::Locomotives => "locomotives",
+            Category::Trains => "trains",
+            Category::FreightCars => "freight-cars",
+            Category::PassengerCars => "passenger-cars",
+        }
+    }
+
+    /// Returns all the categories
+    pub fn values() -> [Category; 4] {
+        [
+            Category::Locomotives,
+            Category::Trains,
+            Category::FreightCars,
+            Category::PassengerCars,
+        ]
+    }
+
+    /// Returns the category for the given symbol, if any.
+    pub fn from_symbol(symbol: char) -> Option<Category> {
+        match symbol.to_ascii_uppercase() {
+            Category::LOCOMOTIVE_SYMBOL => Some(Category::Locomotives),
+            Category::FREIGHT_CAR_SYMBOL => Some(Category::FreightCars),
+            Category::PASSENGER_CAR_SYMBOL => Some(Category::PassengerCars),
+            Category::TRAIN_SYMBOL => Some(Category::Trains),
+            _ => None,
+        }
+    }
+}
+
+impl str::FromStr for Category {
+    type Err = CategoryParseError;
+
+    /// Parses a category, either in the rolling stocks format (like
+    /// "PASSENGER_CAR") or in its plural form (like "passenger-cars").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim();
+        if value.is_empty() {
+            return Err(CategoryParseError::BlankValue);
+        }
+
+        match value.to_lowercase().replace(['_', ' '], "-").as_str() {
+            "locomotive" | "locomotives" => Ok(Category::Locomotives),
+            "train" | "trains" => Ok(Category::Trains),
+            "freight-car" | "freight-cars" => Ok(Category::FreightCars),
+            "passenger-car" | "passenger-cars" => Ok(Category::PassengerCars),
+            _ => Err(CategoryParseError::InvalidValue(value.to_owned())),
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CategoryParseError {
+    #[error("Category value cannot be blank")]
+    BlankValue,
+    #[error("Invalid category '{0}' (expected one of: locomotives, trains, freight-cars, passenger-cars)")]
+    InvalidValue(String),
 }
 
 impl fmt::Display for Category {
@@ -99,6 +160,13 @@ impl str::FromStr for FreightCarType {
     }
 }
 
+impl fmt::Display for FreightCarType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = format!("{:?}", self);
+        write!(f, "{}", s.to_shouty_snake_case())
+    }
+}
+
 /// The different kinds of locomotives
 #[derive(Debug, PartialEq)]
 #[allow(clippy::enum_variant_names)]
@@ -130,6 +198,13 @@ impl str::FromStr for LocomotiveType {
     }
 }
 
+impl fmt::Display for LocomotiveType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = format!("{:?}", self);
+        write!(f, "{}", s.to_shouty_snake_case())
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub enum PassengerCarType {
     /// An "open coach" has a central aisle; the car's interior is often filled with row upon row of
@@ -168,6 +243,10 @@ pub enum PassengerCarType {
     DrivingTrailer,
 
     RailwayPostOffice,
+
+    /// Couchette cars provide simple bunks (usually four or six per
+    /// compartment) for the night trains.
+    CouchetteCar,
 }
 
 impl str::FromStr for PassengerCarType {
@@ -190,6 +269,7 @@ impl str::FromStr for PassengerCarType {
             "COMBINE_CAR" => Ok(PassengerCarType::CombineCar),
             "DRIVING_TRAILER" => Ok(PassengerCarType::DrivingTrailer),
             "RAILWAY_POST_OFFICE" => Ok(PassengerCarType::RailwayPostOffice),
+            "COUCHETTE_CAR" => Ok(PassengerCarType::CouchetteCar),
             _ => Err("Invalid value for passenger car type"),
         }
     }
@@ -198,7 +278,7 @@ impl str::FromStr for PassengerCarType {
 impl fmt::Display for PassengerCarType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let s = format!("{:?}", self);
-        write!(f, "{:?}", s.to_shouty_snake_case())
+        write!(f, "{}", s.to_shouty_snake_case())
     }
 }
 
@@ -217,6 +297,12 @@ pub enum TrainType {
 
     /// The starter sets (usually includes the tracks) category
     StarterSets,
+
+    /// The battery powered railcars category
+    BatteryRailcars,
+
+    /// The steam railcars category
+    SteamRailcars,
 }
 
 impl str::FromStr for TrainType {
@@ -233,18 +319,107 @@ impl str::FromStr for TrainType {
             "ELECTRIC_MULTIPLE_UNITS" => Ok(TrainType::ElectricMultipleUnits),
             "TRAIN_SETS" => Ok(TrainType::TrainSets),
             "STARTER_SETS" => Ok(TrainType::StarterSets),
+            "BATTERY_RAILCARS" => Ok(TrainType::BatteryRailcars),
+            "STEAM_RAILCARS" => Ok(TrainType::SteamRailcars),
             _ => Err("Invalid value for train type"),
         }
     }
 }
 
+impl fmt::Display for TrainType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = format!("{:?}", self);
+        write!(f, "{}", s.to_shouty_snake_case())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    mod category_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_find_categories_by_symbol() {
+            assert_eq!(Some(Category::Locomotives), Category::from_symbol('L'));
+            assert_eq!(Some(Category::FreightCars), Category::from_symbol('f'));
+            assert_eq!(None, Category::from_symbol('X'));
+        }
+
+        #[test]
+        fn it_should_parse_categories_from_the_rolling_stock_values() {
+            assert_eq!(Ok(Category::Locomotives), "LOCOMOTIVE".parse());
+            assert_eq!(Ok(Category::Trains), "TRAIN".parse());
+            assert_eq!(Ok(Category::FreightCars), "FREIGHT_CAR".parse());
+            assert_eq!(Ok(Category::PassengerCars), "PASSENGER_CAR".parse());
+        }
+
+        #[test]
+        fn it_should_parse_categories_from_their_plural_forms() {
+            assert_eq!(Ok(Category::Locomotives), "locomotives".parse());
+            assert_eq!(Ok(Category::Trains), "Trains".parse());
+            assert_eq!(Ok(Category::FreightCars), "freight-cars".parse());
+            assert_eq!(Ok(Category::PassengerCars), "passenger cars".parse());
+        }
+
+        #[test]
+        fn it_should_parse_the_category_names() {
+            for category in Category::values() {
+                assert_eq!(Ok(category), category.name().parse());
+            }
+        }
+
+        #[test]
+        fn it_should_fail_to_parse_invalid_categories() {
+            assert_eq!(
+                Err(CategoryParseError::BlankValue),
+                " ".parse::<Category>()
+            );
+            let result = "wagons".parse::<Category>();
+            assert_eq!(
+                Err(CategoryParseError::InvalidValue(String::from("wagons"))),
+                result
+            );
+            assert_eq!(
+                "Invalid category 'wagons' (expected one of: locomotives, trains, freight-cars, passenger-cars)",
+                result.unwrap_err().to_string()
+            );
+        }
+    }
+
     mod freight_car_type_tests {
         use super::*;
 
+        #[test]
+        fn it_should_round_trip_every_freight_car_type() {
+            let values = [
+                FreightCarType::AutoTransportCars,
+                FreightCarType::BrakeWagon,
+                FreightCarType::ContainerCars,
+                FreightCarType::CoveredFreightCars,
+                FreightCarType::DumpCars,
+                FreightCarType::Gondola,
+                FreightCarType::HeavyGoodsWagons,
+                FreightCarType::HingedCoverWagons,
+                FreightCarType::HopperWagon,
+                FreightCarType::RefrigeratorCars,
+                FreightCarType::SiloContainerCars,
+                FreightCarType::SlideTarpaulinWagon,
+                FreightCarType::SlidingWallBoxcars,
+                FreightCarType::SpecialTransport,
+                FreightCarType::StakeWagons,
+                FreightCarType::SwingRoofWagon,
+                FreightCarType::TankCars,
+                FreightCarType::TelescopeHoodWagons,
+                FreightCarType::DeepWellFlatCars,
+            ];
+
+            for value in values {
+                assert_eq!(Ok(&value), value.to_string().parse().as_ref());
+            }
+        }
+
         #[test]
         fn it_should_convert_string_slices_to_freight_car_types() {
             let locomotive_type =
@@ -269,6 +444,23 @@ mod tests {
     mod train_type_tests {
         use super::*;
 
+        #[test]
+        fn it_should_round_trip_every_train_type() {
+            let values = [
+                TrainType::Railcars,
+                TrainType::PowerCars,
+                TrainType::ElectricMultipleUnits,
+                TrainType::TrainSets,
+                TrainType::StarterSets,
+                TrainType::BatteryRailcars,
+                TrainType::SteamRailcars,
+            ];
+
+            for value in values {
+                assert_eq!(Ok(&value), value.to_string().parse().as_ref());
+            }
+        }
+
         #[test]
         fn it_should_convert_string_slices_to_train_types() {
             let train_type = "ELECTRIC_MULTIPLE_UNITS".parse::<TrainType>();
@@ -289,6 +481,36 @@ mod tests {
     mod passenger_car_type_tests {
         use super::*;
 
+        #[test]
+        fn it_should_round_trip_every_passenger_car_type() {
+            let values = [
+                PassengerCarType::OpenCoach,
+                PassengerCarType::CompartmentCoach,
+                PassengerCarType::DiningCar,
+                PassengerCarType::Lounge,
+                PassengerCarType::Observation,
+                PassengerCarType::SleepingCar,
+                PassengerCarType::BaggageCar,
+                PassengerCarType::DoubleDecker,
+                PassengerCarType::CombineCar,
+                PassengerCarType::DrivingTrailer,
+                PassengerCarType::RailwayPostOffice,
+                PassengerCarType::CouchetteCar,
+            ];
+
+            for value in values {
+                assert_eq!(Ok(&value), value.to_string().parse().as_ref());
+            }
+        }
+
+        #[test]
+        fn it_should_display_passenger_car_types() {
+            assert_eq!(
+                "COUCHETTE_CAR",
+                PassengerCarType::CouchetteCar.to_string()
+            );
+        }
+
         #[test]
         fn it_should_convert_string_slices_to_passenger_car_types() {
             let passenger_car_type =
@@ -313,6 +535,19 @@ mod tests {
     mod locomotive_type_tests {
         use super::*;
 
+        #[test]
+        fn it_should_round_trip_every_locomotive_type() {
+            let values = [
+                LocomotiveType::SteamLocomotive,
+                LocomotiveType::DieselLocomotive,
+                LocomotiveType::ElectricLocomotive,
+            ];
+
+            for value in values {
+                assert_eq!(Ok(&value), value.to_string().parse().as_ref());
+            }
+        }
+
         #[test]
         fn it_should_convert_string_slices_to_locomotive_types() {
             let locomotive_type = "STEAM_LOCOMOTIVE".parse::<LocomotiveType>();
@@ -1,19 +1,217 @@
 //! This module contains everything related to railways.
+use isocountry::CountryCode;
 use std::fmt;
+use std::slice;
+use std::str;
 
 /// It represents a railway company, which is an entity that operates a railroad track or trains.
-#[derive(Debug, PartialEq, Clone)]
-pub struct Railway(String);
+///
+/// Some rolling stocks are marked for more railways (ie "DB/SNCF" for cross-border stock),
+/// in this case the railway includes every company.
+///
+/// Railways are the same when their names are equal, ignoring case.
+#[derive(Debug, Clone)]
+pub enum Railway {
+    Company {
+        name: String,
+        full_name: Option<String>,
+        country: Option<CountryCode>,
+    },
+    Multiple {
+        name: String,
+        railways: Vec<Railway>,
+    },
+}
+
+/// The well known railway companies: the code, the full name and the country
+/// (ISO 3166-1 alpha-2 code).
+const KNOWN_RAILWAYS: [(&str, &str, &str); 22] = [
+    ("BLS", "BLS AG", "CH"),
+    ("BR", "British Rail", "GB"),
+    (
+        "CFL",
+        "Société Nationale des Chemins de Fer Luxembourgeois",
+        "LU",
+    ),
+    ("DB", "Deutsche Bundesbahn", "DE"),
+    ("DR", "Deutsche Reichsbahn", "DE"),
+    ("DSB", "Danske Statsbaner", "DK"),
+    ("FNM", "Ferrovie Nord Milano", "IT"),
+    ("FS", "Ferrovie dello Stato", "IT"),
+    ("MÁV", "Magyar Államvasutak", "HU"),
+    ("NS", "Nederlandse Spoorwegen", "NL"),
+    ("NSB", "Norges Statsbaner", "NO"),
+    ("ÖBB", "Österreichische Bundesbahnen", "AT"),
+    ("PKP", "Polskie Koleje Państwowe", "PL"),
+    ("RENFE", "Red Nacional de los Ferrocarriles Españoles", "ES"),
+    ("RhB", "Rhätische Bahn", "CH"),
+    ("SBB", "Schweizerische Bundesbahnen", "CH"),
+    ("SJ", "Statens Järnvägar", "SE"),
+    ("SNCB", "Société Nationale des Chemins de fer Belges", "BE"),
+    (
+        "SNCF",
+        "Société Nationale des Chemins de fer Français",
+        "FR",
+    ),
+    ("Trenitalia", "Trenitalia", "IT"),
+    ("UP", "Union Pacific", "US"),
+    ("VR", "VR-Yhtymä", "FI"),
+];
 
 impl Railway {
+    const SEPARATOR: char = '/';
+
     /// Creates a new railway with this name
     pub fn new(name: &str) -> Self {
-        Railway(name.to_owned())
+        Railway::Company {
+            name: name.to_owned(),
+            full_name: None,
+            country: None,
+        }
     }
 
-    /// Returns the name for this railway
+    /// Creates a new railway with this name, operating in the country with the given
+    /// ISO 3166-1 alpha-2 code (ie "DE" or "IT").
+    ///
+    /// Well known railways are resolved as in `from_str`, keeping the given country.
+    pub fn with_country(
+        name: &str,
+        country: &str,
+    ) -> Result<Self, &'static str> {
+        let country = CountryCode::for_alpha2_caseless(country)
+            .map_err(|_| "Invalid country code for railway")?;
+        let (name, full_name) = match Railway::known(name) {
+            Some(Railway::Company {
+                name, full_name, ..
+            }) => (name, full_name),
+            _ => (name.to_owned(), None),
+        };
+        Ok(Railway::Company {
+            name,
+            full_name,
+            country: Some(country),
+        })
+    }
+
+    /// Returns the well known railway with this code or full name (ignoring case).
+    pub fn known(value: &str) -> Option<Self> {
+        let value = value.trim().to_lowercase();
+        KNOWN_RAILWAYS
+            .iter()
+            .find(|(code, full_name, _)| {
+                code.to_lowercase() == value
+                    || full_name.to_lowercase() == value
+            })
+            .map(|(code, full_name, country)| Railway::Company {
+                name: (*code).to_owned(),
+                full_name: Some((*full_name).to_owned()),
+                country: CountryCode::for_alpha2(country).ok(),
+            })
+    }
+
+    /// Returns the full name for this railway (ie "Ferrovie dello Stato" for FS), if known
+    pub fn full_name(&self) -> Option<&str> {
+        match self {
+            Railway::Company {
+                full_name: Some(full_name),
+                ..
+            } => Some(full_name),
+            _ => None,
+        }
+    }
+
+    /// Creates a new railway for rolling stocks marked for more companies.
+    pub fn multiple(railways: Vec<Railway>) -> Self {
+        let name = railways
+            .iter()
+            .map(|it| it.name())
+            .collect::<Vec<&str>>()
+            .join(&Railway::SEPARATOR.to_string());
+        Railway::Multiple { name, railways }
+    }
+
+    /// Returns the name for this railway, multiple railways names are separated by '/'
     pub fn name(&self) -> &str {
-        &self.0
+        match self {
+            Railway::Company { name, .. } => name,
+            Railway::Multiple { name, .. } => name,
+        }
+    }
+
+    /// Returns the country code (ISO 3166-1 alpha-2) for this railway, if known
+    pub fn country(&self) -> Option<&str> {
+        match self {
+            Railway::Company {
+                country: Some(country),
+                ..
+            } => Some(country.alpha2()),
+            _ => None,
+        }
+    }
+
+    /// Returns the railway companies included in this railway
+    pub fn railways(&self) -> &[Railway] {
+        match self {
+            Railway::Company { .. } => slice::from_ref(self),
+            Railway::Multiple { railways, .. } => railways,
+        }
+    }
+
+    /// Checks whether this railway includes the company with the given name (ignoring case)
+    pub fn includes(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+        self.railways()
+            .iter()
+            .any(|it| it.name().to_lowercase() == name)
+    }
+}
+
+impl PartialEq for Railway {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                Railway::Company { name, .. },
+                Railway::Company { name: other, .. },
+            ) => name.to_lowercase() == other.to_lowercase(),
+            (
+                Railway::Multiple { railways, .. },
+                Railway::Multiple {
+                    railways: others, ..
+                },
+            ) => railways == others,
+            _ => false,
+        }
+    }
+}
+
+/// Parses the railway names, the well known railways are resolved by code or full
+/// name (ignoring case) while any other value is a custom railway.
+impl str::FromStr for Railway {
+    type Err = &'static str;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.trim().is_empty() {
+            return Err("Railway value cannot be blank");
+        }
+
+        let mut railways: Vec<Railway> = Vec::new();
+        for token in s.split(Railway::SEPARATOR).map(|it| it.trim()) {
+            if token.is_empty() {
+                return Err("Invalid value for multiple railways");
+            }
+
+            let railway =
+                Railway::known(token).unwrap_or_else(|| Railway::new(token));
+            if !railways.contains(&railway) {
+                railways.push(railway);
+            }
+        }
+
+        if railways.len() == 1 {
+            Ok(railways.remove(0))
+        } else {
+            Ok(Railway::multiple(railways))
+        }
     }
 }
 
@@ -34,12 +232,94 @@ mod tests {
         fn it_should_create_new_railways() {
             let b = Railway::new("FS");
             assert_eq!("FS", b.name());
+            assert_eq!(None, b.country());
+            assert_eq!(&[Railway::new("FS")], b.railways());
+        }
+
+        #[test]
+        fn it_should_create_new_railways_with_country() {
+            let b = Railway::with_country("DB", "de");
+            assert!(b.is_ok());
+
+            let railway = b.unwrap();
+            assert_eq!("DB", railway.name());
+            assert_eq!(Some("DE"), railway.country());
+        }
+
+        #[test]
+        fn it_should_fail_to_create_railways_with_invalid_country() {
+            let b = Railway::with_country("DB", "XX");
+            assert!(b.is_err());
+        }
+
+        #[test]
+        fn it_should_parse_string_as_railways() {
+            let railway = "FS".parse::<Railway>();
+            assert!(railway.is_ok());
+            assert_eq!(Railway::new("FS"), railway.unwrap());
+        }
+
+        #[test]
+        fn it_should_parse_string_as_multiple_railways() {
+            let railway = "DB/SNCF".parse::<Railway>();
+            assert!(railway.is_ok());
+
+            let railway = railway.unwrap();
+            assert_eq!("DB/SNCF", railway.name());
+            assert_eq!(
+                &[Railway::new("DB"), Railway::new("SNCF")],
+                railway.railways()
+            );
+            assert!(railway.includes("sncf"));
+            assert!(!railway.includes("FS"));
+        }
+
+        #[test]
+        fn it_should_fail_to_parse_invalid_railways() {
+            assert!("".parse::<Railway>().is_err());
+            assert!("DB/".parse::<Railway>().is_err());
+        }
+
+        #[test]
+        fn it_should_resolve_the_known_railways() {
+            let railway = "fs".parse::<Railway>().unwrap();
+            assert_eq!("FS", railway.name());
+            assert_eq!(Some("Ferrovie dello Stato"), railway.full_name());
+            assert_eq!(Some("IT"), railway.country());
+
+            let railway = "Ferrovie dello Stato".parse::<Railway>().unwrap();
+            assert_eq!("FS", railway.name());
+
+            let railway = "öbb".parse::<Railway>().unwrap();
+            assert_eq!("ÖBB", railway.name());
+            assert_eq!(Some("AT"), railway.country());
+        }
+
+        #[test]
+        fn it_should_parse_unknown_railways_as_custom_railways() {
+            let railway = "Ferrovie Appulo Lucane".parse::<Railway>().unwrap();
+            assert_eq!("Ferrovie Appulo Lucane", railway.name());
+            assert_eq!(None, railway.full_name());
+            assert_eq!(None, railway.country());
+        }
+
+        #[test]
+        fn it_should_compare_railways_ignoring_case() {
+            assert_eq!(Railway::new("fs"), Railway::new("FS"));
+            assert_eq!(
+                "db/sncf".parse::<Railway>().unwrap(),
+                "DB/SNCF".parse::<Railway>().unwrap()
+            );
+            assert_ne!(Railway::new("FS"), Railway::new("DB"));
         }
 
         #[test]
         fn it_should_display_brand_as_string() {
             let b = Railway::new("FS");
             assert_eq!("FS", b.to_string());
+
+            let multiple = "DB/SNCF".parse::<Railway>().unwrap();
+            assert_eq!("DB/SNCF", multiple.to_string());
         }
     }
 }
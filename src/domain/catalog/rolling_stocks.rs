@@ -1,3 +1,4 @@
+use std::cmp;
 use std::fmt;
 use std::str;
 
@@ -14,7 +15,10 @@ use crate::domain::catalog::railways::Railway;
 /// The model railway industry adopted an 'Era', or 'Epoch' system; the idea being to group models
 /// into a defined time bracket, so that locomotives, coaching and wagon stock could be reasonably
 /// grouped together.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Epochs are ordered chronologically, the sub-epochs (like IIIa and IIIb) follow their
+/// main epoch, while multiple epochs (like "III/IV/V") are ordered by their earliest value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[allow(non_snake_case)]
 #[allow(clippy::upper_case_acronyms)]
 pub enum Epoch {
@@ -33,7 +37,8 @@ pub enum Epoch {
     Vb,
     Vm,
     VI,
-    Multiple(Box<Epoch>, Box<Epoch>),
+    /// Rolling stocks valid for more epochs, in chronological order (2 up to 4 values)
+    Multiple(Vec<Epoch>),
 }
 
 impl str::FromStr for Epoch {
@@ -45,12 +50,14 @@ impl str::FromStr for Epoch {
         }
 
         if s.contains('/') {
-            let tokens: Vec<&str> =
-                s.split_terminator('/').sorted().dedup().collect();
-            if tokens.len() == 2 {
-                let first = Epoch::parse_str(tokens[0])?;
-                let second = Epoch::parse_str(tokens[1])?;
-                Ok(Epoch::Multiple(Box::new(first), Box::new(second)))
+            let epochs = s
+                .split_terminator('/')
+                .map(Epoch::parse_str)
+                .collect::<Result<Vec<Epoch>, EpochParseError>>()?;
+            let epochs: Vec<Epoch> =
+                epochs.into_iter().sorted().dedup().collect();
+            if (2..=Epoch::MAX_VALUES).contains(&epochs.len()) {
+                Ok(Epoch::Multiple(epochs))
             } else {
                 Err(EpochParseError::InvalidNumberOfValues)
             }
@@ -71,6 +78,9 @@ pub enum EpochParseError {
 }
 
 impl Epoch {
+    /// The max number of values for multiple epochs
+    const MAX_VALUES: usize = 4;
+
     // Helper method to parse just the simple value
     fn parse_str(value: &str) -> Result<Self, EpochParseError> {
         match value {
@@ -94,18 +104,97 @@ impl Epoch {
     }
 }
 
+impl Epoch {
+    /// Checks whether this epoch includes the other one, multiple epochs include
+    /// each of their values.
+    pub fn includes(&self, other: &Epoch) -> bool {
+        match self {
+            Epoch::Multiple(epochs) => {
+                self == other || epochs.iter().any(|ep| ep.includes(other))
+            }
+            _ => self == other,
+        }
+    }
+
+    /// Checks whether this epoch and the other one share at least one era,
+    /// the sub-eras (like IVa or Vm) are folded into their main era.
+    pub fn overlaps(&self, other: &Epoch) -> bool {
+        let eras = self.main_eras();
+        other.main_eras().iter().any(|era| eras.contains(era))
+    }
+
+    // Helper method to list the main eras, without the sub-eras
+    fn main_eras(&self) -> Vec<Epoch> {
+        match self {
+            Epoch::Multiple(epochs) => {
+                epochs.iter().flat_map(|ep| ep.main_eras()).collect()
+            }
+            Epoch::IIa | Epoch::IIb => vec![Epoch::II],
+            Epoch::IIIa | Epoch::IIIb => vec![Epoch::III],
+            Epoch::IVa | Epoch::IVb => vec![Epoch::IV],
+            Epoch::Va | Epoch::Vb | Epoch::Vm => vec![Epoch::V],
+            _ => vec![self.clone()],
+        }
+    }
+}
+
 impl fmt::Display for Epoch {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Epoch::Multiple(ep1, ep2) => write!(f, "{}/{}", &ep1, &ep2),
+            Epoch::Multiple(epochs) => {
+                write!(f, "{}", epochs.iter().join("/"))
+            }
             _ => write!(f, "{:?}", self),
         }
     }
 }
 
+impl_serde_with_str!(Epoch, "epoch");
+
+impl Epoch {
+    // Helper method for the chronological order, sub-epochs follow their main epoch
+    fn ranks(&self) -> Vec<u8> {
+        match self {
+            Epoch::I => vec![0],
+            Epoch::II => vec![1],
+            Epoch::IIa => vec![2],
+            Epoch::IIb => vec![3],
+            Epoch::III => vec![4],
+            Epoch::IIIa => vec![5],
+            Epoch::IIIb => vec![6],
+            Epoch::IV => vec![7],
+            Epoch::IVa => vec![8],
+            Epoch::IVb => vec![9],
+            Epoch::V => vec![10],
+            Epoch::Va => vec![11],
+            Epoch::Vb => vec![12],
+            Epoch::Vm => vec![13],
+            Epoch::VI => vec![14],
+            Epoch::Multiple(epochs) => {
+                epochs.iter().flat_map(|ep| ep.ranks()).collect()
+            }
+        }
+    }
+}
+
+impl cmp::PartialOrd for Epoch {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::Ord for Epoch {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.ranks().cmp(&other.ranks())
+    }
+}
+
 /// The control method for this railway model.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Control {
+    /// The model is analog only, without a socket for a dcc decoder.
+    Analog,
+
     /// The model can be fitted with a dcc decoder.
     DccReady,
 
@@ -125,6 +214,8 @@ impl fmt::Display for Control {
     }
 }
 
+impl_serde_with_str!(Control, "control");
+
 impl str::FromStr for Control {
     type Err = &'static str;
 
@@ -134,11 +225,53 @@ impl str::FromStr for Control {
         }
 
         match s {
+            "ANALOG" => Ok(Control::Analog),
             "DCC_READY" => Ok(Control::DccReady),
             "DCC" => Ok(Control::Dcc),
             "DCC_SOUND" => Ok(Control::DccSound),
-            _ => Err("Invalid value for control [allowed values are DCC, DCC_READY, DCC_SOUND]"),
+            _ => Err("Invalid value for control [allowed values are ANALOG, DCC, DCC_READY, DCC_SOUND]"),
+        }
+    }
+}
+
+/// The dcc decoder installed on a model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decoder {
+    maker: String,
+    model: String,
+    sound: bool,
+}
+
+impl Decoder {
+    pub fn new(maker: &str, model: &str, sound: bool) -> Self {
+        Decoder {
+            maker: maker.to_owned(),
+            model: model.to_owned(),
+            sound,
+        }
+    }
+
+    pub fn maker(&self) -> &str {
+        &self.maker
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Checks whether the decoder has the sound module
+    pub fn with_sound(&self) -> bool {
+        self.sound
+    }
+}
+
+impl fmt::Display for Decoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.maker, self.model)?;
+        if self.sound {
+            write!(f, " (sound)")?;
         }
+        Ok(())
     }
 }
 
@@ -154,18 +287,44 @@ impl LengthOverBuffer {
         }
         LengthOverBuffer(value)
     }
+
+    /// Creates a new value, it fails when the provided value is not positive.
+    pub fn try_new(value: u32) -> Result<Self, &'static str> {
+        if value == 0 {
+            Err("Invalid length over buffer [it must be positive]")
+        } else {
+            Ok(LengthOverBuffer(value))
+        }
+    }
+
+    /// Returns the length in millimeters
+    pub fn value(&self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for LengthOverBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} mm", self.0)
+    }
 }
 
 /// NMRA and NEM Connectors for digital control (DCC)
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum DccInterface {
     Nem651,
     Nem652,
     Plux8,
+    Plux12,
     Plux16,
     Plux22,
     Next18,
+    /// The NEXT18 variant with the extra pins for the sound decoders
+    Next18S,
+    Mtc14,
     Mtc21,
+    /// Older models without a connector, the decoder needs to be soldered
+    Wired,
 }
 
 impl str::FromStr for DccInterface {
@@ -177,22 +336,69 @@ impl str::FromStr for DccInterface {
         }
 
         match s {
-            "NEM_651" => Ok(DccInterface::Nem651),
-            "NEM_652" => Ok(DccInterface::Nem652),
-            "PLUX_8" => Ok(DccInterface::Plux8),
-            "PLUX_16" => Ok(DccInterface::Plux16),
-            "PLUX_22" => Ok(DccInterface::Plux22),
-            "NEXT_18" => Ok(DccInterface::Next18),
-            "MTC_21" => Ok(DccInterface::Mtc21),
-            _ => Err("Invalid value for dcc interfaces"),
+            "NEM_651" | "NEM651" => Ok(DccInterface::Nem651),
+            "NEM_652" | "NEM652" => Ok(DccInterface::Nem652),
+            "PLUX_8" | "PLUX8" => Ok(DccInterface::Plux8),
+            "PLUX_12" | "PLUX12" => Ok(DccInterface::Plux12),
+            "PLUX_16" | "PLUX16" => Ok(DccInterface::Plux16),
+            "PLUX_22" | "PLUX22" => Ok(DccInterface::Plux22),
+            "NEXT_18" | "NEXT18" => Ok(DccInterface::Next18),
+            "NEXT_18S" | "NEXT18S" => Ok(DccInterface::Next18S),
+            "MTC_14" | "MTC14" => Ok(DccInterface::Mtc14),
+            "MTC_21" | "MTC21" => Ok(DccInterface::Mtc21),
+            "WIRED" => Ok(DccInterface::Wired),
+            _ => Err("Invalid value for dcc interfaces [allowed values are NEM651, NEM652, PLUX8, PLUX12, PLUX16, PLUX22, NEXT18, NEXT18S, MTC14, MTC21, WIRED]"),
         }
     }
 }
 
 impl fmt::Display for DccInterface {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = format!("{:?}", self);
-        write!(f, "{}", s.to_shouty_snake_case())
+        let s = match self {
+            DccInterface::Nem651 => "NEM651",
+            DccInterface::Nem652 => "NEM652",
+            DccInterface::Plux8 => "PLUX8",
+            DccInterface::Plux12 => "PLUX12",
+            DccInterface::Plux16 => "PLUX16",
+            DccInterface::Plux22 => "PLUX22",
+            DccInterface::Next18 => "NEXT18",
+            DccInterface::Next18S => "NEXT18S",
+            DccInterface::Mtc14 => "MTC14",
+            DccInterface::Mtc21 => "MTC21",
+            DccInterface::Wired => "WIRED",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl_serde_with_str!(DccInterface, "dcc interface");
+
+/// The DCC address for a decoder, valid values are in the range 1-10239.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DccAddress(u16);
+
+impl DccAddress {
+    const MIN_VALUE: u16 = 1;
+    const MAX_VALUE: u16 = 10239;
+
+    /// Creates a new dcc address, it fails when the value is outside the valid range.
+    pub fn new(value: u16) -> Result<Self, &'static str> {
+        if (DccAddress::MIN_VALUE..=DccAddress::MAX_VALUE).contains(&value) {
+            Ok(DccAddress(value))
+        } else {
+            Err("Invalid dcc address [allowed values are 1-10239]")
+        }
+    }
+
+    /// Returns the address value
+    pub fn value(&self) -> u16 {
+        self.0
+    }
+}
+
+impl fmt::Display for DccAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
     }
 }
 
@@ -208,6 +414,7 @@ pub enum ServiceLevel {
     FirstAndSecondClass,
     FirstSecondAndThirdClass,
     SecondAndThirdClass,
+    FirstAndThirdClass,
 }
 
 impl ServiceLevel {
@@ -247,10 +454,18 @@ impl fmt::Display for ServiceLevel {
                 ServiceLevel::SECOND_CLASS,
                 ServiceLevel::THIRD_CLASS
             ),
+            ServiceLevel::FirstAndThirdClass => write!(
+                f,
+                "{}/{}",
+                ServiceLevel::FIRST_CLASS,
+                ServiceLevel::THIRD_CLASS
+            ),
         }
     }
 }
 
+impl_serde_with_str!(ServiceLevel, "service level");
+
 impl str::FromStr for ServiceLevel {
     type Err = &'static str;
 
@@ -261,8 +476,12 @@ impl str::FromStr for ServiceLevel {
 
         let service_level;
         if s.contains('/') {
+            let raw_tokens: Vec<&str> = s.split_terminator('/').collect();
             let tokens: Vec<&str> =
-                s.split_terminator('/').sorted().dedup().collect();
+                raw_tokens.iter().copied().sorted().dedup().collect();
+            if tokens.len() != raw_tokens.len() {
+                return Err("Invalid mixed service level: duplicated values");
+            }
 
             if tokens.len() == 2 {
                 let first = tokens[0];
@@ -275,6 +494,10 @@ impl str::FromStr for ServiceLevel {
                     && second == ServiceLevel::THIRD_CLASS
                 {
                     service_level = ServiceLevel::SecondAndThirdClass;
+                } else if first == ServiceLevel::FIRST_CLASS
+                    && second == ServiceLevel::THIRD_CLASS
+                {
+                    service_level = ServiceLevel::FirstAndThirdClass;
                 } else {
                     return Err("Invalid mixed service level");
                 }
@@ -322,6 +545,8 @@ pub enum RollingStock {
         length_over_buffer: Option<LengthOverBuffer>,
         control: Option<Control>,
         dcc_interface: Option<DccInterface>,
+        dcc_address: Option<DccAddress>,
+        decoder: Option<Decoder>,
     },
     FreightCar {
         type_name: String,
@@ -356,16 +581,18 @@ pub enum RollingStock {
         length_over_buffer: Option<LengthOverBuffer>,
         control: Option<Control>,
         dcc_interface: Option<DccInterface>,
+        dcc_address: Option<DccAddress>,
+        decoder: Option<Decoder>,
     },
 }
 
 impl RollingStock {
     pub fn depot(&self) -> Option<&str> {
         match self {
-            RollingStock::Locomotive {
-                depot: Some(depot), ..
-            } => Some(depot),
-            _ => None,
+            RollingStock::Locomotive { depot, .. } => depot.as_deref(),
+            RollingStock::FreightCar { depot, .. } => depot.as_deref(),
+            RollingStock::PassengerCar { depot, .. } => depot.as_deref(),
+            RollingStock::Train { depot, .. } => depot.as_deref(),
         }
     }
 
@@ -376,13 +603,45 @@ impl RollingStock {
         }
     }
 
+    /// Returns the type name for the rolling stocks which are not locomotives
+    pub fn type_name(&self) -> Option<&str> {
+        match self {
+            RollingStock::FreightCar { type_name, .. } => Some(type_name),
+            RollingStock::PassengerCar { type_name, .. } => Some(type_name),
+            RollingStock::Train { type_name, .. } => Some(type_name),
+            _ => None,
+        }
+    }
+
     pub fn road_number(&self) -> Option<&str> {
         match self {
             RollingStock::Locomotive { road_number, .. } => Some(road_number),
+            RollingStock::FreightCar { road_number, .. } => {
+                road_number.as_deref()
+            }
+            RollingStock::PassengerCar { road_number, .. } => {
+                road_number.as_deref()
+            }
+            RollingStock::Train { road_number, .. } => road_number.as_deref(),
+        }
+    }
+
+    /// Returns the service level for passenger cars
+    pub fn service_level(&self) -> Option<ServiceLevel> {
+        match self {
+            RollingStock::PassengerCar { service_level, .. } => *service_level,
             _ => None,
         }
     }
 
+    /// Returns the number of elements, this is always 1 for everything but trains
+    pub fn n_of_elements(&self) -> u8 {
+        match self {
+            RollingStock::Train { n_of_elements, .. } => *n_of_elements,
+            _ => 1,
+        }
+    }
+
     pub fn series(&self) -> Option<&str> {
         match self {
             RollingStock::Locomotive {
@@ -395,11 +654,20 @@ impl RollingStock {
 
     pub fn livery(&self) -> Option<&str> {
         match self {
-            RollingStock::Locomotive {
-                livery: Some(livery),
-                ..
-            } => Some(livery),
-            _ => None,
+            RollingStock::Locomotive { livery, .. } => livery.as_deref(),
+            RollingStock::FreightCar { livery, .. } => livery.as_deref(),
+            RollingStock::PassengerCar { livery, .. } => livery.as_deref(),
+            RollingStock::Train { livery, .. } => livery.as_deref(),
+        }
+    }
+
+    /// Returns the railway company for this rolling stock
+    pub fn railway(&self) -> &Railway {
+        match self {
+            RollingStock::Locomotive { railway, .. } => railway,
+            RollingStock::FreightCar { railway, .. } => railway,
+            RollingStock::PassengerCar { railway, .. } => railway,
+            RollingStock::Train { railway, .. } => railway,
         }
     }
 
@@ -413,33 +681,71 @@ impl RollingStock {
         }
     }
 
-    // pub fn epoch(&self) -> Epoch {
-    //     match &self {
-    //         RollingStock::Locomotive { epoch, .. } => *epoch.clone(),
-    //         RollingStock::FreightCar { epoch, .. } => *epoch.clone(),
-    //         RollingStock::PassengerCar { epoch, .. } => *epoch.clone(),
-    //         RollingStock::Train { epoch, .. } => *epoch.clone(),
-    //     }
-    // }
+    /// Returns the epoch for this rolling stock
+    pub fn epoch(&self) -> &Epoch {
+        match self {
+            RollingStock::Locomotive { epoch, .. } => epoch,
+            RollingStock::FreightCar { epoch, .. } => epoch,
+            RollingStock::PassengerCar { epoch, .. } => epoch,
+            RollingStock::Train { epoch, .. } => epoch,
+        }
+    }
+
+    /// Returns the length over buffer for this rolling stock
+    pub fn length_over_buffer(&self) -> Option<LengthOverBuffer> {
+        match self {
+            RollingStock::Locomotive {
+                length_over_buffer, ..
+            } => *length_over_buffer,
+            RollingStock::FreightCar {
+                length_over_buffer, ..
+            } => *length_over_buffer,
+            RollingStock::PassengerCar {
+                length_over_buffer, ..
+            } => *length_over_buffer,
+            RollingStock::Train {
+                length_over_buffer, ..
+            } => *length_over_buffer,
+        }
+    }
 
     pub fn is_locomotive(&self) -> bool {
         self.category() == Category::Locomotives
     }
 
+    pub fn is_train(&self) -> bool {
+        self.category() == Category::Trains
+    }
+
+    /// Checks whether a dcc decoder is installed, the explicit decoder info
+    /// (when present) wins over the control value.
     pub fn with_decoder(&self) -> bool {
+        if self.decoder().is_some() {
+            return true;
+        }
+
         match self {
             RollingStock::Locomotive {
                 control: Some(control),
                 ..
-            } => *control != Control::DccReady,
-            RollingStock::Train {
+            }
+            | RollingStock::Train {
                 control: Some(control),
                 ..
-            } => *control != Control::DccReady,
+            } => !matches!(control, Control::Analog | Control::DccReady),
             _ => false,
         }
     }
 
+    /// Returns the dcc decoder installed, if known
+    pub fn decoder(&self) -> Option<&Decoder> {
+        match self {
+            RollingStock::Locomotive { decoder, .. } => decoder.as_ref(),
+            RollingStock::Train { decoder, .. } => decoder.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn dcc_interface(&self) -> Option<DccInterface> {
         match self {
             RollingStock::Locomotive {
@@ -454,7 +760,16 @@ impl RollingStock {
         }
     }
 
+    pub fn dcc_address(&self) -> Option<DccAddress> {
+        match self {
+            RollingStock::Locomotive { dcc_address, .. } => *dcc_address,
+            RollingStock::Train { dcc_address, .. } => *dcc_address,
+            _ => None,
+        }
+    }
+
     /// Creates a new freight car rolling stock
+    #[deprecated(since = "0.2.0", note = "use the RollingStockBuilder instead")]
     #[allow(clippy::too_many_arguments)]
     pub fn new_freight_car(
         type_name: String,
@@ -466,19 +781,23 @@ impl RollingStock {
         livery: Option<String>,
         length_over_buffer: Option<LengthOverBuffer>,
     ) -> Self {
-        RollingStock::FreightCar {
-            type_name,
+        RollingStockBuilder {
+            type_name: Some(type_name),
             road_number,
-            railway,
-            epoch,
-            category,
+            railway: Some(railway),
+            epoch: Some(epoch),
+            freight_car_type: category,
             depot,
             livery,
             length_over_buffer,
+            ..RollingStockBuilder::freight_car()
         }
+        .build()
+        .expect("the freight car fields are set")
     }
 
     /// Creates a new train rolling stock
+    #[deprecated(since = "0.2.0", note = "use the RollingStockBuilder instead")]
     #[allow(clippy::too_many_arguments)]
     pub fn new_train(
         type_name: String,
@@ -492,23 +811,29 @@ impl RollingStock {
         length_over_buffer: Option<LengthOverBuffer>,
         control: Option<Control>,
         dcc_interface: Option<DccInterface>,
+        dcc_address: Option<DccAddress>,
     ) -> Self {
-        RollingStock::Train {
-            type_name,
+        RollingStockBuilder {
+            type_name: Some(type_name),
             road_number,
-            n_of_elements,
-            railway,
-            epoch,
-            category,
+            n_of_elements: Some(n_of_elements),
+            railway: Some(railway),
+            epoch: Some(epoch),
+            train_type: category,
             depot,
             livery,
             length_over_buffer,
             control,
             dcc_interface,
+            dcc_address,
+            ..RollingStockBuilder::train()
         }
+        .build()
+        .expect("the train fields are set")
     }
 
     /// Creates a new locomotive rolling stock
+    #[deprecated(since = "0.2.0", note = "use the RollingStockBuilder instead")]
     #[allow(clippy::too_many_arguments)]
     pub fn new_locomotive(
         class_name: String,
@@ -522,23 +847,29 @@ impl RollingStock {
         length_over_buffer: Option<LengthOverBuffer>,
         control: Option<Control>,
         dcc_interface: Option<DccInterface>,
+        dcc_address: Option<DccAddress>,
     ) -> Self {
-        RollingStock::Locomotive {
-            class_name,
-            road_number,
+        RollingStockBuilder {
+            class_name: Some(class_name),
+            road_number: Some(road_number),
             series,
-            railway,
-            epoch,
-            category,
+            railway: Some(railway),
+            epoch: Some(epoch),
+            locomotive_type: Some(category),
             depot,
             livery,
             length_over_buffer,
             control,
             dcc_interface,
+            dcc_address,
+            ..RollingStockBuilder::locomotive()
         }
+        .build()
+        .expect("the locomotive fields are set")
     }
 
     /// Creates a new passenger car rolling stock
+    #[deprecated(since = "0.2.0", note = "use the RollingStockBuilder instead")]
     #[allow(clippy::too_many_arguments)]
     pub fn new_passenger_car(
         type_name: String,
@@ -551,16 +882,338 @@ impl RollingStock {
         livery: Option<String>,
         length_over_buffer: Option<LengthOverBuffer>,
     ) -> Self {
-        RollingStock::PassengerCar {
-            type_name,
+        RollingStockBuilder {
+            type_name: Some(type_name),
             road_number,
-            railway,
-            epoch,
-            category,
+            railway: Some(railway),
+            epoch: Some(epoch),
+            passenger_car_type: category,
             service_level,
             depot,
             livery,
             length_over_buffer,
+            ..RollingStockBuilder::passenger_car()
+        }
+        .build()
+        .expect("the passenger car fields are set")
+    }
+}
+
+/// The errors building a rolling stock.
+#[derive(Error, Debug, PartialEq)]
+pub enum BuildError {
+    #[error("Missing {field} (required for {category:?})")]
+    MissingField {
+        category: Category,
+        field: &'static str,
+    },
+    #[error("The {field} is not applicable to {category:?}")]
+    NotApplicable {
+        category: Category,
+        field: &'static str,
+    },
+}
+
+/// A builder for the rolling stocks, with named setters for every field.
+///
+/// The mandatory fields depend on the category, and they are checked by
+/// `build()`: the railway and the epoch are always required, locomotives
+/// also need the class name, road number and locomotive type, while the
+/// other categories need the type name. Setting a field that does not belong
+/// to the category (like a service level for a locomotive) is an error.
+#[derive(Debug)]
+pub struct RollingStockBuilder {
+    category: Category,
+    class_name: Option<String>,
+    type_name: Option<String>,
+    road_number: Option<String>,
+    series: Option<String>,
+    railway: Option<Railway>,
+    epoch: Option<Epoch>,
+    locomotive_type: Option<LocomotiveType>,
+    freight_car_type: Option<FreightCarType>,
+    passenger_car_type: Option<PassengerCarType>,
+    train_type: Option<TrainType>,
+    service_level: Option<ServiceLevel>,
+    n_of_elements: Option<u8>,
+    depot: Option<String>,
+    livery: Option<String>,
+    length_over_buffer: Option<LengthOverBuffer>,
+    control: Option<Control>,
+    dcc_interface: Option<DccInterface>,
+    dcc_address: Option<DccAddress>,
+    decoder: Option<Decoder>,
+}
+
+impl RollingStockBuilder {
+    /// Creates a new builder for a rolling stock with the given category.
+    pub fn new(category: Category) -> Self {
+        RollingStockBuilder {
+            category,
+            class_name: None,
+            type_name: None,
+            road_number: None,
+            series: None,
+            railway: None,
+            epoch: None,
+            locomotive_type: None,
+            freight_car_type: None,
+            passenger_car_type: None,
+            train_type: None,
+            service_level: None,
+            n_of_elements: None,
+            depot: None,
+            livery: None,
+            length_over_buffer: None,
+            control: None,
+            dcc_interface: None,
+            dcc_address: None,
+            decoder: None,
+        }
+    }
+
+    pub fn locomotive() -> Self {
+        RollingStockBuilder::new(Category::Locomotives)
+    }
+
+    pub fn freight_car() -> Self {
+        RollingStockBuilder::new(Category::FreightCars)
+    }
+
+    pub fn passenger_car() -> Self {
+        RollingStockBuilder::new(Category::PassengerCars)
+    }
+
+    pub fn train() -> Self {
+        RollingStockBuilder::new(Category::Trains)
+    }
+
+    pub fn with_class_name(mut self, class_name: &str) -> Self {
+        self.class_name = Some(class_name.to_owned());
+        self
+    }
+
+    pub fn with_type_name(mut self, type_name: &str) -> Self {
+        self.type_name = Some(type_name.to_owned());
+        self
+    }
+
+    pub fn with_road_number(mut self, road_number: &str) -> Self {
+        self.road_number = Some(road_number.to_owned());
+        self
+    }
+
+    pub fn with_series(mut self, series: &str) -> Self {
+        self.series = Some(series.to_owned());
+        self
+    }
+
+    pub fn with_railway(mut self, railway: Railway) -> Self {
+        self.railway = Some(railway);
+        self
+    }
+
+    pub fn with_epoch(mut self, epoch: Epoch) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
+    pub fn with_locomotive_type(
+        mut self,
+        locomotive_type: LocomotiveType,
+    ) -> Self {
+        self.locomotive_type = Some(locomotive_type);
+        self
+    }
+
+    pub fn with_freight_car_type(
+        mut self,
+        freight_car_type: FreightCarType,
+    ) -> Self {
+        self.freight_car_type = Some(freight_car_type);
+        self
+    }
+
+    pub fn with_passenger_car_type(
+        mut self,
+        passenger_car_type: PassengerCarType,
+    ) -> Self {
+        self.passenger_car_type = Some(passenger_car_type);
+        self
+    }
+
+    pub fn with_train_type(mut self, train_type: TrainType) -> Self {
+        self.train_type = Some(train_type);
+        self
+    }
+
+    pub fn with_service_level(mut self, service_level: ServiceLevel) -> Self {
+        self.service_level = Some(service_level);
+        self
+    }
+
+    /// Sets the number of elements for a train (1 when not provided).
+    pub fn with_n_of_elements(mut self, n_of_elements: u8) -> Self {
+        self.n_of_elements = Some(n_of_elements);
+        self
+    }
+
+    pub fn with_depot(mut self, depot: &str) -> Self {
+        self.depot = Some(depot.to_owned());
+        self
+    }
+
+    pub fn with_livery(mut self, livery: &str) -> Self {
+        self.livery = Some(livery.to_owned());
+        self
+    }
+
+    pub fn with_length_over_buffer(
+        mut self,
+        length_over_buffer: LengthOverBuffer,
+    ) -> Self {
+        self.length_over_buffer = Some(length_over_buffer);
+        self
+    }
+
+    pub fn with_control(mut self, control: Control) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    pub fn with_dcc_interface(mut self, dcc_interface: DccInterface) -> Self {
+        self.dcc_interface = Some(dcc_interface);
+        self
+    }
+
+    pub fn with_dcc_address(mut self, dcc_address: DccAddress) -> Self {
+        self.dcc_address = Some(dcc_address);
+        self
+    }
+
+    pub fn with_decoder(mut self, decoder: Decoder) -> Self {
+        self.decoder = Some(decoder);
+        self
+    }
+
+    /// Builds the rolling stock, checking the mandatory fields for its category.
+    pub fn build(self) -> Result<RollingStock, BuildError> {
+        let category = self.category;
+        let is_set = [
+            ("class name", self.class_name.is_some()),
+            ("type name", self.type_name.is_some()),
+            ("series", self.series.is_some()),
+            ("locomotive type", self.locomotive_type.is_some()),
+            ("freight car type", self.freight_car_type.is_some()),
+            ("passenger car type", self.passenger_car_type.is_some()),
+            ("train type", self.train_type.is_some()),
+            ("service level", self.service_level.is_some()),
+            ("number of elements", self.n_of_elements.is_some()),
+            ("control", self.control.is_some()),
+            ("dcc interface", self.dcc_interface.is_some()),
+            ("dcc address", self.dcc_address.is_some()),
+            ("decoder", self.decoder.is_some()),
+        ];
+        let applicable: &[&str] = match category {
+            Category::Locomotives => &[
+                "class name",
+                "series",
+                "locomotive type",
+                "control",
+                "dcc interface",
+                "dcc address",
+                "decoder",
+            ],
+            Category::FreightCars => &["type name", "freight car type"],
+            Category::PassengerCars => {
+                &["type name", "passenger car type", "service level"]
+            }
+            Category::Trains => &[
+                "type name",
+                "train type",
+                "number of elements",
+                "control",
+                "dcc interface",
+                "dcc address",
+                "decoder",
+            ],
+        };
+        if let Some((field, _)) = is_set
+            .iter()
+            .find(|(field, set)| *set && !applicable.contains(field))
+        {
+            return Err(BuildError::NotApplicable { category, field });
+        }
+
+        let missing =
+            |field: &'static str| BuildError::MissingField { category, field };
+        let railway = self.railway.ok_or_else(|| missing("railway"))?;
+        let epoch = self.epoch.ok_or_else(|| missing("epoch"))?;
+
+        match category {
+            Category::Locomotives => Ok(RollingStock::Locomotive {
+                class_name: self
+                    .class_name
+                    .ok_or_else(|| missing("class name"))?,
+                road_number: self
+                    .road_number
+                    .ok_or_else(|| missing("road number"))?,
+                series: self.series,
+                railway,
+                epoch,
+                category: self
+                    .locomotive_type
+                    .ok_or_else(|| missing("locomotive type"))?,
+                depot: self.depot,
+                livery: self.livery,
+                length_over_buffer: self.length_over_buffer,
+                control: self.control,
+                dcc_interface: self.dcc_interface,
+                dcc_address: self.dcc_address,
+                decoder: self.decoder,
+            }),
+            Category::FreightCars => Ok(RollingStock::FreightCar {
+                type_name: self
+                    .type_name
+                    .ok_or_else(|| missing("type name"))?,
+                road_number: self.road_number,
+                railway,
+                epoch,
+                category: self.freight_car_type,
+                depot: self.depot,
+                livery: self.livery,
+                length_over_buffer: self.length_over_buffer,
+            }),
+            Category::PassengerCars => Ok(RollingStock::PassengerCar {
+                type_name: self
+                    .type_name
+                    .ok_or_else(|| missing("type name"))?,
+                road_number: self.road_number,
+                railway,
+                epoch,
+                category: self.passenger_car_type,
+                service_level: self.service_level,
+                depot: self.depot,
+                livery: self.livery,
+                length_over_buffer: self.length_over_buffer,
+            }),
+            Category::Trains => Ok(RollingStock::Train {
+                type_name: self
+                    .type_name
+                    .ok_or_else(|| missing("type name"))?,
+                road_number: self.road_number,
+                n_of_elements: self.n_of_elements.unwrap_or(1),
+                railway,
+                epoch,
+                category: self.train_type,
+                depot: self.depot,
+                livery: self.livery,
+                length_over_buffer: self.length_over_buffer,
+                control: self.control,
+                dcc_interface: self.dcc_interface,
+                dcc_address: self.dcc_address,
+                decoder: self.decoder,
+            }),
         }
     }
 }
@@ -579,6 +1232,13 @@ mod tests {
             assert_eq!(dcc.unwrap(), DccInterface::Nem652);
         }
 
+        #[test]
+        fn it_should_parse_displayed_values_as_dcc_interfaces() {
+            let dcc = "PLUX22".parse::<DccInterface>();
+            assert!(dcc.is_ok());
+            assert_eq!(dcc.unwrap(), DccInterface::Plux22);
+        }
+
         #[test]
         fn it_should_fail_to_parse_invalid_string_as_dcc_interfaces() {
             let blank = "".parse::<DccInterface>();
@@ -593,11 +1253,87 @@ mod tests {
             let dcc = DccInterface::Nem652;
             assert_eq!("NEM652", dcc.to_string());
         }
+
+        #[test]
+        fn it_should_parse_both_spellings_for_the_next18s_interface() {
+            assert_eq!(Ok(DccInterface::Next18S), "NEXT_18S".parse());
+            assert_eq!(Ok(DccInterface::Next18S), "NEXT18S".parse());
+        }
+
+        #[test]
+        fn it_should_round_trip_every_dcc_interface() {
+            let interfaces = [
+                DccInterface::Nem651,
+                DccInterface::Nem652,
+                DccInterface::Plux8,
+                DccInterface::Plux12,
+                DccInterface::Plux16,
+                DccInterface::Plux22,
+                DccInterface::Next18,
+                DccInterface::Next18S,
+                DccInterface::Mtc14,
+                DccInterface::Mtc21,
+                DccInterface::Wired,
+            ];
+
+            for dcc in interfaces {
+                assert_eq!(Ok(dcc), dcc.to_string().parse::<DccInterface>());
+            }
+        }
+    }
+
+    mod dcc_address_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_create_new_dcc_addresses() {
+            let address = DccAddress::new(3);
+            assert!(address.is_ok());
+            assert_eq!(3, address.unwrap().value());
+        }
+
+        #[test]
+        fn it_should_fail_to_create_dcc_addresses_outside_the_valid_range() {
+            assert!(DccAddress::new(0).is_err());
+            assert!(DccAddress::new(10240).is_err());
+            assert!(DccAddress::new(10239).is_ok());
+        }
+
+        #[test]
+        fn it_should_display_dcc_addresses() {
+            let address = DccAddress::new(1234).unwrap();
+            assert_eq!("1234", address.to_string());
+        }
+    }
+
+    mod length_over_buffer_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_fail_to_create_lengths_not_positive() {
+            assert!(LengthOverBuffer::try_new(0).is_err());
+            assert_eq!(210, LengthOverBuffer::try_new(210).unwrap().value());
+        }
     }
 
     mod epoch_tests {
         use super::*;
 
+        #[test]
+        fn it_should_serialize_epochs_as_strings() {
+            let epoch: Epoch = "IV/III".parse().unwrap();
+
+            let json = serde_json::to_string(&epoch).unwrap();
+            assert_eq!("\"III/IV\"", json);
+            assert_eq!(epoch, serde_json::from_str::<Epoch>(&json).unwrap());
+        }
+
+        #[test]
+        fn it_should_report_the_invalid_value_deserializing_epochs() {
+            let error = serde_json::from_str::<Epoch>("\"IX\"").unwrap_err();
+            assert!(error.to_string().starts_with("Invalid epoch 'IX'"));
+        }
+
         #[test]
         fn it_should_convert_string_slices_to_epochs() {
             let epoch = "I".parse::<Epoch>();
@@ -611,10 +1347,50 @@ mod tests {
             assert!(epoch.is_ok());
             assert_eq!(
                 epoch.unwrap(),
-                Epoch::Multiple(Box::new(Epoch::I), Box::new(Epoch::II))
+                Epoch::Multiple(vec![Epoch::I, Epoch::II])
             );
         }
 
+        #[test]
+        fn it_should_convert_string_slices_to_epochs_in_chronological_order() {
+            let epoch = "V/III/IV".parse::<Epoch>().unwrap();
+            assert_eq!(
+                Epoch::Multiple(vec![Epoch::III, Epoch::IV, Epoch::V]),
+                epoch
+            );
+            assert_eq!("III/IV/V", epoch.to_string());
+
+            let epoch = "VI/II".parse::<Epoch>().unwrap();
+            assert_eq!("II/VI", epoch.to_string());
+
+            let epoch = "IIIb/IIIa".parse::<Epoch>().unwrap();
+            assert_eq!("IIIa/IIIb", epoch.to_string());
+        }
+
+        #[test]
+        fn it_should_fail_to_convert_too_many_epochs() {
+            assert!("II/III/IV/V".parse::<Epoch>().is_ok());
+            assert!("II/III/IV/V/VI".parse::<Epoch>().is_err());
+            assert!("IV/IV".parse::<Epoch>().is_err());
+            assert!("IV/X".parse::<Epoch>().is_err());
+        }
+
+        #[test]
+        fn it_should_order_epochs_chronologically() {
+            assert!(Epoch::II < Epoch::IIa);
+            assert!(Epoch::IIa < Epoch::IIb);
+            assert!(Epoch::IIb < Epoch::III);
+            assert!(Epoch::III < Epoch::IIIa);
+            assert!(Epoch::IIIa < Epoch::IIIb);
+            assert!(Epoch::IIIb < Epoch::IV);
+            assert!(Epoch::Vm < Epoch::VI);
+
+            let multiple = "III/IV".parse::<Epoch>().unwrap();
+            assert!(Epoch::III < multiple);
+            assert!(multiple < Epoch::IIIa);
+            assert!(multiple < "III/V".parse::<Epoch>().unwrap());
+        }
+
         #[test]
         fn it_should_fail_to_convert_invalid_values_to_epochs() {
             let empty_epoch = "".parse::<Epoch>();
@@ -624,11 +1400,34 @@ mod tests {
             assert!(invalid_epoch.is_err());
         }
 
+        #[test]
+        fn it_should_check_whether_an_epoch_includes_another() {
+            let multiple = Epoch::Multiple(vec![Epoch::IV, Epoch::V]);
+
+            assert!(Epoch::IV.includes(&Epoch::IV));
+            assert!(!Epoch::IV.includes(&Epoch::V));
+            assert!(multiple.includes(&Epoch::IV));
+            assert!(multiple.includes(&Epoch::V));
+            assert!(!multiple.includes(&Epoch::III));
+        }
+
+        #[test]
+        fn it_should_check_whether_two_epochs_overlap() {
+            let multiple = Epoch::Multiple(vec![Epoch::IV, Epoch::V]);
+
+            assert!(Epoch::IV.overlaps(&Epoch::IV));
+            assert!(Epoch::IV.overlaps(&Epoch::IVa));
+            assert!(Epoch::Vm.overlaps(&Epoch::Vb));
+            assert!(!Epoch::IV.overlaps(&Epoch::V));
+            assert!(multiple.overlaps(&Epoch::IVb));
+            assert!(Epoch::Va.overlaps(&multiple));
+            assert!(!multiple.overlaps(&Epoch::IIIa));
+        }
+
         #[test]
         #[allow(non_snake_case)]
         fn it_should_diplay_epoch_values() {
-            let epoch_I_II =
-                Epoch::Multiple(Box::new(Epoch::I), Box::new(Epoch::II));
+            let epoch_I_II = Epoch::Multiple(vec![Epoch::I, Epoch::II]);
             let epoch_IVa = Epoch::IVa;
 
             assert_eq!("I/II", epoch_I_II.to_string());
@@ -660,6 +1459,71 @@ mod tests {
             let c = Control::DccReady;
             assert_eq!("DCC_READY", c.to_string());
         }
+
+        #[test]
+        fn it_should_parse_and_display_the_analog_control() {
+            assert_eq!(Ok(Control::Analog), "ANALOG".parse::<Control>());
+            assert_eq!("ANALOG", Control::Analog.to_string());
+        }
+    }
+
+    mod decoder_tests {
+        use super::*;
+
+        fn new_locomotive(
+            control: Option<Control>,
+            decoder: Option<Decoder>,
+        ) -> RollingStock {
+            let mut builder = RollingStockBuilder::locomotive()
+                .with_class_name("E.656")
+                .with_road_number("E.656 210")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_locomotive_type(LocomotiveType::ElectricLocomotive);
+            if let Some(control) = control {
+                builder = builder.with_control(control);
+            }
+            if let Some(decoder) = decoder {
+                builder = builder.with_decoder(decoder);
+            }
+            builder.build().unwrap()
+        }
+
+        #[test]
+        fn it_should_have_no_decoder_for_analog_models() {
+            let rs = new_locomotive(Some(Control::Analog), None);
+            assert!(!rs.with_decoder());
+        }
+
+        #[test]
+        fn it_should_prefer_the_decoder_info_over_the_control() {
+            let decoder = Decoder::new("ESU", "LokSound 5", true);
+            let rs = new_locomotive(Some(Control::DccReady), Some(decoder));
+
+            assert!(rs.with_decoder());
+            assert_eq!(
+                "ESU LokSound 5 (sound)",
+                rs.decoder().unwrap().to_string()
+            );
+        }
+
+        #[test]
+        fn it_should_not_allow_decoders_for_passenger_cars() {
+            let result = RollingStockBuilder::passenger_car()
+                .with_type_name("UIC-Z")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_decoder(Decoder::new("Zimo", "MX600", false))
+                .build();
+
+            assert_eq!(
+                Some(BuildError::NotApplicable {
+                    category: Category::PassengerCars,
+                    field: "decoder",
+                }),
+                result.err()
+            );
+        }
     }
 
     mod rolling_stock_tests {
@@ -669,19 +1533,21 @@ mod tests {
         fn it_should_create_new_locomotives() {
             let railway_fs = Railway::new("FS");
 
-            let rs = RollingStock::new_locomotive(
-                String::from("E.656"),
-                String::from("E.656 210"),
-                Some(String::from("1a serie")),
-                railway_fs.clone(),
-                Epoch::IV,
-                LocomotiveType::ElectricLocomotive,
-                Some(String::from("Milano Centrale")),
-                Some(String::from("blu/grigio")),
-                Some(LengthOverBuffer::new(210)),
-                Some(Control::DccReady),
-                Some(DccInterface::Nem652),
-            );
+            let rs = RollingStockBuilder::locomotive()
+                .with_class_name("E.656")
+                .with_road_number("E.656 210")
+                .with_series("1a serie")
+                .with_railway(railway_fs.clone())
+                .with_epoch(Epoch::IV)
+                .with_locomotive_type(LocomotiveType::ElectricLocomotive)
+                .with_depot("Milano Centrale")
+                .with_livery("blu/grigio")
+                .with_length_over_buffer(LengthOverBuffer::new(210))
+                .with_control(Control::DccReady)
+                .with_dcc_interface(DccInterface::Nem652)
+                .with_dcc_address(DccAddress::new(656).unwrap())
+                .build()
+                .unwrap();
 
             match rs {
                 RollingStock::Locomotive {
@@ -696,6 +1562,7 @@ mod tests {
                     length_over_buffer,
                     control,
                     dcc_interface,
+                    dcc_address,
                     ..
                 } => {
                     assert_eq!(class_name, String::from("E.656"));
@@ -712,6 +1579,10 @@ mod tests {
                     );
                     assert_eq!(control, Some(Control::DccReady));
                     assert_eq!(dcc_interface, Some(DccInterface::Nem652));
+                    assert_eq!(
+                        dcc_address,
+                        Some(DccAddress::new(656).unwrap())
+                    );
                 }
                 _ => panic!(
                     "Invalid rolling stock type - expect a locomotive here!!!!"
@@ -723,19 +1594,20 @@ mod tests {
         fn it_should_create_new_trains() {
             let railway_fs = Railway::new("FS");
 
-            let rs = RollingStock::new_train(
-                String::from("Etr 220"),
-                None,
-                4,
-                railway_fs.clone(),
-                Epoch::IV,
-                Some(TrainType::ElectricMultipleUnits),
-                Some(String::from("Milano Centrale")),
-                Some(String::from("grigio nebbia/verde magnolia")),
-                Some(LengthOverBuffer::new(800)),
-                Some(Control::DccReady),
-                Some(DccInterface::Nem652),
-            );
+            let rs = RollingStockBuilder::train()
+                .with_type_name("Etr 220")
+                .with_n_of_elements(4)
+                .with_railway(railway_fs.clone())
+                .with_epoch(Epoch::IV)
+                .with_train_type(TrainType::ElectricMultipleUnits)
+                .with_depot("Milano Centrale")
+                .with_livery("grigio nebbia/verde magnolia")
+                .with_length_over_buffer(LengthOverBuffer::new(800))
+                .with_control(Control::DccReady)
+                .with_dcc_interface(DccInterface::Nem652)
+                .with_dcc_address(DccAddress::new(656).unwrap())
+                .build()
+                .unwrap();
 
             match rs {
                 RollingStock::Train {
@@ -750,6 +1622,7 @@ mod tests {
                     length_over_buffer,
                     control,
                     dcc_interface,
+                    dcc_address,
                     ..
                 } => {
                     assert_eq!(type_name, String::from("Etr 220"));
@@ -772,6 +1645,10 @@ mod tests {
                     );
                     assert_eq!(control, Some(Control::DccReady));
                     assert_eq!(dcc_interface, Some(DccInterface::Nem652));
+                    assert_eq!(
+                        dcc_address,
+                        Some(DccAddress::new(656).unwrap())
+                    );
                 }
                 _ => panic!(
                     "Invalid rolling stock type - expect a train here!!!!"
@@ -783,17 +1660,16 @@ mod tests {
         fn it_should_create_new_passenger_cars() {
             let railway_fs = Railway::new("FS");
 
-            let rs = RollingStock::new_passenger_car(
-                String::from("UIC-Z"),
-                None,
-                railway_fs.clone(),
-                Epoch::IV,
-                Some(PassengerCarType::OpenCoach),
-                Some(ServiceLevel::FirstClass),
-                None,
-                Some(String::from("bandiera")),
-                Some(LengthOverBuffer::new(303)),
-            );
+            let rs = RollingStockBuilder::passenger_car()
+                .with_type_name("UIC-Z")
+                .with_railway(railway_fs.clone())
+                .with_epoch(Epoch::IV)
+                .with_passenger_car_type(PassengerCarType::OpenCoach)
+                .with_service_level(ServiceLevel::FirstClass)
+                .with_livery("bandiera")
+                .with_length_over_buffer(LengthOverBuffer::new(303))
+                .build()
+                .unwrap();
 
             match rs {
                 RollingStock::PassengerCar {
@@ -826,16 +1702,15 @@ mod tests {
         fn it_should_create_new_freight_cars() {
             let railway_fs = Railway::new("FS");
 
-            let rs = RollingStock::new_freight_car(
-                String::from("Gbhs"),
-                None,
-                railway_fs.clone(),
-                Epoch::V,
-                Some(FreightCarType::SwingRoofWagon),
-                None,
-                Some(String::from("marrone")),
-                Some(LengthOverBuffer::new(122)),
-            );
+            let rs = RollingStockBuilder::freight_car()
+                .with_type_name("Gbhs")
+                .with_railway(railway_fs.clone())
+                .with_epoch(Epoch::V)
+                .with_freight_car_type(FreightCarType::SwingRoofWagon)
+                .with_livery("marrone")
+                .with_length_over_buffer(LengthOverBuffer::new(122))
+                .build()
+                .unwrap();
 
             match rs {
                 RollingStock::FreightCar {
@@ -863,6 +1738,171 @@ mod tests {
         }
     }
 
+    mod rolling_stock_builder_tests {
+        use super::*;
+
+        #[test]
+        #[allow(deprecated)]
+        fn it_should_create_the_same_rolling_stock_with_the_old_constructors() {
+            let rs = RollingStock::new_freight_car(
+                String::from("Gbhs"),
+                Some(String::from("21 83 2888 123-4")),
+                Railway::new("FS"),
+                Epoch::IV,
+                Some(FreightCarType::SlidingWallBoxcars),
+                None,
+                None,
+                Some(LengthOverBuffer::new(180)),
+            );
+
+            let expected = RollingStockBuilder::freight_car()
+                .with_type_name("Gbhs")
+                .with_road_number("21 83 2888 123-4")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_freight_car_type(FreightCarType::SlidingWallBoxcars)
+                .with_length_over_buffer(LengthOverBuffer::new(180))
+                .build()
+                .unwrap();
+            assert_eq!(expected, rs);
+        }
+
+        #[test]
+        fn it_should_build_trains_with_one_element_by_default() {
+            let rs = RollingStockBuilder::train()
+                .with_type_name("ALn 668")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .build()
+                .unwrap();
+
+            assert!(matches!(
+                rs,
+                RollingStock::Train {
+                    n_of_elements: 1,
+                    ..
+                }
+            ));
+        }
+
+        #[test]
+        fn it_should_fail_to_build_locomotives_without_the_mandatory_fields() {
+            let result = RollingStockBuilder::locomotive()
+                .with_class_name("E.656")
+                .with_road_number("E.656 210")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .build();
+
+            assert_eq!(
+                Err(BuildError::MissingField {
+                    category: Category::Locomotives,
+                    field: "locomotive type",
+                }),
+                result
+            );
+
+            let error = RollingStockBuilder::freight_car()
+                .with_type_name("Gbhs")
+                .with_railway(Railway::new("FS"))
+                .build()
+                .unwrap_err();
+            assert_eq!(
+                "Missing epoch (required for FreightCars)",
+                error.to_string()
+            );
+        }
+
+        #[test]
+        fn it_should_fail_to_build_rolling_stocks_with_fields_from_other_categories(
+        ) {
+            let result = RollingStockBuilder::passenger_car()
+                .with_type_name("UIC-Z")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_control(Control::Dcc)
+                .build();
+
+            assert_eq!(
+                Err(BuildError::NotApplicable {
+                    category: Category::PassengerCars,
+                    field: "control",
+                }),
+                result
+            );
+        }
+    }
+
+    mod rolling_stock_accessors_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_return_the_train_fields() {
+            let rs = RollingStockBuilder::train()
+                .with_type_name("ALn 668")
+                .with_road_number("ALn 668 1449")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_train_type(TrainType::Railcars)
+                .with_n_of_elements(2)
+                .with_depot("Cagliari")
+                .with_livery("livrea d'origine")
+                .with_control(Control::Dcc)
+                .build()
+                .unwrap();
+
+            assert!(rs.is_train());
+            assert_eq!(Some("ALn 668"), rs.type_name());
+            assert_eq!(Some("ALn 668 1449"), rs.road_number());
+            assert_eq!(Some("Cagliari"), rs.depot());
+            assert_eq!(Some("livrea d'origine"), rs.livery());
+            assert_eq!(2, rs.n_of_elements());
+            assert_eq!(None, rs.service_level());
+            assert_eq!(None, rs.class_name());
+        }
+
+        #[test]
+        fn it_should_return_the_passenger_car_fields() {
+            let rs = RollingStockBuilder::passenger_car()
+                .with_type_name("UIC-Z")
+                .with_road_number("61 83 19-90 105-3")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_passenger_car_type(PassengerCarType::OpenCoach)
+                .with_service_level(ServiceLevel::FirstClass)
+                .with_depot("Milano Centrale")
+                .with_livery("bandiera")
+                .build()
+                .unwrap();
+
+            assert_eq!(Some("UIC-Z"), rs.type_name());
+            assert_eq!(Some("61 83 19-90 105-3"), rs.road_number());
+            assert_eq!(Some("Milano Centrale"), rs.depot());
+            assert_eq!(Some("bandiera"), rs.livery());
+            assert_eq!(Some(ServiceLevel::FirstClass), rs.service_level());
+            assert_eq!(1, rs.n_of_elements());
+            assert_eq!(None, rs.class_name());
+        }
+
+        #[test]
+        fn it_should_return_the_freight_car_fields() {
+            let rs = RollingStockBuilder::freight_car()
+                .with_type_name("Gbhs")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::V)
+                .with_freight_car_type(FreightCarType::SwingRoofWagon)
+                .with_livery("marrone")
+                .build()
+                .unwrap();
+
+            assert_eq!(Some("Gbhs"), rs.type_name());
+            assert_eq!(None, rs.road_number());
+            assert_eq!(None, rs.depot());
+            assert_eq!(Some("marrone"), rs.livery());
+            assert_eq!(None, rs.service_level());
+        }
+    }
+
     mod service_level_tests {
         use super::*;
 
@@ -875,7 +1915,7 @@ mod tests {
 
         #[test]
         fn it_should_convert_string_slices_to_mixed_service_levels() {
-            let service_level = "1cl/2cl/3cl/2cl".parse::<ServiceLevel>();
+            let service_level = "3cl/1cl/2cl".parse::<ServiceLevel>();
             assert!(service_level.is_ok());
             assert_eq!(
                 service_level.unwrap(),
@@ -883,6 +1923,15 @@ mod tests {
             );
         }
 
+        #[test]
+        fn it_should_fail_to_convert_mixed_service_levels_with_duplicates() {
+            assert_eq!(
+                Err("Invalid mixed service level: duplicated values"),
+                "2cl/2cl".parse::<ServiceLevel>()
+            );
+            assert!("1cl/2cl/3cl/2cl".parse::<ServiceLevel>().is_err());
+        }
+
         #[test]
         fn it_should_fail_to_convert_invalid_values_to_service_levels() {
             let empty_string = "".parse::<ServiceLevel>();
@@ -905,6 +1954,22 @@ mod tests {
                 "1cl/2cl",
                 format!("{}", ServiceLevel::FirstAndSecondClass)
             );
+            assert_eq!(
+                "1cl/3cl",
+                format!("{}", ServiceLevel::FirstAndThirdClass)
+            );
+        }
+
+        #[test]
+        fn it_should_convert_string_slices_to_first_and_third_class() {
+            assert_eq!(
+                Ok(ServiceLevel::FirstAndThirdClass),
+                "1cl/3cl".parse::<ServiceLevel>()
+            );
+            assert_eq!(
+                Ok(ServiceLevel::FirstAndThirdClass),
+                "3cl/1cl".parse::<ServiceLevel>()
+            );
         }
     }
 }
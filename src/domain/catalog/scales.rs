@@ -21,7 +21,7 @@ pub enum TrackGauge {
     Narrow,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Scale {
     name: String,
     ratio: Decimal,
@@ -47,8 +47,11 @@ impl Scale {
 
     pub fn from_name(name: &str) -> Option<Self> {
         match name {
+            "O" => Some(Scale::O()),
             "H0" => Some(Scale::H0()),
+            "TT" => Some(Scale::TT()),
             "N" => Some(Scale::N()),
+            "Z" => Some(Scale::Z()),
             _ => None,
         }
     }
@@ -73,6 +76,19 @@ impl Scale {
         self.track_gauge
     }
 
+    /// Checks whether models in this scale are larger than the ones in the other
+    /// scale, that is this scale has a smaller ratio.
+    pub fn is_larger_than(&self, other: &Scale) -> bool {
+        self.ratio < other.ratio
+    }
+
+    #[allow(non_snake_case)]
+    pub fn O() -> Scale {
+        let ratio = Decimal::new(45, 0);
+        let gauge = Decimal::new(32, 0);
+        Scale::new("O", ratio, Some(gauge), TrackGauge::Standard)
+    }
+
     #[allow(non_snake_case)]
     pub fn H0() -> Scale {
         let ratio = Decimal::new(87, 0);
@@ -86,6 +102,20 @@ impl Scale {
         let gauge = Decimal::new(9, 0);
         Scale::new("N", ratio, Some(gauge), TrackGauge::Standard)
     }
+
+    #[allow(non_snake_case)]
+    pub fn TT() -> Scale {
+        let ratio = Decimal::new(120, 0);
+        let gauge = Decimal::new(12, 0);
+        Scale::new("TT", ratio, Some(gauge), TrackGauge::Standard)
+    }
+
+    #[allow(non_snake_case)]
+    pub fn Z() -> Scale {
+        let ratio = Decimal::new(220, 0);
+        let gauge = Decimal::new(65, 1);
+        Scale::new("Z", ratio, Some(gauge), TrackGauge::Standard)
+    }
 }
 
 impl fmt::Display for Scale {
@@ -102,6 +132,25 @@ impl cmp::PartialEq for Scale {
 
 impl cmp::Eq for Scale {}
 
+/// Scales are ordered by ratio, from the largest models to the smallest ones;
+/// scales with the same ratio are ordered by name.
+impl cmp::PartialOrd for Scale {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::Ord for Scale {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        let cmp1 = self.ratio.cmp(&other.ratio);
+        if cmp1 == cmp::Ordering::Equal {
+            return self.name.cmp(&other.name);
+        }
+
+        cmp1
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,5 +186,39 @@ mod tests {
             assert!(scale_h0 == scale_h0);
             assert!(scale_h0 != scale_n);
         }
+
+        #[test]
+        fn it_should_order_scales_by_ratio() {
+            let scale_o = Scale::O();
+            let scale_n = Scale::N();
+
+            assert!(scale_o < scale_n);
+            assert!(scale_o.is_larger_than(&scale_n));
+            assert!(!scale_n.is_larger_than(&scale_o));
+
+            let mut scales = vec![Scale::Z(), Scale::H0(), Scale::O()];
+            scales.sort();
+            assert_eq!(vec![Scale::O(), Scale::H0(), Scale::Z()], scales);
+        }
+
+        #[test]
+        fn it_should_order_the_larger_models_first() {
+            assert!(Scale::O() < Scale::H0());
+            assert!(Scale::H0() < Scale::N());
+            assert_eq!(
+                Some(cmp::Ordering::Less),
+                Scale::O().partial_cmp(&Scale::N())
+            );
+        }
+
+        #[test]
+        fn it_should_order_scales_with_the_same_ratio_by_name() {
+            let ratio = Decimal::new(87, 0);
+            let scale_h0 = Scale::H0();
+            let scale_h0m = Scale::new("H0m", ratio, None, TrackGauge::Narrow);
+
+            assert!(scale_h0 < scale_h0m);
+            assert!(!scale_h0.is_larger_than(&scale_h0m));
+        }
     }
 }
@@ -1,16 +1,26 @@
 use crate::domain::catalog::{
-    catalog_items::CatalogItem, rolling_stocks::RollingStock,
+    brands::Brand, catalog_items::ItemNumber, categories::Category,
+    railways::Railway, scales::Scale,
+};
+use crate::domain::catalog::{
+    catalog_items::{CatalogItem, PowerMethod},
+    rolling_stocks::RollingStock,
 };
-use crate::domain::catalog::{catalog_items::ItemNumber, categories::Category};
 
-use chrono::{Datelike, NaiveDate, NaiveDateTime, Utc};
-use prettytable::Table;
+use chrono::{Datelike, Months, NaiveDate, NaiveDateTime, Utc};
+use heck::ToShoutySnakeCase;
 use rust_decimal::prelude::*;
 use std::fmt::Write;
-use std::{cmp, collections::HashMap, fmt, ops, str};
+use std::{
+    cmp,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt, ops, str,
+};
 
-use crate::domain::catalog::rolling_stocks::DccInterface;
-use crate::domain::collecting::Price;
+use crate::domain::catalog::rolling_stocks::{
+    DccAddress, DccInterface, Decoder, Epoch, LengthOverBuffer,
+};
+use crate::domain::collecting::{Currency, Price, PriceError};
 
 /// A railway models collections, a collection stores a description and the items.
 /// Everything else the application is able to determine from the collection content
@@ -56,6 +66,11 @@ impl Collection {
         self.items.push(collection_item);
     }
 
+    /// Adds an item to this collection, keeping its notes and tags.
+    pub fn add_collection_item(&mut self, collection_item: CollectionItem) {
+        self.items.push(collection_item);
+    }
+
     /// Updates the modification fields (version and modified_date) for this collection.
     pub fn set_modified(
         &mut self,
@@ -66,14 +81,60 @@ impl Collection {
         self.modified_date = modified_date;
     }
 
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn modified_date(&self) -> &NaiveDateTime {
+        &self.modified_date
+    }
+
     pub fn len(&self) -> usize {
         self.items.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
     pub fn get_items(&self) -> &Vec<CollectionItem> {
         &self.items
     }
 
+    /// Returns the currency used for every purchase in this collection, the
+    /// statistics cannot sum prices in different currencies.
+    pub fn currency(&self) -> Result<Currency, PriceError> {
+        Price::common_currency(
+            self.items
+                .iter()
+                .filter_map(|it| it.purchased_info().price()),
+        )
+    }
+
+    /// Returns the total length (in millimeters) for all the rolling stocks in
+    /// this collection, the rolling stocks without a length are not counted.
+    pub fn total_length_mm(&self) -> u32 {
+        self.items.iter().map(CollectionItem::length_mm).sum()
+    }
+
+    /// Returns the tags used in this collection (lowercase), with the number
+    /// of items for each one.
+    pub fn tags(&self) -> BTreeMap<String, usize> {
+        let mut tags = BTreeMap::new();
+        for item in self.items.iter() {
+            let item_tags: BTreeSet<String> =
+                item.tags().iter().map(|t| t.to_lowercase()).collect();
+            for tag in item_tags {
+                *tags.entry(tag).or_insert(0) += 1;
+            }
+        }
+        tags
+    }
+
     pub fn get(&self, index: usize) -> Option<&CollectionItem> {
         self.items.get(index)
     }
@@ -82,9 +143,332 @@ impl Collection {
         self.items.sort();
     }
 
-    fn bump_version(&mut self) {
-        self.version += 1;
-        self.modified_date = Utc::now().naive_local();
+    /// Sorts the items by the given field, the items with the same value are
+    /// kept in brand and item number order (even when descending).
+    pub fn sort_items_by(&mut self, sort_by: SortItemsBy, descending: bool) {
+        self.items.sort_by(|a, b| {
+            let ordering = a.cmp_by(b, sort_by);
+            let ordering = if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            };
+            ordering.then_with(|| a.cmp(b))
+        });
+    }
+
+    /// Returns a new collection with only the items matching the predicate,
+    /// keeping the description, version and modified date.
+    pub fn filtered<P>(mut self, predicate: P) -> Collection
+    where
+        P: FnMut(&CollectionItem) -> bool,
+    {
+        self.items.retain(predicate);
+        self
+    }
+
+    /// Returns a new collection without the sold items.
+    pub fn without_sold(self) -> Collection {
+        self.filtered(|it| !it.is_sold())
+    }
+
+    /// Returns the items still owned, skipping the sold ones.
+    pub fn owned_items(&self) -> impl Iterator<Item = &CollectionItem> + '_ {
+        self.items.iter().filter(|it| !it.is_sold())
+    }
+
+    /// Returns the items with a price, from the most expensive to the cheapest
+    /// one; the items with the same price are sorted by purchase date.
+    pub fn by_price(&self) -> impl Iterator<Item = &CollectionItem> + '_ {
+        let mut items: Vec<&CollectionItem> = self
+            .items
+            .iter()
+            .filter(|it| it.purchased_info().price().is_some())
+            .collect();
+        items.sort_by(|a, b| {
+            let (a, b) = (a.purchased_info(), b.purchased_info());
+            b.price()
+                .cmp(&a.price())
+                .then_with(|| a.purchased_date().cmp(b.purchased_date()))
+        });
+        items.into_iter()
+    }
+
+    /// Merges the items from the other collection into this one, keeping the
+    /// highest version and the latest modified date.
+    ///
+    /// Every item is appended, so the stats for the merged collection are the
+    /// sum of the stats for the parts. Items are the same when they have the
+    /// same brand and item number: the items from the other collection already
+    /// in this one are reported as duplicates, while the same item number from
+    /// different brands is reported as a collision.
+    pub fn merge(&mut self, other: Collection) -> MergeReport {
+        self.merge_items(other, false)
+    }
+
+    /// Merges the items from the other collection into this one, like `merge`,
+    /// but skipping the duplicated items.
+    pub fn merge_rejecting_duplicates(
+        &mut self,
+        other: Collection,
+    ) -> MergeReport {
+        self.merge_items(other, true)
+    }
+
+    fn merge_items(
+        &mut self,
+        other: Collection,
+        reject_duplicates: bool,
+    ) -> MergeReport {
+        let mut report = MergeReport {
+            duplicates_skipped: reject_duplicates,
+            ..MergeReport::default()
+        };
+
+        self.version = self.version.max(other.version);
+        self.modified_date = self.modified_date.max(other.modified_date);
+
+        // only the items already in this collection are checked, the repeated
+        // purchases within the other collection are not duplicates
+        let len = self.items.len();
+        for item in other.items {
+            let ci = item.catalog_item();
+            let existing = &self.items[..len];
+            if existing.iter().any(|it| it.catalog_item() == ci) {
+                report.duplicates.push(DuplicatedItem {
+                    brand: ci.brand().clone(),
+                    item_number: ci.item_number().clone(),
+                });
+                if reject_duplicates {
+                    continue;
+                }
+            } else if let Some(existing) = existing
+                .iter()
+                .find(|it| it.catalog_item().item_number() == ci.item_number())
+            {
+                report.collisions.push(ItemNumberCollision {
+                    item_number: ci.item_number().clone(),
+                    brand: existing.catalog_item().brand().clone(),
+                    other_brand: ci.brand().clone(),
+                });
+            }
+
+            self.items.push(item);
+        }
+
+        report
+    }
+
+    /// Returns the catalog items included more than once in this collection,
+    /// together with the number of occurrences.
+    pub fn find_duplicates(&self) -> Vec<(&CatalogItem, usize)> {
+        let mut occurrences: Vec<(&CatalogItem, usize)> = Vec::new();
+        for item in self.items.iter() {
+            let ci = item.catalog_item();
+            match occurrences.iter_mut().find(|(it, _)| *it == ci) {
+                Some((_, count)) => *count += 1,
+                None => occurrences.push((ci, 1)),
+            }
+        }
+
+        occurrences.retain(|(_, count)| *count > 1);
+        occurrences
+    }
+
+    /// Returns the items matching the query (case insensitive) in the catalog
+    /// item description, brand or item number, or in any of their rolling
+    /// stocks class name, road number, type name or livery.
+    pub fn search(&self, query: &str) -> Vec<SearchMatch<'_>> {
+        let query = query.to_lowercase();
+        let contains = |value: &str| value.to_lowercase().contains(&query);
+
+        self.items
+            .iter()
+            .filter_map(|item| {
+                let ci = item.catalog_item();
+                let mut fields = vec![
+                    ("description", Some(ci.description())),
+                    ("brand", Some(ci.brand().name())),
+                    ("item number", Some(ci.item_number().value())),
+                ];
+                for rs in ci.rolling_stocks() {
+                    fields.push(("class name", rs.class_name()));
+                    fields.push(("road number", rs.road_number()));
+                    fields.push(("type name", rs.type_name()));
+                    fields.push(("livery", rs.livery()));
+                }
+
+                fields
+                    .into_iter()
+                    .find(|(_, value)| matches!(value, Some(v) if contains(v)))
+                    .map(|(field, _)| SearchMatch { item, field })
+            })
+            .collect()
+    }
+
+    /// Returns the items purchased more than once (same brand and item number),
+    /// grouped together.
+    pub fn duplicated_items(&self) -> Vec<Vec<&CollectionItem>> {
+        let mut groups: Vec<Vec<&CollectionItem>> = Vec::new();
+        for item in self.items.iter() {
+            match groups
+                .iter_mut()
+                .find(|g| g[0].catalog_item() == item.catalog_item())
+            {
+                Some(group) => group.push(item),
+                None => groups.push(vec![item]),
+            }
+        }
+
+        groups.retain(|g| g.len() > 1);
+        groups
+    }
+
+    /// Returns the locomotives with the same class name and road number found
+    /// more than once, even when they are part of different catalog items.
+    pub fn duplicated_locomotives(&self) -> Vec<LocomotiveDuplicate<'_>> {
+        let mut duplicates: Vec<LocomotiveDuplicate> = Vec::new();
+        for item in self.items.iter() {
+            for rs in item.catalog_item().rolling_stocks() {
+                if !rs.is_locomotive() {
+                    continue;
+                }
+
+                let class_name = rs.class_name().unwrap_or_default();
+                let road_number = rs.road_number().unwrap_or_default();
+                match duplicates.iter_mut().find(|it| {
+                    it.class_name == class_name && it.road_number == road_number
+                }) {
+                    Some(it) => it.items.push(item),
+                    None => duplicates.push(LocomotiveDuplicate {
+                        class_name: class_name.to_owned(),
+                        road_number: road_number.to_owned(),
+                        items: vec![item],
+                    }),
+                }
+            }
+        }
+
+        duplicates.retain(|it| it.items.len() > 1);
+        duplicates
+    }
+
+    /// Returns the first owned item for the catalog item with the same brand
+    /// (ignoring case) and item number, if any; the sold items are skipped.
+    pub fn find_catalog_item(
+        &self,
+        catalog_item: &CatalogItem,
+    ) -> Option<&CollectionItem> {
+        self.owned_items().find(|it| {
+            let ci = it.catalog_item();
+            ci.brand()
+                .name()
+                .eq_ignore_ascii_case(catalog_item.brand().name())
+                && ci.item_number() == catalog_item.item_number()
+        })
+    }
+
+    /// Checks whether the collection includes the catalog item, with the
+    /// same brand (ignoring case) and item number.
+    pub fn contains_catalog_item(&self, catalog_item: &CatalogItem) -> bool {
+        self.find_catalog_item(catalog_item).is_some()
+    }
+
+    /// Returns the first owned locomotive with the given class name and road
+    /// number (both ignoring case), together with its collection item.
+    pub fn find_rolling_stock_by_road_number(
+        &self,
+        class_name: &str,
+        road_number: &str,
+    ) -> Option<(&CollectionItem, &RollingStock)> {
+        let matches = |value: Option<&str>, expected: &str| {
+            value
+                .is_some_and(|v| v.trim().eq_ignore_ascii_case(expected.trim()))
+        };
+
+        self.owned_items().find_map(|item| {
+            item.catalog_item()
+                .rolling_stocks()
+                .iter()
+                .find(|rs| {
+                    rs.is_locomotive()
+                        && matches(rs.class_name(), class_name)
+                        && matches(rs.road_number(), road_number)
+                })
+                .map(|rs| (item, rs))
+        })
+    }
+
+    /// Returns the items still under warranty at the given date, sorted by
+    /// their warranty expiry date.
+    pub fn items_under_warranty(
+        &self,
+        as_of: &NaiveDate,
+    ) -> Vec<&CollectionItem> {
+        let mut items: Vec<&CollectionItem> = self
+            .items
+            .iter()
+            .filter(|it| it.purchased_info().is_under_warranty(as_of))
+            .collect();
+        items.sort_by_key(|it| it.purchased_info().warranty_expiry());
+        items
+    }
+
+    /// Finds the scales where a power method is used only by a minority of the items,
+    /// this usually signals a data entry mistake or an incompatible model.
+    ///
+    /// A power method is a minority when its share of the items in the scale is at
+    /// most `threshold` percent. On a tie the majority is the first power method
+    /// (DC, then AC), the results are sorted by scale and power method.
+    pub fn power_method_inconsistencies(
+        &self,
+        threshold: u8,
+    ) -> Vec<PowerMethodInconsistency<'_>> {
+        let mut by_scale: BTreeMap<&str, Vec<&CollectionItem>> =
+            BTreeMap::new();
+        for item in self.items.iter() {
+            let scale = item.catalog_item().scale().name();
+            by_scale.entry(scale).or_default().push(item);
+        }
+
+        let mut output = Vec::new();
+        for (scale, items) in by_scale {
+            let mut by_power_method: BTreeMap<
+                PowerMethod,
+                Vec<&CollectionItem>,
+            > = BTreeMap::new();
+            for item in items.iter() {
+                let pm = item.catalog_item().power_method();
+                by_power_method.entry(pm).or_default().push(item);
+            }
+
+            if by_power_method.len() < 2 {
+                continue;
+            }
+
+            let majority = by_power_method
+                .iter()
+                .max_by(|(pm1, it1), (pm2, it2)| {
+                    it1.len().cmp(&it2.len()).then_with(|| pm2.cmp(pm1))
+                })
+                .map(|(pm, _)| *pm)
+                .unwrap();
+
+            for (pm, minority_items) in by_power_method {
+                let share = minority_items.len() * 100 / items.len();
+                if pm != majority && share <= threshold as usize {
+                    output.push(PowerMethodInconsistency {
+                        scale: scale.to_owned(),
+                        majority,
+                        minority: pm,
+                        number_of_items: items.len(),
+                        items: minority_items,
+                    });
+                }
+            }
+        }
+
+        output
     }
 }
 
@@ -120,537 +504,4894 @@ impl ops::IndexMut<usize> for Collection {
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct PurchasedInfo {
-    shop: String,
-    purchased_date: NaiveDate,
-    price: Price,
+/// A scale where a minority power method was found.
+#[derive(Debug)]
+pub struct PowerMethodInconsistency<'a> {
+    scale: String,
+    majority: PowerMethod,
+    minority: PowerMethod,
+    number_of_items: usize,
+    items: Vec<&'a CollectionItem>,
 }
 
-impl PurchasedInfo {
-    pub fn new(shop: &str, purchased_date: NaiveDate, price: Price) -> Self {
-        PurchasedInfo {
-            shop: shop.to_owned(),
-            purchased_date,
-            price,
-        }
+impl<'a> PowerMethodInconsistency<'a> {
+    pub fn scale(&self) -> &str {
+        &self.scale
     }
 
-    pub fn price(&self) -> &Price {
-        &self.price
+    /// The power method used by most of the items in the scale.
+    pub fn majority(&self) -> PowerMethod {
+        self.majority
     }
 
-    pub fn shop(&self) -> &str {
-        &self.shop
+    /// The power method used only by a few items in the scale.
+    pub fn minority(&self) -> PowerMethod {
+        self.minority
     }
 
-    pub fn purchased_date(&self) -> &NaiveDate {
-        &self.purchased_date
+    /// The total number of items in the scale.
+    pub fn number_of_items(&self) -> usize {
+        self.number_of_items
     }
-}
 
-impl fmt::Display for PurchasedInfo {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "purchased at '{}' on {} for {}",
-            self.shop, self.purchased_date, self.price
-        )
+    /// The items using the minority power method.
+    pub fn items(&self) -> &Vec<&'a CollectionItem> {
+        &self.items
     }
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct CollectionItem {
-    catalog_item: CatalogItem,
-    purchased_at: PurchasedInfo,
+/// A collection item matching a search, with the first field that matched.
+#[derive(Debug)]
+pub struct SearchMatch<'a> {
+    item: &'a CollectionItem,
+    field: &'static str,
 }
 
-impl cmp::PartialOrd for CollectionItem {
-    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        Some(self.cmp(other))
+impl<'a> SearchMatch<'a> {
+    pub fn item(&self) -> &'a CollectionItem {
+        self.item
     }
-}
 
-impl cmp::Ord for CollectionItem {
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
-        self.catalog_item().cmp(other.catalog_item())
+    /// The name of the field matching the query
+    pub fn field(&self) -> &'static str {
+        self.field
     }
 }
 
-impl CollectionItem {
-    pub fn new(catalog_item: CatalogItem, purchased_at: PurchasedInfo) -> Self {
-        CollectionItem {
-            catalog_item,
-            purchased_at,
-        }
+/// A locomotive (class name and road number) found in more collection items.
+#[derive(Debug)]
+pub struct LocomotiveDuplicate<'a> {
+    class_name: String,
+    road_number: String,
+    items: Vec<&'a CollectionItem>,
+}
+
+impl<'a> LocomotiveDuplicate<'a> {
+    pub fn class_name(&self) -> &str {
+        &self.class_name
     }
 
-    pub fn catalog_item(&self) -> &CatalogItem {
-        &self.catalog_item
+    pub fn road_number(&self) -> &str {
+        &self.road_number
     }
 
-    pub fn purchased_info(&self) -> &PurchasedInfo {
-        &self.purchased_at
+    /// The collection items including this locomotive
+    pub fn items(&self) -> &[&'a CollectionItem] {
+        &self.items
     }
+}
 
-    pub fn rolling_stocks(&self) -> &Vec<RollingStock> {
-        self.catalog_item.rolling_stocks()
+/// The outcome for a collections merge.
+#[derive(Debug, Default)]
+pub struct MergeReport {
+    duplicates: Vec<DuplicatedItem>,
+    duplicates_skipped: bool,
+    collisions: Vec<ItemNumberCollision>,
+}
+
+impl MergeReport {
+    /// The items already in the collection (same brand and item number)
+    pub fn duplicates(&self) -> &Vec<DuplicatedItem> {
+        &self.duplicates
     }
 
-    pub fn price_info(&self) -> (&Price, i32) {
-        (
-            &self.purchased_at.price,
-            self.purchased_at.purchased_date.year(),
-        )
+    /// Checks whether the duplicated items were skipped, instead of merged
+    pub fn duplicates_skipped(&self) -> bool {
+        self.duplicates_skipped
     }
-}
 
-impl fmt::Display for CollectionItem {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}, {}", self.catalog_item, self.purchased_at)
+    /// The items merged sharing the item number with an item from a different brand
+    pub fn collisions(&self) -> &Vec<ItemNumberCollision> {
+        &self.collisions
     }
 }
 
+/// An item from the merged collection, with the same brand and item number of
+/// an item already in the collection.
 #[derive(Debug, PartialEq)]
-pub struct Depot {
-    locomotives: Vec<DepotCard>,
+pub struct DuplicatedItem {
+    brand: Brand,
+    item_number: ItemNumber,
 }
 
-impl Depot {
-    pub fn new() -> Self {
-        Depot {
-            locomotives: Vec::new(),
-        }
+impl DuplicatedItem {
+    pub fn brand(&self) -> &Brand {
+        &self.brand
     }
 
-    pub fn from_collection(collection: &Collection) -> Self {
-        let mut depot = Depot::new();
-
-        for item in collection.get_items() {
-            depot.add_catalog_item(item.catalog_item());
-        }
-
-        depot.locomotives.sort();
-        depot
+    pub fn item_number(&self) -> &ItemNumber {
+        &self.item_number
     }
+}
 
-    pub fn locomotives(&self) -> &Vec<DepotCard> {
-        &self.locomotives
-    }
+/// The same item number used by two different brands.
+#[derive(Debug, PartialEq)]
+pub struct ItemNumberCollision {
+    item_number: ItemNumber,
+    brand: Brand,
+    other_brand: Brand,
+}
 
-    pub fn len(&self) -> usize {
-        self.locomotives.len()
+impl ItemNumberCollision {
+    pub fn item_number(&self) -> &ItemNumber {
+        &self.item_number
     }
 
-    fn add_catalog_item(&mut self, ci: &CatalogItem) {
-        let locomotives =
-            ci.rolling_stocks().iter().filter(|it| it.is_locomotive());
-        for rs in locomotives {
-            let card = DepotCard::new(
-                rs.class_name().unwrap_or_default(),
-                rs.road_number().unwrap_or_default(),
-                rs.series(),
-                rs.livery(),
-                ci.brand().name(),
-                ci.item_number(),
-                rs.with_decoder(),
-                rs.dcc_interface(),
-            );
+    pub fn brand(&self) -> &Brand {
+        &self.brand
+    }
 
-            self.locomotives.push(card);
-        }
+    pub fn other_brand(&self) -> &Brand {
+        &self.other_brand
     }
 }
 
-/// A depot card contains the basic info for a model locomotive.
+/// The differences between two versions of a collection, the items are
+/// matched by brand (ignoring case) and item number. When the same catalog
+/// item was purchased more times, its items are matched in their order.
 #[derive(Debug)]
-pub struct DepotCard {
-    class_name: String,
-    road_number: String,
-    series: Option<String>,
-    livery: Option<String>,
-    brand: String,
-    item_number: ItemNumber,
-    with_decoder: bool,
-    dcc_interface: Option<DccInterface>,
+pub struct CollectionDiff<'a> {
+    added: Vec<&'a CollectionItem>,
+    removed: Vec<&'a CollectionItem>,
+    changed: Vec<ItemChanges<'a>>,
 }
 
-impl DepotCard {
-    #[allow(clippy::too_many_arguments)]
-    pub fn new(
-        class_name: &str,
-        road_number: &str,
-        series: Option<&str>,
-        livery: Option<&str>,
-        brand: &str,
-        item_number: &ItemNumber,
-        with_decoder: bool,
-        dcc_interface: Option<DccInterface>,
-    ) -> Self {
-        DepotCard {
-            class_name: class_name.to_owned(),
-            road_number: road_number.to_owned(),
-            series: series.map(|s| s.to_owned()),
-            livery: livery.map(|s| s.to_owned()),
-            brand: brand.to_owned(),
-            item_number: item_number.clone(),
-            with_decoder,
-            dcc_interface,
+impl<'a> CollectionDiff<'a> {
+    /// Compares the old version of a collection with the new one.
+    pub fn between(old: &'a Collection, new: &'a Collection) -> Self {
+        let mut matched = vec![false; new.items.len()];
+        let mut removed = Vec::new();
+        let mut changed = Vec::new();
+
+        for old_item in old.items.iter() {
+            let position = new.items.iter().enumerate().position(|(i, it)| {
+                !matched[i] && same_catalog_item(old_item, it)
+            });
+            match position {
+                Some(i) => {
+                    matched[i] = true;
+                    let new_item = &new.items[i];
+                    let changes = old_item.changes_to(new_item);
+                    if !changes.is_empty() {
+                        changed.push(ItemChanges {
+                            old: old_item,
+                            new: new_item,
+                            changes,
+                        });
+                    }
+                }
+                None => removed.push(old_item),
+            }
         }
-    }
 
-    pub fn class_name(&self) -> &str {
-        &self.class_name
+        let added = new
+            .items
+            .iter()
+            .zip(matched)
+            .filter(|(_, matched)| !matched)
+            .map(|(it, _)| it)
+            .collect();
+
+        CollectionDiff {
+            added,
+            removed,
+            changed,
+        }
     }
 
-    pub fn road_number(&self) -> &str {
-        &self.road_number
+    /// The items only in the new collection
+    pub fn added(&self) -> &[&'a CollectionItem] {
+        &self.added
     }
 
-    pub fn series(&self) -> Option<String> {
-        self.series.clone()
+    /// The items only in the old collection
+    pub fn removed(&self) -> &[&'a CollectionItem] {
+        &self.removed
     }
 
-    pub fn livery(&self) -> Option<String> {
-        self.livery.clone()
+    /// The items in both collections, with different values
+    pub fn changed(&self) -> &[ItemChanges<'a>] {
+        &self.changed
     }
 
-    pub fn brand(&self) -> &str {
-        &self.brand
+    /// Checks whether the two collections have the same items
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.changed.is_empty()
     }
+}
 
-    pub fn item_number(&self) -> &ItemNumber {
-        &self.item_number
+fn same_catalog_item(item: &CollectionItem, other: &CollectionItem) -> bool {
+    let ci = item.catalog_item();
+    let other = other.catalog_item();
+    ci.brand().name().eq_ignore_ascii_case(other.brand().name())
+        && ci.item_number() == other.item_number()
+}
+
+/// A collection item found in both versions of a collection, with its changes.
+#[derive(Debug)]
+pub struct ItemChanges<'a> {
+    old: &'a CollectionItem,
+    new: &'a CollectionItem,
+    changes: Vec<FieldChange>,
+}
+
+impl<'a> ItemChanges<'a> {
+    /// The item in the old collection
+    pub fn old_item(&self) -> &'a CollectionItem {
+        self.old
     }
 
-    pub fn with_decoder(&self) -> bool {
-        self.with_decoder
+    /// The item in the new collection
+    pub fn new_item(&self) -> &'a CollectionItem {
+        self.new
     }
 
-    pub fn dcc_interface(&self) -> Option<DccInterface> {
-        self.dcc_interface
+    pub fn changes(&self) -> &[FieldChange] {
+        &self.changes
     }
 }
 
-impl cmp::PartialEq for DepotCard {
-    fn eq(&self, other: &Self) -> bool {
-        self.road_number == other.road_number
-            && self.class_name == other.class_name
-    }
+/// A field with different values in two versions of the same collection item,
+/// the missing values are written as "-".
+#[derive(Debug, PartialEq)]
+pub struct FieldChange {
+    field: &'static str,
+    old_value: String,
+    new_value: String,
 }
 
-impl cmp::Eq for DepotCard {}
+impl FieldChange {
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
 
-impl cmp::PartialOrd for DepotCard {
-    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        Some(self.cmp(other))
+    pub fn old_value(&self) -> &str {
+        &self.old_value
+    }
+
+    pub fn new_value(&self) -> &str {
+        &self.new_value
     }
 }
 
-impl cmp::Ord for DepotCard {
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
-        let cmp1 = self.class_name.cmp(&other.class_name);
-        if cmp1 == cmp::Ordering::Equal {
-            return self.road_number.cmp(&other.road_number);
-        }
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.field, self.old_value, self.new_value
+        )
+    }
+}
 
-        cmp1
+/// The condition for a collection item, when it was purchased.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Condition {
+    New,
+    LikeNew,
+    Used,
+    Damaged,
+}
+
+impl str::FromStr for Condition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NEW" => Ok(Condition::New),
+            "LIKE_NEW" => Ok(Condition::LikeNew),
+            "USED" => Ok(Condition::Used),
+            "DAMAGED" => Ok(Condition::Damaged),
+            _ => Err(format!(
+                "Invalid condition '{}' (expected NEW, LIKE_NEW, USED or DAMAGED)",
+                s
+            )),
+        }
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct CollectionStats {
-    total_value: Decimal,
-    size: usize,
-    values_by_year: Vec<YearlyCollectionStats>,
-    totals: StatisticsTotals,
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = format!("{:?}", self);
+        write!(f, "{}", s.to_shouty_snake_case())
+    }
 }
 
-impl CollectionStats {
-    pub fn from_collection(collection: &Collection) -> Self {
-        let mut output: HashMap<Year, YearlyCollectionStats> = HashMap::new();
+#[derive(Debug, PartialEq, Eq)]
+pub struct PurchasedInfo {
+    shop: String,
+    purchased_date: NaiveDate,
+    price: Option<Price>,
+    condition: Option<Condition>,
+    receipt: Option<String>,
+    warranty_months: Option<u8>,
+}
 
-        for item in collection.get_items() {
-            let year = item.purchased_info().purchased_date().year();
+impl PurchasedInfo {
+    pub fn new(shop: &str, purchased_date: NaiveDate, price: Price) -> Self {
+        PurchasedInfo {
+            shop: shop.to_owned(),
+            purchased_date,
+            price: Some(price),
+            condition: None,
+            receipt: None,
+            warranty_months: None,
+        }
+    }
 
-            output
-                .entry(year)
-                .or_insert(YearlyCollectionStats::new_from_item(item))
-                .sum(item);
+    /// Creates a new purchase without a meaningful price, like for gifts
+    /// and swaps.
+    pub fn without_price(shop: &str, purchased_date: NaiveDate) -> Self {
+        PurchasedInfo {
+            price: None,
+            ..PurchasedInfo::new(shop, purchased_date, Price::default())
         }
+    }
 
-        let mut values: Vec<YearlyCollectionStats> =
-            output.values().cloned().collect();
-        values.sort();
+    /// Sets the condition for the purchased item.
+    pub fn with_condition(mut self, condition: Condition) -> Self {
+        self.condition = Some(condition);
+        self
+    }
 
-        let mut totals = StatisticsTotals::new();
-        for it in values.iter() {
-            totals.add(it);
-        }
+    /// Sets the receipt reference for this purchase.
+    pub fn with_receipt(mut self, receipt: &str) -> Self {
+        self.receipt = Some(receipt.to_owned());
+        self
+    }
 
-        let size = collection.len();
-        let total_value: Price = Price::euro(totals.total_value);
+    /// Sets the warranty length (in months) for this purchase.
+    pub fn with_warranty_months(mut self, warranty_months: u8) -> Self {
+        self.warranty_months = Some(warranty_months);
+        self
+    }
 
-        CollectionStats {
-            total_value: total_value.amount,
-            size,
-            values_by_year: values,
-            totals,
-        }
+    pub fn receipt(&self) -> Option<&str> {
+        self.receipt.as_deref()
     }
 
-    /// The total value of this collection
-    pub fn total_value(&self) -> Decimal {
-        self.total_value
+    pub fn warranty_months(&self) -> Option<u8> {
+        self.warranty_months
     }
 
-    /// The number of items included in this collection.
-    /// In case a catalog item contains more rolling stocks, all of them are accounted for.
-    pub fn size(&self) -> usize {
-        self.size
+    /// Returns the date when the warranty expires, if this purchase has one.
+    pub fn warranty_expiry(&self) -> Option<NaiveDate> {
+        self.warranty_months.and_then(|months| {
+            self.purchased_date
+                .checked_add_months(Months::new(months as u32))
+        })
     }
 
-    pub fn values_by_year(&self) -> &Vec<YearlyCollectionStats> {
-        &self.values_by_year
+    /// Checks whether this purchase is still under warranty at the given date.
+    pub fn is_under_warranty(&self, as_of: &NaiveDate) -> bool {
+        self.warranty_expiry()
+            .map(|expiry| &expiry > as_of)
+            .unwrap_or(false)
     }
 
-    pub fn number_of_locomotives(&self) -> u8 {
-        self.totals.number_of_locomotives
+    /// The purchase price, if any (gifts and swaps don't have one)
+    pub fn price(&self) -> Option<&Price> {
+        self.price.as_ref()
     }
 
-    pub fn locomotives_value(&self) -> Decimal {
-        self.totals.locomotives_value
+    pub fn condition(&self) -> Option<Condition> {
+        self.condition
     }
 
-    pub fn number_of_passenger_cars(&self) -> u8 {
-        self.totals.number_of_passenger_cars
+    pub fn shop(&self) -> &str {
+        &self.shop
     }
 
-    pub fn passenger_cars_value(&self) -> Decimal {
-        self.totals.passenger_cars_value
+    pub fn purchased_date(&self) -> &NaiveDate {
+        &self.purchased_date
     }
+}
 
-    pub fn number_of_freight_cars(&self) -> u8 {
-        self.totals.number_of_freight_cars
+impl fmt::Display for PurchasedInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.price {
+            Some(price) => write!(
+                f,
+                "purchased at '{}' on {} for {}",
+                self.shop, self.purchased_date, price
+            ),
+            None => write!(
+                f,
+                "purchased at '{}' on {} without price",
+                self.shop, self.purchased_date
+            ),
+        }
     }
+}
 
-    pub fn freight_cars_value(&self) -> Decimal {
-        self.totals.freight_cars_value
+/// The information about a collection item that was sold (or given away).
+#[derive(Debug, PartialEq, Eq)]
+pub struct SoldInfo {
+    sold_date: NaiveDate,
+    price: Option<Price>,
+    buyer: Option<String>,
+}
+
+impl SoldInfo {
+    pub fn new(sold_date: NaiveDate, price: Price) -> Self {
+        SoldInfo {
+            sold_date,
+            price: Some(price),
+            buyer: None,
+        }
     }
 
-    pub fn number_of_trains(&self) -> u8 {
-        self.totals.number_of_trains
+    /// Creates a new sold info for the items given away
+    pub fn without_price(sold_date: NaiveDate) -> Self {
+        SoldInfo {
+            sold_date,
+            price: None,
+            buyer: None,
+        }
     }
 
-    pub fn trains_value(&self) -> Decimal {
-        self.totals.trains_value
+    /// Sets who bought the item (like a shop or a person).
+    pub fn with_buyer(mut self, buyer: &str) -> Self {
+        self.buyer = Some(buyer.to_owned());
+        self
     }
 
-    pub fn number_of_rolling_stocks(&self) -> u16 {
-        self.totals.number_of_rolling_stocks
+    pub fn sold_date(&self) -> &NaiveDate {
+        &self.sold_date
+    }
+
+    pub fn price(&self) -> Option<&Price> {
+        self.price.as_ref()
+    }
+
+    pub fn buyer(&self) -> Option<&str> {
+        self.buyer.as_deref()
     }
 }
 
-pub type Year = i32;
+#[derive(Debug, PartialEq, Eq)]
+pub struct CollectionItem {
+    catalog_item: CatalogItem,
+    purchased_at: PurchasedInfo,
+    notes: Option<String>,
+    tags: Vec<String>,
+    storage: Option<String>,
+    replacement_value: Option<Price>,
+    sold: Option<SoldInfo>,
+}
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct YearlyCollectionStats {
-    year: Year,
-    locomotives: (u8, Decimal),
-    passenger_cars: (u8, Decimal),
-    freight_cars: (u8, Decimal),
-    trains: (u8, Decimal),
-    total: (u8, Decimal),
+impl cmp::PartialOrd for CollectionItem {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
 }
 
-impl YearlyCollectionStats {
-    pub fn new(year: Year) -> Self {
-        let zero: Decimal = Decimal::from(0);
+impl cmp::Ord for CollectionItem {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.catalog_item().cmp(other.catalog_item())
+    }
+}
 
-        YearlyCollectionStats {
-            year,
-            locomotives: (0u8, zero),
-            passenger_cars: (0u8, zero),
-            freight_cars: (0u8, zero),
-            trains: (0u8, zero),
-            total: (0u8, zero),
+/// The fields to sort the collection items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortItemsBy {
+    /// By brand and item number
+    #[default]
+    Brand,
+    /// By purchase price
+    Price,
+    /// By purchase date
+    Date,
+    /// By number of rolling stocks
+    Count,
+}
+
+impl CollectionItem {
+    /// Compares the items by the given field only, the items with the same
+    /// value are equal (but for the brand, that is the catalog item order).
+    pub fn cmp_by(&self, other: &Self, sort_by: SortItemsBy) -> cmp::Ordering {
+        match sort_by {
+            SortItemsBy::Brand => self.cmp(other),
+            SortItemsBy::Price => self
+                .purchased_info()
+                .price()
+                .cmp(&other.purchased_info().price()),
+            SortItemsBy::Date => self
+                .purchased_info()
+                .purchased_date()
+                .cmp(other.purchased_info().purchased_date()),
+            SortItemsBy::Count => self
+                .catalog_item()
+                .count()
+                .cmp(&other.catalog_item().count()),
         }
     }
 
-    pub fn new_from_item(item: &CollectionItem) -> YearlyCollectionStats {
-        let year = item.purchased_info().purchased_date().year();
-        let mut stat = Self::new(year);
-        stat.sum(item);
-        stat
+    pub fn new(catalog_item: CatalogItem, purchased_at: PurchasedInfo) -> Self {
+        CollectionItem {
+            catalog_item,
+            purchased_at,
+            notes: None,
+            tags: Vec::new(),
+            storage: None,
+            replacement_value: None,
+            sold: None,
+        }
     }
 
-    pub fn sum(&mut self, item: &CollectionItem) {
-        match item.catalog_item().category() {
-            Category::FreightCars => self.add_freight_cars(item),
-            Category::Locomotives => self.add_locomotives(item),
-            Category::PassengerCars => self.add_passenger_cars(item),
-            Category::Trains => self.add_trains(item),
-        }
-        self.update_total(item);
+    pub fn with_notes(mut self, notes: &str) -> Self {
+        self.notes = Some(notes.to_owned());
+        self
     }
 
-    pub fn year(&self) -> Year {
-        self.year
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
     }
 
-    pub fn number_of_locomotives(&self) -> u8 {
-        let (c, _) = self.locomotives;
-        c
+    /// Sets where this item is stored (like "Box 12").
+    pub fn with_storage(mut self, storage: &str) -> Self {
+        self.storage = Some(storage.to_owned());
+        self
     }
 
-    pub fn locomotives_value(&self) -> Decimal {
-        let (_, v) = self.locomotives;
-        v
+    /// Sets the estimated cost to replace this item (ie for the insurance).
+    pub fn with_replacement_value(mut self, replacement_value: Price) -> Self {
+        self.replacement_value = Some(replacement_value);
+        self
     }
 
-    pub fn number_of_passenger_cars(&self) -> u8 {
-        let (c, _) = self.passenger_cars;
-        c
+    /// Marks this item as sold.
+    pub fn with_sold_info(mut self, sold_info: SoldInfo) -> Self {
+        self.sold = Some(sold_info);
+        self
     }
 
-    pub fn passenger_cars_value(&self) -> Decimal {
-        let (_, v) = self.passenger_cars;
-        v
+    pub fn notes(&self) -> Option<&str> {
+        self.notes.as_deref()
     }
 
-    pub fn number_of_freight_cars(&self) -> u8 {
-        let (c, _) = self.freight_cars;
-        c
+    pub fn tags(&self) -> &Vec<String> {
+        &self.tags
     }
 
-    pub fn freight_cars_value(&self) -> Decimal {
-        let (_, v) = self.freight_cars;
-        v
+    pub fn storage(&self) -> Option<&str> {
+        self.storage.as_deref()
     }
 
-    pub fn number_of_trains(&self) -> u8 {
-        let (c, _) = self.trains;
-        c
+    /// Returns the difference between the reference price (ie the current
+    /// value) and the price paid, negative when the item lost value. It is not
+    /// available for the items without a price, or when the reference price is
+    /// in another currency.
+    pub fn value_delta(&self, reference: &Price) -> Option<Decimal> {
+        let paid = self.purchased_at.price()?;
+        if paid.currency() != reference.currency() {
+            return None;
+        }
+        Some(reference.amount() - paid.amount())
     }
 
-    pub fn trains_value(&self) -> Decimal {
-        let (_, v) = self.trains;
-        v
+    /// Returns the value delta as a percentage of the price paid, rounded to
+    /// two decimals; it is not available when the value delta is not.
+    pub fn value_change(&self, reference: &Price) -> Option<Decimal> {
+        let delta = self.value_delta(reference)?;
+        let paid = self.purchased_at.price().map(Price::amount)?;
+        if paid.is_zero() {
+            return None;
+        }
+        Some((delta * Decimal::ONE_HUNDRED / paid).round_dp(2))
     }
 
-    pub fn number_of_rolling_stocks(&self) -> u8 {
-        let (c, _) = self.total;
-        c
+    /// The estimated cost to replace this item, when it is known
+    pub fn replacement_value(&self) -> Option<&Price> {
+        self.replacement_value.as_ref()
     }
 
-    pub fn total_value(&self) -> Decimal {
-        let (_, v) = self.total;
-        v
+    pub fn sold_info(&self) -> Option<&SoldInfo> {
+        self.sold.as_ref()
     }
 
-    fn add_locomotives(&mut self, item: &CollectionItem) {
-        let (count, total_value) = &self.locomotives;
-        self.locomotives = (
-            count + item.catalog_item().count(),
-            total_value + item.purchased_at.price().amount,
-        );
+    /// Checks whether this item was sold, the sold items are no longer part
+    /// of the collection but they are kept for its history.
+    pub fn is_sold(&self) -> bool {
+        self.sold.is_some()
     }
 
-    fn add_passenger_cars(&mut self, item: &CollectionItem) {
-        let (count, total_value) = &self.passenger_cars;
-        self.passenger_cars = (
-            count + item.catalog_item().count(),
-            total_value + item.purchased_at.price().amount,
-        );
+    /// Returns the realized gain (or loss, when negative) for a sold item,
+    /// that is the sold price minus the purchase price. The missing prices
+    /// are considered zero.
+    pub fn realized_gain(&self) -> Option<Decimal> {
+        let sold = self.sold.as_ref()?;
+        let amount = |price: Option<&Price>| {
+            price.map(Price::amount).unwrap_or_default()
+        };
+        Some(amount(sold.price()) - amount(self.purchased_at.price()))
     }
 
-    fn add_freight_cars(&mut self, item: &CollectionItem) {
-        let (count, total_value) = &self.freight_cars;
-        self.freight_cars = (
-            count + item.catalog_item().count(),
-            total_value + item.purchased_at.price().amount,
-        );
+    /// Returns the total length (in millimeters) for the rolling stocks in
+    /// this item, the rolling stocks without a length are not counted.
+    pub fn length_mm(&self) -> u32 {
+        let ci = &self.catalog_item;
+        let length: u32 = ci
+            .rolling_stocks()
+            .iter()
+            .filter_map(|rs| rs.length_over_buffer())
+            .map(|length| length.value())
+            .sum();
+        length * u32::from(ci.count())
     }
 
-    fn add_trains(&mut self, item: &CollectionItem) {
-        let (count, total_value) = &self.trains;
-        self.trains = (
-            count + item.catalog_item().count(),
-            total_value + item.purchased_at.price().amount,
-        );
+    /// Checks whether this item has the given tag (ignoring case).
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
     }
 
-    fn update_total(&mut self, item: &CollectionItem) {
-        let (count, total_value) = &self.total;
-        self.total = (
-            count + item.catalog_item().count(),
-            total_value + item.purchased_at.price().amount,
-        );
+    /// Returns the fields with a different value in the other item. The
+    /// catalog items equality only looks at the brand and item number, this
+    /// comparison goes through the values the collector can change instead.
+    pub fn changes_to(&self, other: &CollectionItem) -> Vec<FieldChange> {
+        fn optional<T: fmt::Display>(value: Option<T>) -> String {
+            value.map_or_else(|| String::from("-"), |it| it.to_string())
+        }
+
+        fn each<T: fmt::Display>(values: &[Option<T>]) -> String {
+            values
+                .iter()
+                .map(|it| optional(it.as_ref()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        }
+
+        fn change<T: PartialEq>(
+            field: &'static str,
+            old: T,
+            new: T,
+            to_string: impl Fn(&T) -> String,
+        ) -> Option<FieldChange> {
+            if old == new {
+                None
+            } else {
+                Some(FieldChange {
+                    field,
+                    old_value: to_string(&old),
+                    new_value: to_string(&new),
+                })
+            }
+        }
+
+        fn rolling_stocks<'b, T>(
+            item: &'b CollectionItem,
+            value: impl Fn(&'b RollingStock) -> Option<T>,
+        ) -> Vec<Option<T>> {
+            item.rolling_stocks().iter().map(value).collect()
+        }
+
+        let (old_sold, new_sold) = (self.sold.as_ref(), other.sold.as_ref());
+        let (old_ci, new_ci) = (&self.catalog_item, &other.catalog_item);
+        let (old_pi, new_pi) = (&self.purchased_at, &other.purchased_at);
+
+        vec![
+            change(
+                "description",
+                old_ci.description(),
+                new_ci.description(),
+                |it| it.to_string(),
+            ),
+            change("count", old_ci.count(), new_ci.count(), |it| {
+                it.to_string()
+            }),
+            change(
+                "scale",
+                old_ci.scale().to_string(),
+                new_ci.scale().to_string(),
+                |it| it.clone(),
+            ),
+            change(
+                "power method",
+                old_ci.power_method(),
+                new_ci.power_method(),
+                |it| it.to_string(),
+            ),
+            change(
+                "rolling stocks",
+                self.rolling_stocks().len(),
+                other.rolling_stocks().len(),
+                |it| it.to_string(),
+            ),
+            change(
+                "road numbers",
+                rolling_stocks(self, |rs| rs.road_number()),
+                rolling_stocks(other, |rs| rs.road_number()),
+                |it| each(it),
+            ),
+            change(
+                "dcc interfaces",
+                rolling_stocks(self, |rs| rs.dcc_interface()),
+                rolling_stocks(other, |rs| rs.dcc_interface()),
+                |it| each(it),
+            ),
+            change(
+                "dcc addresses",
+                rolling_stocks(self, |rs| rs.dcc_address()),
+                rolling_stocks(other, |rs| rs.dcc_address()),
+                |it| each(it),
+            ),
+            change(
+                "decoders",
+                rolling_stocks(self, |rs| rs.decoder()),
+                rolling_stocks(other, |rs| rs.decoder()),
+                |it| each(it),
+            ),
+            change(
+                "lengths",
+                rolling_stocks(self, |rs| rs.length_over_buffer()),
+                rolling_stocks(other, |rs| rs.length_over_buffer()),
+                |it| each(it),
+            ),
+            change("price", old_pi.price(), new_pi.price(), |it| optional(*it)),
+            change(
+                "purchase date",
+                old_pi.purchased_date,
+                new_pi.purchased_date,
+                |it| it.to_string(),
+            ),
+            change("shop", old_pi.shop(), new_pi.shop(), |it| it.to_string()),
+            change("condition", old_pi.condition(), new_pi.condition(), |it| {
+                optional(*it)
+            }),
+            change("storage", self.storage(), other.storage(), |it| {
+                optional(*it)
+            }),
+            change("notes", self.notes(), other.notes(), |it| optional(*it)),
+            change("tags", self.tags(), other.tags(), |it| {
+                if it.is_empty() {
+                    String::from("-")
+                } else {
+                    it.join(", ")
+                }
+            }),
+            change(
+                "replacement value",
+                self.replacement_value(),
+                other.replacement_value(),
+                |it| optional(*it),
+            ),
+            change(
+                "sold date",
+                old_sold.map(|s| s.sold_date),
+                new_sold.map(|s| s.sold_date),
+                |it| optional(*it),
+            ),
+            change(
+                "sold price",
+                old_sold.and_then(|s| s.price()),
+                new_sold.and_then(|s| s.price()),
+                |it| optional(*it),
+            ),
+            change(
+                "buyer",
+                old_sold.and_then(|s| s.buyer()),
+                new_sold.and_then(|s| s.buyer()),
+                |it| optional(*it),
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
     }
-}
 
-impl cmp::PartialOrd for YearlyCollectionStats {
-    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        Some(self.cmp(other))
+    pub fn catalog_item(&self) -> &CatalogItem {
+        &self.catalog_item
+    }
+
+    pub fn purchased_info(&self) -> &PurchasedInfo {
+        &self.purchased_at
+    }
+
+    pub fn rolling_stocks(&self) -> &Vec<RollingStock> {
+        self.catalog_item.rolling_stocks()
+    }
+
+    pub fn price_info(&self) -> (Option<&Price>, i32) {
+        (
+            self.purchased_at.price(),
+            self.purchased_at.purchased_date.year(),
+        )
     }
 }
 
-impl cmp::Ord for YearlyCollectionStats {
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
-        self.year.cmp(&other.year)
+impl fmt::Display for CollectionItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}, {}", self.catalog_item, self.purchased_at)
     }
 }
 
-#[derive(Debug, PartialEq)]
-pub struct StatisticsTotals {
-    number_of_locomotives: u8,
-    locomotives_value: Decimal,
-    number_of_trains: u8,
-    trains_value: Decimal,
-    number_of_passenger_cars: u8,
-    passenger_cars_value: Decimal,
-    number_of_freight_cars: u8,
-    freight_cars_value: Decimal,
-    number_of_rolling_stocks: u16,
-    total_value: Decimal,
+#[derive(Debug, PartialEq, Default)]
+pub struct Depot {
+    locomotives: Vec<DepotCard>,
 }
 
-impl StatisticsTotals {
+impl Depot {
     pub fn new() -> Self {
-        StatisticsTotals {
-            number_of_locomotives: 0u8,
-            locomotives_value: Decimal::from(0),
-            number_of_trains: 0u8,
-            trains_value: Decimal::from(0),
-            number_of_passenger_cars: 0u8,
-            passenger_cars_value: Decimal::from(0),
-            number_of_freight_cars: 0u8,
-            freight_cars_value: Decimal::from(0),
-            number_of_rolling_stocks: 0u16,
-            total_value: Decimal::from(0),
+        Depot {
+            locomotives: Vec::new(),
         }
     }
 
-    fn add(&mut self, yearly: &YearlyCollectionStats) {
-        self.number_of_locomotives += yearly.number_of_locomotives();
-        self.locomotives_value += yearly.locomotives_value();
-        self.number_of_trains += yearly.number_of_trains();
-        self.trains_value += yearly.trains_value();
-        self.number_of_passenger_cars += yearly.number_of_passenger_cars();
-        self.passenger_cars_value += yearly.passenger_cars_value();
-        self.number_of_freight_cars += yearly.number_of_freight_cars();
-        self.freight_cars_value += yearly.freight_cars_value();
-        self.number_of_rolling_stocks +=
-            yearly.number_of_rolling_stocks() as u16;
-        self.total_value += yearly.total_value();
+    /// Creates a new depot with the locomotives in the collection.
+    pub fn from_collection(collection: &Collection) -> Self {
+        Depot::build(collection, false)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Creates a new depot with the locomotives and the trains (railcars and
+    /// multiple units included) in the collection; only the trains with a
+    /// decoder are included, the unpowered elements of a train set are not.
+    pub fn from_collection_with_trains(collection: &Collection) -> Self {
+        Depot::build(collection, true)
+    }
 
-    mod collection_tests {
-        use super::*;
+    fn build(collection: &Collection, include_trains: bool) -> Self {
+        let mut depot = Depot::new();
+
+        for item in collection.get_items() {
+            depot.add_catalog_item(item.catalog_item(), include_trains);
+        }
+
+        depot.locomotives.sort();
+        depot
+    }
+
+    pub fn locomotives(&self) -> &Vec<DepotCard> {
+        &self.locomotives
+    }
+
+    pub fn len(&self) -> usize {
+        self.locomotives.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locomotives.is_empty()
+    }
+
+    /// Keeps only the depot cards matching the filter.
+    pub fn filter(&mut self, filter: &DepotFilter) {
+        self.locomotives.retain(|card| filter.matches(card));
+    }
+
+    /// Removes the duplicated cards (same class name and road number), keeping the
+    /// first occurrence. Returns the number of cards removed.
+    pub fn dedup(&mut self) -> usize {
+        let len = self.locomotives.len();
+        self.locomotives.sort();
+        self.locomotives.dedup();
+        len - self.locomotives.len()
+    }
+
+    /// Returns the dcc addresses shared by locomotives with different road numbers,
+    /// together with the conflicting depot cards.
+    pub fn address_conflicts(&self) -> Vec<(DccAddress, Vec<&DepotCard>)> {
+        let mut by_address: BTreeMap<DccAddress, Vec<&DepotCard>> =
+            BTreeMap::new();
+        for card in self.locomotives.iter() {
+            if let Some(address) = card.dcc_address() {
+                by_address.entry(address).or_default().push(card);
+            }
+        }
+
+        by_address
+            .into_iter()
+            .filter(|(_, cards)| {
+                cards
+                    .iter()
+                    .any(|c| c.road_number() != cards[0].road_number())
+            })
+            .collect()
+    }
+
+    /// Returns the number of locomotives still to be equipped with a decoder,
+    /// grouped by their dcc interface.
+    pub fn decoder_summary(&self) -> DecoderSummary {
+        let mut summary = DecoderSummary::default();
+        for card in self.locomotives.iter().filter(|it| !it.with_decoder()) {
+            match card.dcc_interface() {
+                Some(dcc_interface) => {
+                    *summary.by_interface.entry(dcc_interface).or_default() += 1
+                }
+                None => summary.unknown_socket += 1,
+            }
+        }
+        summary
+    }
+
+    /// Returns the locomotives still to be equipped with a decoder, grouped
+    /// by their dcc interface, to find out which decoders to buy.
+    pub fn decoder_shopping_list(&self) -> DecoderShoppingList<'_> {
+        let mut list = DecoderShoppingList::default();
+        for card in self.locomotives.iter().filter(|it| !it.with_decoder()) {
+            match card.dcc_interface() {
+                Some(dcc_interface) => list
+                    .by_interface
+                    .entry(dcc_interface)
+                    .or_default()
+                    .push(card),
+                None => list.unknown_socket.push(card),
+            }
+        }
+        list
+    }
+
+    fn add_catalog_item(&mut self, ci: &CatalogItem, include_trains: bool) {
+        let locomotives = ci.rolling_stocks().iter().filter(|it| {
+            it.is_locomotive()
+                || (include_trains && it.is_train() && it.with_decoder())
+        });
+        for rs in locomotives {
+            let class_name = rs.class_name().or_else(|| rs.type_name());
+            let card = DepotCard::new(
+                class_name.unwrap_or_default(),
+                rs.road_number().unwrap_or_default(),
+                rs.series(),
+                rs.livery(),
+                rs.railway(),
+                rs.epoch(),
+                ci.brand().name(),
+                ci.item_number(),
+                rs.with_decoder(),
+                rs.dcc_interface(),
+                rs.dcc_address(),
+                rs.decoder(),
+            );
+
+            self.locomotives.push(card);
+        }
+    }
+}
+
+/// The roster lists every rolling stock in the collection, whatever its category.
+#[derive(Debug, Default)]
+pub struct Roster<'a> {
+    rolling_stocks: Vec<&'a RollingStock>,
+}
+
+impl<'a> Roster<'a> {
+    /// Creates a new roster with all the rolling stocks in the collection, sorted
+    /// by railway and then by class (or type) name.
+    pub fn from_collection(collection: &'a Collection) -> Self {
+        let mut rolling_stocks: Vec<&RollingStock> = collection
+            .get_items()
+            .iter()
+            .flat_map(|it| it.catalog_item().rolling_stocks().iter())
+            .collect();
+        rolling_stocks.sort_by(|a, b| {
+            a.railway()
+                .name()
+                .cmp(b.railway().name())
+                .then_with(|| Roster::name(a).cmp(Roster::name(b)))
+        });
+
+        Roster { rolling_stocks }
+    }
+
+    /// Keeps only the rolling stocks with one of these categories, an empty
+    /// list keeps every rolling stock.
+    pub fn filter_by_category(&mut self, categories: &[Category]) {
+        if !categories.is_empty() {
+            self.rolling_stocks
+                .retain(|rs| categories.contains(&rs.category()));
+        }
+    }
+
+    /// Returns the class name for locomotives, the type name otherwise
+    pub fn name(rolling_stock: &RollingStock) -> &str {
+        rolling_stock
+            .class_name()
+            .or_else(|| rolling_stock.type_name())
+            .unwrap_or_default()
+    }
+
+    pub fn rolling_stocks(&self) -> &[&'a RollingStock] {
+        &self.rolling_stocks
+    }
+
+    pub fn len(&self) -> usize {
+        self.rolling_stocks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rolling_stocks.is_empty()
+    }
+}
+
+/// The sold items, with the realized gain (or loss) for each of them.
+#[derive(Debug)]
+pub struct SalesReport<'a> {
+    currency: Currency,
+    sales: Vec<&'a CollectionItem>,
+    by_year: Vec<YearlySales>,
+}
+
+impl<'a> SalesReport<'a> {
+    /// Creates the report for the sold items, sorted by sold date.
+    pub fn from_collection(
+        collection: &'a Collection,
+    ) -> Result<Self, PriceError> {
+        let mut sales: Vec<&CollectionItem> = collection
+            .get_items()
+            .iter()
+            .filter(|it| it.is_sold())
+            .collect();
+        sales.sort_by_key(|it| it.sold_info().map(|sold| sold.sold_date));
+
+        let currency = Price::common_currency(sales.iter().flat_map(|it| {
+            it.purchased_info()
+                .price()
+                .into_iter()
+                .chain(it.sold_info().and_then(SoldInfo::price))
+        }))?;
+
+        let mut by_year: BTreeMap<Year, YearlySales> = BTreeMap::new();
+        for item in sales.iter() {
+            let sold = item.sold_info().expect("the item must be sold");
+            let year = sold.sold_date.year();
+            let entry = by_year.entry(year).or_insert_with(|| YearlySales {
+                year,
+                number_of_items: 0,
+                purchase_total: Decimal::ZERO,
+                sold_total: Decimal::ZERO,
+            });
+            entry.number_of_items += 1;
+            entry.purchase_total += item
+                .purchased_info()
+                .price()
+                .map(Price::amount)
+                .unwrap_or_default();
+            entry.sold_total +=
+                sold.price().map(Price::amount).unwrap_or_default();
+        }
+
+        Ok(SalesReport {
+            currency,
+            sales,
+            by_year: by_year.into_values().collect(),
+        })
+    }
+
+    /// The currency for the prices
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn sales(&self) -> &[&'a CollectionItem] {
+        &self.sales
+    }
+
+    /// The sales totals for each year (when the items were sold)
+    pub fn by_year(&self) -> &[YearlySales] {
+        &self.by_year
+    }
+
+    /// The total realized gain (or loss, when negative)
+    pub fn realized_gain(&self) -> Decimal {
+        self.by_year.iter().map(YearlySales::realized_gain).sum()
+    }
+
+    pub fn len(&self) -> usize {
+        self.sales.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sales.is_empty()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct YearlySales {
+    year: Year,
+    number_of_items: usize,
+    purchase_total: Decimal,
+    sold_total: Decimal,
+}
+
+impl YearlySales {
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    pub fn number_of_items(&self) -> usize {
+        self.number_of_items
+    }
+
+    /// The total purchase price for the items sold this year
+    pub fn purchase_total(&self) -> Decimal {
+        self.purchase_total
+    }
+
+    /// The total sold price for the items sold this year
+    pub fn sold_total(&self) -> Decimal {
+        self.sold_total
+    }
+
+    /// The realized gain (or loss, when negative) for this year
+    pub fn realized_gain(&self) -> Decimal {
+        self.sold_total - self.purchase_total
+    }
+}
+
+/// The most expensive purchases in a collection, with the share of the total
+/// collection value for each of them.
+#[derive(Debug)]
+pub struct TopPurchases<'a> {
+    currency: Currency,
+    total_value: Decimal,
+    purchases: Vec<TopPurchase<'a>>,
+}
+
+impl<'a> TopPurchases<'a> {
+    /// Selects the `n` most expensive items with one of these categories (an
+    /// empty list selects every category). The percentages are always
+    /// calculated on the whole collection value.
+    pub fn from_collection(
+        collection: &'a Collection,
+        n: usize,
+        categories: &[Category],
+    ) -> Result<Self, PriceError> {
+        let currency = collection.currency()?;
+        let total_value: Decimal = collection
+            .get_items()
+            .iter()
+            .filter_map(|it| it.purchased_info().price())
+            .map(Price::amount)
+            .sum();
+
+        let purchases = collection
+            .by_price()
+            .filter(|it| {
+                categories.is_empty()
+                    || categories.contains(&it.catalog_item().category())
+            })
+            .take(n)
+            .map(|item| {
+                let amount = item
+                    .purchased_info()
+                    .price()
+                    .map(Price::amount)
+                    .unwrap_or_default();
+                let percentage = if total_value.is_zero() {
+                    Decimal::ZERO
+                } else {
+                    (amount * Decimal::from(100) / total_value).round_dp(2)
+                };
+                TopPurchase { item, percentage }
+            })
+            .collect();
+
+        Ok(TopPurchases {
+            currency,
+            total_value,
+            purchases,
+        })
+    }
+
+    /// The currency for the collection values
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// The total value for the whole collection
+    pub fn total_value(&self) -> Decimal {
+        self.total_value
+    }
+
+    pub fn purchases(&self) -> &[TopPurchase<'a>] {
+        &self.purchases
+    }
+
+    pub fn len(&self) -> usize {
+        self.purchases.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.purchases.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub struct TopPurchase<'a> {
+    item: &'a CollectionItem,
+    percentage: Decimal,
+}
+
+impl<'a> TopPurchase<'a> {
+    pub fn item(&self) -> &'a CollectionItem {
+        self.item
+    }
+
+    /// The percentage of the total collection value for this purchase,
+    /// rounded to two decimals
+    pub fn percentage(&self) -> Decimal {
+        self.percentage
+    }
+}
+
+/// The criteria to select the depot cards, every criteria must match.
+#[derive(Debug, Default)]
+pub struct DepotFilter {
+    railway: Option<String>,
+    epoch: Option<Epoch>,
+    with_decoder: Option<bool>,
+    dcc_interface: Option<DccInterface>,
+}
+
+impl DepotFilter {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Selects the cards for the railway with this name (ignoring case).
+    pub fn with_railway(mut self, railway: &str) -> Self {
+        self.railway = Some(railway.to_owned());
+        self
+    }
+
+    /// Selects the cards whose epoch includes this epoch.
+    pub fn with_epoch(mut self, epoch: Epoch) -> Self {
+        self.epoch = Some(epoch);
+        self
+    }
+
+    /// Selects the cards with (or without) a decoder installed.
+    pub fn with_decoder(mut self, with_decoder: bool) -> Self {
+        self.with_decoder = Some(with_decoder);
+        self
+    }
+
+    /// Selects the cards with this dcc interface.
+    pub fn with_dcc_interface(mut self, dcc_interface: DccInterface) -> Self {
+        self.dcc_interface = Some(dcc_interface);
+        self
+    }
+
+    pub fn matches(&self, card: &DepotCard) -> bool {
+        if let Some(railway) = &self.railway {
+            if !card.railway().includes(railway) {
+                return false;
+            }
+        }
+
+        if let Some(epoch) = &self.epoch {
+            if !card.epoch().includes(epoch) {
+                return false;
+            }
+        }
+
+        if let Some(with_decoder) = self.with_decoder {
+            if card.with_decoder() != with_decoder {
+                return false;
+            }
+        }
+
+        if let Some(dcc_interface) = self.dcc_interface {
+            if card.dcc_interface() != Some(dcc_interface) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// The number of locomotives without a decoder, by dcc interface.
+#[derive(Debug, PartialEq, Default)]
+pub struct DecoderSummary {
+    by_interface: BTreeMap<DccInterface, usize>,
+    unknown_socket: usize,
+}
+
+impl DecoderSummary {
+    /// Returns the locomotives to equip for each dcc interface
+    pub fn by_interface(&self) -> &BTreeMap<DccInterface, usize> {
+        &self.by_interface
+    }
+
+    /// Returns the locomotives to equip without a declared dcc interface
+    pub fn unknown_socket(&self) -> usize {
+        self.unknown_socket
+    }
+
+    /// Returns the total number of locomotives to equip
+    pub fn total(&self) -> usize {
+        self.by_interface.values().sum::<usize>() + self.unknown_socket
+    }
+}
+
+/// The locomotives without a decoder, by dcc interface.
+#[derive(Debug, Default)]
+pub struct DecoderShoppingList<'a> {
+    by_interface: BTreeMap<DccInterface, Vec<&'a DepotCard>>,
+    unknown_socket: Vec<&'a DepotCard>,
+}
+
+impl<'a> DecoderShoppingList<'a> {
+    /// Returns the locomotives to equip for each dcc interface
+    pub fn by_interface(&self) -> &BTreeMap<DccInterface, Vec<&'a DepotCard>> {
+        &self.by_interface
+    }
+
+    /// Returns the locomotives to equip without a declared dcc interface
+    pub fn unknown_socket(&self) -> &[&'a DepotCard] {
+        &self.unknown_socket
+    }
+
+    /// Returns the total number of decoders to buy
+    pub fn total(&self) -> usize {
+        self.by_interface.values().map(|it| it.len()).sum::<usize>()
+            + self.unknown_socket.len()
+    }
+}
+
+/// A depot card contains the basic info for a model locomotive.
+#[derive(Debug)]
+pub struct DepotCard {
+    class_name: String,
+    road_number: String,
+    series: Option<String>,
+    livery: Option<String>,
+    railway: Railway,
+    epoch: Epoch,
+    brand: String,
+    item_number: ItemNumber,
+    with_decoder: bool,
+    dcc_interface: Option<DccInterface>,
+    dcc_address: Option<DccAddress>,
+    decoder: Option<Decoder>,
+}
+
+impl DepotCard {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        class_name: &str,
+        road_number: &str,
+        series: Option<&str>,
+        livery: Option<&str>,
+        railway: &Railway,
+        epoch: &Epoch,
+        brand: &str,
+        item_number: &ItemNumber,
+        with_decoder: bool,
+        dcc_interface: Option<DccInterface>,
+        dcc_address: Option<DccAddress>,
+        decoder: Option<&Decoder>,
+    ) -> Self {
+        DepotCard {
+            class_name: class_name.to_owned(),
+            road_number: road_number.to_owned(),
+            series: series.map(|s| s.to_owned()),
+            livery: livery.map(|s| s.to_owned()),
+            railway: railway.clone(),
+            epoch: epoch.clone(),
+            brand: brand.to_owned(),
+            item_number: item_number.clone(),
+            with_decoder,
+            dcc_interface,
+            dcc_address,
+            decoder: decoder.cloned(),
+        }
+    }
+
+    pub fn class_name(&self) -> &str {
+        &self.class_name
+    }
+
+    pub fn road_number(&self) -> &str {
+        &self.road_number
+    }
+
+    pub fn series(&self) -> Option<String> {
+        self.series.clone()
+    }
+
+    pub fn livery(&self) -> Option<String> {
+        self.livery.clone()
+    }
+
+    pub fn railway(&self) -> &Railway {
+        &self.railway
+    }
+
+    pub fn epoch(&self) -> &Epoch {
+        &self.epoch
+    }
+
+    pub fn brand(&self) -> &str {
+        &self.brand
+    }
+
+    pub fn item_number(&self) -> &ItemNumber {
+        &self.item_number
+    }
+
+    pub fn with_decoder(&self) -> bool {
+        self.with_decoder
+    }
+
+    pub fn dcc_interface(&self) -> Option<DccInterface> {
+        self.dcc_interface
+    }
+
+    pub fn dcc_address(&self) -> Option<DccAddress> {
+        self.dcc_address
+    }
+
+    pub fn decoder(&self) -> Option<&Decoder> {
+        self.decoder.as_ref()
+    }
+}
+
+impl cmp::PartialEq for DepotCard {
+    fn eq(&self, other: &Self) -> bool {
+        self.road_number == other.road_number
+            && self.class_name == other.class_name
+    }
+}
+
+impl cmp::Eq for DepotCard {}
+
+impl cmp::PartialOrd for DepotCard {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::Ord for DepotCard {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        let cmp1 = self.class_name.cmp(&other.class_name);
+        if cmp1 == cmp::Ordering::Equal {
+            return self.road_number.cmp(&other.road_number);
+        }
+
+        cmp1
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CollectionStats {
+    currency: Currency,
+    total_value: Decimal,
+    size: usize,
+    number_of_items_without_price: usize,
+    values_by_year: Vec<YearlyCollectionStats>,
+    totals: StatisticsTotals,
+    total_length_mm: u32,
+    average_length_mm: Option<Decimal>,
+    longest_item: Option<(String, LengthOverBuffer)>,
+    prices: Vec<Decimal>,
+}
+
+impl CollectionStats {
+    /// Creates the statistics for the items in the collection, the sold items
+    /// are not included.
+    pub fn from_collection(
+        collection: &Collection,
+    ) -> Result<Self, PriceError> {
+        CollectionStats::from_items(
+            collection
+                .get_items()
+                .iter()
+                .filter(|it| !it.is_sold())
+                .collect(),
+        )
+    }
+
+    /// Creates the statistics for every purchase, including the sold items:
+    /// the values for each purchase year do not change when the items are sold.
+    pub fn from_purchase_history(
+        collection: &Collection,
+    ) -> Result<Self, PriceError> {
+        CollectionStats::from_items(collection.get_items().iter().collect())
+    }
+
+    fn from_items(items: Vec<&CollectionItem>) -> Result<Self, PriceError> {
+        let currency = Price::common_currency(
+            items.iter().filter_map(|it| it.purchased_info().price()),
+        )?;
+        let mut output: HashMap<Year, YearlyCollectionStats> = HashMap::new();
+
+        for item in items.iter() {
+            let year = item.purchased_info().purchased_date().year();
+
+            output
+                .entry(year)
+                .or_insert_with(|| YearlyCollectionStats::new(year, currency))
+                .sum(item);
+        }
+
+        let mut values: Vec<YearlyCollectionStats> =
+            output.values().cloned().collect();
+        values.sort();
+
+        let mut totals = StatisticsTotals::new(currency);
+        for it in values.iter() {
+            totals.add(it);
+        }
+
+        let size = items.len();
+        let number_of_items_without_price = items
+            .iter()
+            .filter(|it| it.purchased_info().price().is_none())
+            .count();
+
+        Ok(CollectionStats {
+            currency,
+            total_value: totals.total_value(),
+            size,
+            number_of_items_without_price,
+            values_by_year: values,
+            totals,
+            total_length_mm: items.iter().map(|it| it.length_mm()).sum(),
+            average_length_mm: CollectionStats::average_length_mm_in(&items),
+            longest_item: CollectionStats::longest_item_in(&items),
+            prices: CollectionStats::prices_in(&items),
+        })
+    }
+
+    // The item prices, sorted from the cheapest
+    fn prices_in(items: &[&CollectionItem]) -> Vec<Decimal> {
+        let mut prices: Vec<Decimal> = items
+            .iter()
+            .filter_map(|it| it.purchased_info().price())
+            .map(Price::amount)
+            .collect();
+        prices.sort();
+        prices
+    }
+
+    // The average length for the rolling stocks with a known length
+    fn average_length_mm_in(items: &[&CollectionItem]) -> Option<Decimal> {
+        let (total, count) = items
+            .iter()
+            .flat_map(|it| {
+                let ci = it.catalog_item();
+                ci.rolling_stocks()
+                    .iter()
+                    .filter_map(|rs| rs.length_over_buffer())
+                    .map(move |length| (length.value(), u32::from(ci.count())))
+            })
+            .fold((0, 0), |(total, count), (length, n)| {
+                (total + length * n, count + n)
+            });
+
+        if count == 0 {
+            None
+        } else {
+            Some(Decimal::from(total) / Decimal::from(count))
+        }
+    }
+
+    // The longest rolling stock, together with its item description
+    fn longest_item_in(
+        items: &[&CollectionItem],
+    ) -> Option<(String, LengthOverBuffer)> {
+        items
+            .iter()
+            .flat_map(|it| {
+                let ci = it.catalog_item();
+                ci.rolling_stocks()
+                    .iter()
+                    .filter_map(|rs| rs.length_over_buffer())
+                    .map(move |length| (ci.description(), length))
+            })
+            .max_by_key(|(_, length)| length.value())
+            .map(|(description, length)| (description.to_owned(), length))
+    }
+
+    /// The currency for the collection values
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// The number of items without a purchase price (like gifts), they are
+    /// excluded from the collection values
+    pub fn number_of_items_without_price(&self) -> usize {
+        self.number_of_items_without_price
+    }
+
+    /// The total value of this collection
+    pub fn total_value(&self) -> Decimal {
+        self.total_value
+    }
+
+    /// The number of items included in this collection.
+    /// In case a catalog item contains more rolling stocks, all of them are accounted for.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Checks whether these statistics include no items at all
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// The total length (in millimeters) for the rolling stocks in this collection
+    pub fn total_length_mm(&self) -> u32 {
+        self.total_length_mm
+    }
+
+    /// The average length (in millimeters) for the rolling stocks, only the
+    /// rolling stocks with a known length are included
+    pub fn average_length_mm(&self) -> Option<Decimal> {
+        self.average_length_mm
+    }
+
+    /// The description and the length for the longest rolling stock
+    pub fn longest_item(&self) -> Option<(&str, LengthOverBuffer)> {
+        self.longest_item
+            .as_ref()
+            .map(|(description, length)| (description.as_str(), *length))
+    }
+
+    /// The average price for the items with a price, rounded to two decimals
+    pub fn average_price(&self) -> Option<Decimal> {
+        if self.prices.is_empty() {
+            None
+        } else {
+            let total: Decimal = self.prices.iter().sum();
+            Some((total / Decimal::from(self.prices.len())).round_dp(2))
+        }
+    }
+
+    /// The median price for the items with a price, with an even number of
+    /// prices it is the average of the two middle ones
+    pub fn median_price(&self) -> Option<Decimal> {
+        let len = self.prices.len();
+        if len == 0 {
+            None
+        } else if len % 2 == 1 {
+            Some(self.prices[len / 2])
+        } else {
+            let middle = self.prices[len / 2 - 1] + self.prices[len / 2];
+            Some((middle / Decimal::TWO).round_dp(2))
+        }
+    }
+
+    /// The lowest price paid for an item
+    pub fn min_price(&self) -> Option<Decimal> {
+        self.prices.first().copied()
+    }
+
+    /// The highest price paid for an item
+    pub fn max_price(&self) -> Option<Decimal> {
+        self.prices.last().copied()
+    }
+
+    /// The number of items with a price in each price range
+    pub fn price_distribution(&self) -> PriceDistribution {
+        PriceDistribution::from_prices(self.currency, &self.prices)
+    }
+
+    pub fn values_by_year(&self) -> &Vec<YearlyCollectionStats> {
+        &self.values_by_year
+    }
+
+    pub fn number_of_locomotives(&self) -> u8 {
+        self.totals.number_of_locomotives
+    }
+
+    pub fn locomotives_value(&self) -> Decimal {
+        self.totals.locomotives_value()
+    }
+
+    pub fn number_of_passenger_cars(&self) -> u8 {
+        self.totals.number_of_passenger_cars
+    }
+
+    pub fn passenger_cars_value(&self) -> Decimal {
+        self.totals.passenger_cars_value()
+    }
+
+    pub fn number_of_freight_cars(&self) -> u8 {
+        self.totals.number_of_freight_cars
+    }
+
+    pub fn freight_cars_value(&self) -> Decimal {
+        self.totals.freight_cars_value()
+    }
+
+    pub fn number_of_trains(&self) -> u8 {
+        self.totals.number_of_trains
+    }
+
+    pub fn trains_value(&self) -> Decimal {
+        self.totals.trains_value()
+    }
+
+    pub fn number_of_rolling_stocks(&self) -> u16 {
+        self.totals.number_of_rolling_stocks
+    }
+
+    /// The totals for all the years in this collection
+    pub fn totals(&self) -> &StatisticsTotals {
+        &self.totals
+    }
+}
+
+/// The number of items and rolling stocks in a collection; unlike the
+/// statistics, the counts do not look at the prices.
+#[derive(Debug, PartialEq)]
+pub struct CollectionCount {
+    number_of_items: usize,
+    number_of_rolling_stocks: usize,
+    by_category: Vec<(Category, usize)>,
+}
+
+impl CollectionCount {
+    /// Counts the items in the collection, the sold items are not included.
+    pub fn from_collection(collection: &Collection) -> Self {
+        CollectionCount::from_items(collection.owned_items().collect())
+    }
+
+    /// Counts every purchase, including the sold items.
+    pub fn from_purchase_history(collection: &Collection) -> Self {
+        CollectionCount::from_items(collection.get_items().iter().collect())
+    }
+
+    fn from_items(items: Vec<&CollectionItem>) -> Self {
+        let pieces = |category: Option<Category>| -> usize {
+            items
+                .iter()
+                .map(|it| it.catalog_item())
+                .filter(|ci| category.map_or(true, |c| ci.category() == c))
+                .map(|ci| usize::from(ci.count()))
+                .sum()
+        };
+
+        CollectionCount {
+            number_of_items: items.len(),
+            number_of_rolling_stocks: pieces(None),
+            by_category: Category::values()
+                .iter()
+                .map(|category| (*category, pieces(Some(*category))))
+                .collect(),
+        }
+    }
+
+    pub fn number_of_items(&self) -> usize {
+        self.number_of_items
+    }
+
+    pub fn number_of_rolling_stocks(&self) -> usize {
+        self.number_of_rolling_stocks
+    }
+
+    /// The number of rolling stocks for every category, in the
+    /// `Category::values()` order
+    pub fn by_category(&self) -> &[(Category, usize)] {
+        &self.by_category
+    }
+}
+
+pub type Year = i32;
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct YearlyCollectionStats {
+    year: Year,
+    locomotives: (u8, Price),
+    passenger_cars: (u8, Price),
+    freight_cars: (u8, Price),
+    trains: (u8, Price),
+    total: (u8, Price),
+}
+
+impl YearlyCollectionStats {
+    pub fn new(year: Year, currency: Currency) -> Self {
+        let zero = Price::zero(currency);
+
+        YearlyCollectionStats {
+            year,
+            locomotives: (0u8, zero.clone()),
+            passenger_cars: (0u8, zero.clone()),
+            freight_cars: (0u8, zero.clone()),
+            trains: (0u8, zero.clone()),
+            total: (0u8, zero),
+        }
+    }
+
+    pub fn new_from_item(item: &CollectionItem) -> YearlyCollectionStats {
+        let year = item.purchased_info().purchased_date().year();
+        let currency = item
+            .purchased_info()
+            .price()
+            .map(Price::currency)
+            .unwrap_or_default();
+        let mut stat = Self::new(year, currency);
+        stat.sum(item);
+        stat
+    }
+
+    pub fn sum(&mut self, item: &CollectionItem) {
+        let values = match item.catalog_item().category() {
+            Category::FreightCars => &mut self.freight_cars,
+            Category::Locomotives => &mut self.locomotives,
+            Category::PassengerCars => &mut self.passenger_cars,
+            Category::Trains => &mut self.trains,
+        };
+        Self::add_item(values, item);
+        Self::add_item(&mut self.total, item);
+    }
+
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    pub fn number_of_locomotives(&self) -> u8 {
+        self.locomotives.0
+    }
+
+    pub fn locomotives_value(&self) -> Decimal {
+        self.locomotives.1.amount()
+    }
+
+    pub fn number_of_passenger_cars(&self) -> u8 {
+        self.passenger_cars.0
+    }
+
+    pub fn passenger_cars_value(&self) -> Decimal {
+        self.passenger_cars.1.amount()
+    }
+
+    pub fn number_of_freight_cars(&self) -> u8 {
+        self.freight_cars.0
+    }
+
+    pub fn freight_cars_value(&self) -> Decimal {
+        self.freight_cars.1.amount()
+    }
+
+    pub fn number_of_trains(&self) -> u8 {
+        self.trains.0
+    }
+
+    pub fn trains_value(&self) -> Decimal {
+        self.trains.1.amount()
+    }
+
+    pub fn number_of_rolling_stocks(&self) -> u8 {
+        self.total.0
+    }
+
+    pub fn total_value(&self) -> Decimal {
+        self.total.1.amount()
+    }
+
+    // Helper method to add the item count and price to the values
+    fn add_item(values: &mut (u8, Price), item: &CollectionItem) {
+        values.0 += item.catalog_item().count();
+        if let Some(price) = item.purchased_at.price() {
+            values.1 += price.clone();
+        }
+    }
+}
+
+impl cmp::PartialOrd for YearlyCollectionStats {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl cmp::Ord for YearlyCollectionStats {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        self.year.cmp(&other.year)
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct StatisticsTotals {
+    number_of_locomotives: u8,
+    locomotives_value: Price,
+    number_of_trains: u8,
+    trains_value: Price,
+    number_of_passenger_cars: u8,
+    passenger_cars_value: Price,
+    number_of_freight_cars: u8,
+    freight_cars_value: Price,
+    number_of_rolling_stocks: u16,
+    total_value: Price,
+}
+
+impl StatisticsTotals {
+    pub fn new(currency: Currency) -> Self {
+        StatisticsTotals {
+            number_of_locomotives: 0u8,
+            locomotives_value: Price::zero(currency),
+            number_of_trains: 0u8,
+            trains_value: Price::zero(currency),
+            number_of_passenger_cars: 0u8,
+            passenger_cars_value: Price::zero(currency),
+            number_of_freight_cars: 0u8,
+            freight_cars_value: Price::zero(currency),
+            number_of_rolling_stocks: 0u16,
+            total_value: Price::zero(currency),
+        }
+    }
+
+    fn add(&mut self, yearly: &YearlyCollectionStats) {
+        self.number_of_locomotives += yearly.number_of_locomotives();
+        self.locomotives_value += yearly.locomotives.1.clone();
+        self.number_of_trains += yearly.number_of_trains();
+        self.trains_value += yearly.trains.1.clone();
+        self.number_of_passenger_cars += yearly.number_of_passenger_cars();
+        self.passenger_cars_value += yearly.passenger_cars.1.clone();
+        self.number_of_freight_cars += yearly.number_of_freight_cars();
+        self.freight_cars_value += yearly.freight_cars.1.clone();
+        self.number_of_rolling_stocks +=
+            yearly.number_of_rolling_stocks() as u16;
+        self.total_value += yearly.total.1.clone();
+    }
+
+    pub fn number_of_locomotives(&self) -> u8 {
+        self.number_of_locomotives
+    }
+
+    pub fn locomotives_value(&self) -> Decimal {
+        self.locomotives_value.amount()
+    }
+
+    pub fn number_of_passenger_cars(&self) -> u8 {
+        self.number_of_passenger_cars
+    }
+
+    pub fn passenger_cars_value(&self) -> Decimal {
+        self.passenger_cars_value.amount()
+    }
+
+    pub fn number_of_freight_cars(&self) -> u8 {
+        self.number_of_freight_cars
+    }
+
+    pub fn freight_cars_value(&self) -> Decimal {
+        self.freight_cars_value.amount()
+    }
+
+    pub fn number_of_trains(&self) -> u8 {
+        self.number_of_trains
+    }
+
+    pub fn trains_value(&self) -> Decimal {
+        self.trains_value.amount()
+    }
+
+    pub fn number_of_rolling_stocks(&self) -> u16 {
+        self.number_of_rolling_stocks
+    }
+
+    pub fn total_value(&self) -> Decimal {
+        self.total_value.amount()
+    }
+}
+
+/// The number of items for each price range.
+#[derive(Debug, PartialEq)]
+pub struct PriceDistribution {
+    currency: Currency,
+    buckets: Vec<PriceBucket>,
+}
+
+impl PriceDistribution {
+    /// The upper bounds for the price ranges in euro, the last range has no
+    /// upper bound.
+    const BOUNDS: [i64; 4] = [50, 100, 200, 500];
+
+    /// The factor to scale the bounds in the given currency, the yen amounts
+    /// are roughly a hundred times the amounts in the other currencies.
+    fn bounds_scale(currency: Currency) -> i64 {
+        match currency {
+            Currency::JPY => 100,
+            Currency::EUR | Currency::USD | Currency::GBP | Currency::CHF => 1,
+        }
+    }
+
+    fn from_prices(currency: Currency, prices: &[Decimal]) -> Self {
+        let scale = PriceDistribution::bounds_scale(currency);
+        let mut buckets: Vec<PriceBucket> = Vec::new();
+        let mut min = None;
+        for bound in PriceDistribution::BOUNDS
+            .iter()
+            .map(|it| Some(Decimal::from(*it * scale)))
+            .chain(std::iter::once(None))
+        {
+            let number_of_items = prices
+                .iter()
+                .filter(|price| {
+                    min.map_or(true, |min| **price >= min)
+                        && bound.map_or(true, |max| **price < max)
+                })
+                .count();
+            buckets.push(PriceBucket {
+                min,
+                max: bound,
+                number_of_items,
+            });
+            min = bound;
+        }
+
+        PriceDistribution { currency, buckets }
+    }
+
+    /// The currency for the price ranges
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn buckets(&self) -> &Vec<PriceBucket> {
+        &self.buckets
+    }
+
+    /// The number of items with a price
+    pub fn number_of_items(&self) -> usize {
+        self.buckets.iter().map(|it| it.number_of_items).sum()
+    }
+
+    /// The percentage of the items with a price in this price range, rounded
+    /// to two decimals
+    pub fn percentage(&self, bucket: &PriceBucket) -> Decimal {
+        let total = self.number_of_items();
+        if total == 0 {
+            Decimal::ZERO
+        } else {
+            (Decimal::from(bucket.number_of_items) * Decimal::ONE_HUNDRED
+                / Decimal::from(total))
+            .round_dp(2)
+        }
+    }
+}
+
+/// A price range, including the lower bound and excluding the upper one.
+#[derive(Debug, PartialEq)]
+pub struct PriceBucket {
+    min: Option<Decimal>,
+    max: Option<Decimal>,
+    number_of_items: usize,
+}
+
+impl PriceBucket {
+    pub fn min(&self) -> Option<Decimal> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<Decimal> {
+        self.max
+    }
+
+    pub fn number_of_items(&self) -> usize {
+        self.number_of_items
+    }
+}
+
+impl fmt::Display for PriceBucket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (None, Some(max)) => write!(f, "< {}", max),
+            (Some(min), Some(max)) => write!(f, "{}-{}", min, max),
+            (Some(min), None) => write!(f, ">= {}", min),
+            (None, None) => write!(f, "any"),
+        }
+    }
+}
+
+/// The collection statistics grouped by scale.
+#[derive(Debug, PartialEq)]
+pub struct CollectionStatsByScale {
+    currency: Currency,
+    values: Vec<ScaleStats>,
+    others: Option<OtherGroups>,
+}
+
+impl CollectionStatsByScale {
+    pub fn from_collection(
+        collection: &Collection,
+    ) -> Result<Self, PriceError> {
+        let currency = collection.currency()?;
+        let mut values: Vec<ScaleStats> = Vec::new();
+
+        for item in collection.get_items() {
+            let scale = item.catalog_item().scale();
+            match values.iter_mut().find(|it| &it.scale == scale) {
+                Some(stats) => stats.sum(item),
+                None => {
+                    let mut stats = ScaleStats::new(scale.clone());
+                    stats.sum(item);
+                    values.push(stats);
+                }
+            }
+        }
+
+        values.sort_by(|a, b| a.scale.name().cmp(b.scale.name()));
+        Ok(CollectionStatsByScale {
+            currency,
+            values,
+            others: None,
+        })
+    }
+
+    /// The currency for the collection values
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Sorts the values by scale ratio, from the largest models to the smallest ones.
+    pub fn sort_by_ratio(&mut self) {
+        self.values.sort_by(|a, b| a.scale.cmp(&b.scale));
+    }
+
+    /// Keeps only the `n` scales with the highest value, the other scales are
+    /// collapsed together.
+    pub fn keep_top(&mut self, n: usize) {
+        self.others = keep_top(
+            &mut self.values,
+            n,
+            |it| it.total_value,
+            |it| it.number_of_items,
+        );
+    }
+
+    pub fn values(&self) -> &Vec<ScaleStats> {
+        &self.values
+    }
+
+    /// The scales collapsed by `keep_top`, if any
+    pub fn others(&self) -> Option<&OtherGroups> {
+        self.others.as_ref()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ScaleStats {
+    scale: Scale,
+    number_of_items: u16,
+    total_value: Decimal,
+}
+
+impl ScaleStats {
+    fn new(scale: Scale) -> Self {
+        ScaleStats {
+            scale,
+            number_of_items: 0,
+            total_value: Decimal::from(0),
+        }
+    }
+
+    fn sum(&mut self, item: &CollectionItem) {
+        self.number_of_items += item.catalog_item().count() as u16;
+        self.total_value += item
+            .purchased_info()
+            .price()
+            .map(Price::amount)
+            .unwrap_or_default();
+    }
+
+    pub fn scale(&self) -> &Scale {
+        &self.scale
+    }
+
+    pub fn number_of_items(&self) -> u16 {
+        self.number_of_items
+    }
+
+    pub fn total_value(&self) -> Decimal {
+        self.total_value
+    }
+}
+
+/// The collection statistics grouped by railway.
+///
+/// Rolling stocks marked for more railways (ie "DB/SNCF") are counted toward each of them.
+#[derive(Debug)]
+pub struct CollectionStatsByRailway {
+    currency: Currency,
+    values: Vec<RailwayStats>,
+    others: Option<OtherGroups>,
+}
+
+impl CollectionStatsByRailway {
+    pub fn from_collection(
+        collection: &Collection,
+    ) -> Result<Self, PriceError> {
+        let currency = collection.currency()?;
+        let mut values: Vec<RailwayStats> = Vec::new();
+
+        for item in collection.get_items() {
+            let mut railways: Vec<&Railway> = Vec::new();
+            for rs in item.catalog_item().rolling_stocks() {
+                for railway in rs.railway().railways() {
+                    if !railways.contains(&railway) {
+                        railways.push(railway);
+                    }
+                }
+            }
+
+            for railway in railways {
+                match values.iter_mut().find(|it| &it.railway == railway) {
+                    Some(stats) => stats.sum(item),
+                    None => {
+                        let mut stats = RailwayStats::new(railway);
+                        stats.sum(item);
+                        values.push(stats);
+                    }
+                }
+            }
+        }
+
+        values.sort_by(|a, b| a.railway().cmp(b.railway()));
+        Ok(CollectionStatsByRailway {
+            currency,
+            values,
+            others: None,
+        })
+    }
+
+    /// The currency for the collection values
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Keeps only the `n` railways with the highest value, the other railways
+    /// are collapsed together.
+    pub fn keep_top(&mut self, n: usize) {
+        self.others = keep_top(
+            &mut self.values,
+            n,
+            |it| it.total_value,
+            |it| it.number_of_items,
+        );
+    }
+
+    pub fn values(&self) -> &Vec<RailwayStats> {
+        &self.values
+    }
+
+    /// The railways collapsed by `keep_top`, if any
+    pub fn others(&self) -> Option<&OtherGroups> {
+        self.others.as_ref()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RailwayStats {
+    railway: Railway,
+    number_of_items: u16,
+    total_value: Decimal,
+}
+
+impl RailwayStats {
+    fn new(railway: &Railway) -> Self {
+        RailwayStats {
+            railway: railway.clone(),
+            number_of_items: 0,
+            total_value: Decimal::from(0),
+        }
+    }
+
+    fn sum(&mut self, item: &CollectionItem) {
+        self.number_of_items += item.catalog_item().count() as u16;
+        self.total_value += item
+            .purchased_info()
+            .price()
+            .map(Price::amount)
+            .unwrap_or_default();
+    }
+
+    pub fn railway(&self) -> &str {
+        self.railway.name()
+    }
+
+    /// The country code for the railway, if known
+    pub fn country(&self) -> Option<&str> {
+        self.railway.country()
+    }
+
+    pub fn number_of_items(&self) -> u16 {
+        self.number_of_items
+    }
+
+    pub fn total_value(&self) -> Decimal {
+        self.total_value
+    }
+}
+
+/// The collection statistics grouped by brand.
+#[derive(Debug)]
+pub struct CollectionStatsByBrand {
+    currency: Currency,
+    values: Vec<BrandStats>,
+    others: Option<OtherGroups>,
+}
+
+impl CollectionStatsByBrand {
+    pub fn from_collection(
+        collection: &Collection,
+    ) -> Result<Self, PriceError> {
+        let currency = collection.currency()?;
+        let mut values: Vec<BrandStats> = Vec::new();
+
+        for item in collection.get_items() {
+            let brand = item.catalog_item().brand().name();
+            match values
+                .iter_mut()
+                .find(|it| it.brand.eq_ignore_ascii_case(brand))
+            {
+                Some(stats) => stats.sum(item),
+                None => {
+                    let mut stats = BrandStats::new(brand);
+                    stats.sum(item);
+                    values.push(stats);
+                }
+            }
+        }
+
+        values.sort_by(|a, b| a.brand.cmp(&b.brand));
+        Ok(CollectionStatsByBrand {
+            currency,
+            values,
+            others: None,
+        })
+    }
+
+    /// The currency for the collection values
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Keeps only the `n` brands with the highest value, the other brands are
+    /// collapsed together.
+    pub fn keep_top(&mut self, n: usize) {
+        self.others = keep_top(
+            &mut self.values,
+            n,
+            |it| it.total_value,
+            |it| it.number_of_items,
+        );
+    }
+
+    pub fn values(&self) -> &Vec<BrandStats> {
+        &self.values
+    }
+
+    /// The brands collapsed by `keep_top`, if any
+    pub fn others(&self) -> Option<&OtherGroups> {
+        self.others.as_ref()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BrandStats {
+    brand: String,
+    number_of_items: u16,
+    total_value: Decimal,
+}
+
+impl BrandStats {
+    fn new(brand: &str) -> Self {
+        BrandStats {
+            brand: brand.to_owned(),
+            number_of_items: 0,
+            total_value: Decimal::from(0),
+        }
+    }
+
+    fn sum(&mut self, item: &CollectionItem) {
+        self.number_of_items += item.catalog_item().count() as u16;
+        self.total_value += item
+            .purchased_info()
+            .price()
+            .map(Price::amount)
+            .unwrap_or_default();
+    }
+
+    pub fn brand(&self) -> &str {
+        &self.brand
+    }
+
+    pub fn number_of_items(&self) -> u16 {
+        self.number_of_items
+    }
+
+    pub fn total_value(&self) -> Decimal {
+        self.total_value
+    }
+}
+
+/// The groups collapsed together in the grouped statistics, to show only the
+/// groups with the highest value.
+#[derive(Debug, PartialEq, Default)]
+pub struct OtherGroups {
+    number_of_groups: usize,
+    number_of_items: u16,
+    total_value: Decimal,
+}
+
+impl OtherGroups {
+    pub fn number_of_groups(&self) -> usize {
+        self.number_of_groups
+    }
+
+    pub fn number_of_items(&self) -> u16 {
+        self.number_of_items
+    }
+
+    pub fn total_value(&self) -> Decimal {
+        self.total_value
+    }
+}
+
+// Keeps the `n` groups with the highest value (in their current order) and sums
+// the other groups together; ties are resolved in favour of the first groups.
+fn keep_top<T>(
+    values: &mut Vec<T>,
+    n: usize,
+    total_value: fn(&T) -> Decimal,
+    number_of_items: fn(&T) -> u16,
+) -> Option<OtherGroups> {
+    if values.len() <= n {
+        return None;
+    }
+
+    let mut by_value: Vec<usize> = (0..values.len()).collect();
+    by_value.sort_by(|a, b| {
+        total_value(&values[*b]).cmp(&total_value(&values[*a]))
+    });
+    let top: BTreeSet<usize> = by_value.into_iter().take(n).collect();
+
+    let mut others = OtherGroups::default();
+    let mut index = 0;
+    values.retain(|it| {
+        let keep = top.contains(&index);
+        index += 1;
+        if !keep {
+            others.number_of_groups += 1;
+            others.number_of_items += number_of_items(it);
+            others.total_value += total_value(it);
+        }
+        keep
+    });
+
+    Some(others)
+}
+
+/// The collection purchases grouped by year and month.
+#[derive(Debug)]
+pub struct PurchasesByMonth {
+    currency: Currency,
+    values: BTreeMap<(Year, u32), MonthlyPurchases>,
+}
+
+impl PurchasesByMonth {
+    pub fn from_collection(
+        collection: &Collection,
+    ) -> Result<Self, PriceError> {
+        let currency = collection.currency()?;
+        let mut values = BTreeMap::new();
+
+        for item in collection.get_items() {
+            let date = item.purchased_info().purchased_date();
+            values
+                .entry((date.year(), date.month()))
+                .or_insert_with(MonthlyPurchases::default)
+                .sum(item);
+        }
+
+        Ok(PurchasesByMonth { currency, values })
+    }
+
+    /// The currency for the amounts spent
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Returns the years with at least one purchase, in ascending order
+    pub fn years(&self) -> Vec<Year> {
+        let mut years: Vec<Year> =
+            self.values.keys().map(|(year, _)| *year).collect();
+        years.dedup();
+        years
+    }
+
+    /// Returns the purchases for the month (1 to 12) in the given year
+    pub fn get(&self, year: Year, month: u32) -> Option<&MonthlyPurchases> {
+        self.values.get(&(year, month))
+    }
+
+    /// The highest number of items purchased in a single month
+    pub fn max_number_of_items(&self) -> u16 {
+        self.values
+            .values()
+            .map(|it| it.number_of_items)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// The highest amount spent in a single month
+    pub fn max_total_value(&self) -> Decimal {
+        self.values
+            .values()
+            .map(|it| it.total_value)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+pub struct MonthlyPurchases {
+    number_of_items: u16,
+    total_value: Decimal,
+}
+
+impl MonthlyPurchases {
+    fn sum(&mut self, item: &CollectionItem) {
+        self.number_of_items += item.catalog_item().count() as u16;
+        self.total_value += item
+            .purchased_info()
+            .price()
+            .map(Price::amount)
+            .unwrap_or_default();
+    }
+
+    pub fn number_of_items(&self) -> u16 {
+        self.number_of_items
+    }
+
+    pub fn total_value(&self) -> Decimal {
+        self.total_value
+    }
+}
+
+/// The amounts spent over time (by year or by month), with the running total
+/// since the first purchase.
+#[derive(Debug)]
+pub struct SpendingTimeline {
+    currency: Currency,
+    entries: Vec<TimelineEntry>,
+}
+
+impl SpendingTimeline {
+    /// Creates the timeline for the collection purchases, every period between
+    /// the bounds is included even without purchases. Without bounds the
+    /// timeline goes from the first to the last purchase.
+    pub fn from_collection(
+        collection: &Collection,
+        monthly: bool,
+        from: Option<Year>,
+        to: Option<Year>,
+    ) -> Result<Self, PriceError> {
+        let currency = collection.currency()?;
+
+        let period = |date: &NaiveDate| {
+            if monthly {
+                (date.year(), Some(date.month()))
+            } else {
+                (date.year(), None)
+            }
+        };
+
+        let mut periods: BTreeMap<(Year, Option<u32>), TimelineEntry> =
+            BTreeMap::new();
+        for item in collection.get_items() {
+            let (year, month) = period(item.purchased_info().purchased_date());
+            periods
+                .entry((year, month))
+                .or_insert_with(|| TimelineEntry::new(year, month))
+                .sum(item);
+        }
+
+        let first = from
+            .map(|year| (year, monthly.then_some(1)))
+            .or_else(|| periods.keys().next().copied());
+        let last = to
+            .map(|year| (year, monthly.then_some(12)))
+            .or_else(|| periods.keys().next_back().copied());
+
+        let mut entries = Vec::new();
+        if let (Some(first), Some(last)) = (first, last) {
+            let mut cumulative: Decimal = periods
+                .range(..first)
+                .map(|(_, entry)| entry.amount_spent)
+                .sum();
+
+            let mut current = first;
+            while current <= last {
+                let mut entry = periods.remove(&current).unwrap_or_else(|| {
+                    TimelineEntry::new(current.0, current.1)
+                });
+                cumulative += entry.amount_spent;
+                entry.cumulative_total = cumulative;
+                entries.push(entry);
+
+                current = match current {
+                    (year, Some(12)) => (year + 1, Some(1)),
+                    (year, Some(month)) => (year, Some(month + 1)),
+                    (year, None) => (year + 1, None),
+                };
+            }
+        }
+
+        Ok(SpendingTimeline { currency, entries })
+    }
+
+    /// The currency for the amounts spent
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn entries(&self) -> &[TimelineEntry] {
+        &self.entries
+    }
+}
+
+/// The purchases for a single year (or month) in the spending timeline.
+#[derive(Debug, PartialEq)]
+pub struct TimelineEntry {
+    year: Year,
+    month: Option<u32>,
+    number_of_items: u16,
+    number_of_priced_items: u16,
+    amount_spent: Decimal,
+    cumulative_total: Decimal,
+}
+
+impl TimelineEntry {
+    fn new(year: Year, month: Option<u32>) -> Self {
+        TimelineEntry {
+            year,
+            month,
+            number_of_items: 0,
+            number_of_priced_items: 0,
+            amount_spent: Decimal::ZERO,
+            cumulative_total: Decimal::ZERO,
+        }
+    }
+
+    // the purchases are counted as catalog items, a set is one item
+    fn sum(&mut self, item: &CollectionItem) {
+        self.number_of_items += 1;
+        if let Some(price) = item.purchased_info().price() {
+            self.number_of_priced_items += 1;
+            self.amount_spent += price.amount();
+        }
+    }
+
+    pub fn year(&self) -> Year {
+        self.year
+    }
+
+    /// The month (1 to 12) for the monthly timelines
+    pub fn month(&self) -> Option<u32> {
+        self.month
+    }
+
+    /// The period label, like "2020" or "2020-03"
+    pub fn period(&self) -> String {
+        match self.month {
+            Some(month) => format!("{}-{:02}", self.year, month),
+            None => self.year.to_string(),
+        }
+    }
+
+    pub fn number_of_items(&self) -> u16 {
+        self.number_of_items
+    }
+
+    pub fn amount_spent(&self) -> Decimal {
+        self.amount_spent
+    }
+
+    /// The total amount spent up to (and including) this period
+    pub fn cumulative_total(&self) -> Decimal {
+        self.cumulative_total
+    }
+
+    /// The average price for the items with a purchase price
+    pub fn average_price(&self) -> Option<Decimal> {
+        if self.number_of_priced_items == 0 {
+            None
+        } else {
+            Some(self.amount_spent / Decimal::from(self.number_of_priced_items))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::catalog::{
+        brands::Brand,
+        categories::LocomotiveType,
+        rolling_stocks::{
+            Control, Epoch, LengthOverBuffer, RollingStockBuilder,
+        },
+    };
+    use crate::domain::collecting::fixtures;
+
+    fn new_locomotive_builder(road_number: &str) -> RollingStockBuilder {
+        RollingStockBuilder::locomotive()
+            .with_class_name("E.656")
+            .with_road_number(road_number)
+            .with_railway(Railway::new("FS"))
+            .with_epoch(Epoch::IV)
+            .with_locomotive_type(LocomotiveType::ElectricLocomotive)
+            .with_control(Control::DccReady)
+    }
+
+    fn new_locomotive(road_number: &str) -> RollingStock {
+        new_locomotive_builder(road_number).build().unwrap()
+    }
+
+    fn new_catalog_item(
+        brand: &str,
+        item_number: &str,
+        scale: Scale,
+    ) -> CatalogItem {
+        new_catalog_item_with_power_method(
+            brand,
+            item_number,
+            scale,
+            PowerMethod::DC,
+        )
+    }
+
+    fn new_catalog_item_with_power_method(
+        brand: &str,
+        item_number: &str,
+        scale: Scale,
+        power_method: PowerMethod,
+    ) -> CatalogItem {
+        CatalogItem::new(
+            Brand::new(brand),
+            ItemNumber::new(item_number).unwrap(),
+            String::from("My catalog item"),
+            vec![new_locomotive("E.656 210")],
+            power_method,
+            scale,
+            None,
+            1,
+        )
+    }
+
+    fn new_purchased_info(date: &str, price: &str) -> PurchasedInfo {
+        fixtures::new_purchased_info("Treni&Treni", date, price)
+    }
+
+    fn new_collection() -> Collection {
+        let mut collection = Collection::create_empty("My collection");
+        collection.add_item(
+            new_catalog_item("ACME", "60023", Scale::H0()),
+            new_purchased_info("2019-05-02", "229,00"),
+        );
+        collection.add_item(
+            new_catalog_item("Fleischmann", "7360", Scale::N()),
+            new_purchased_info("2020-01-10", "120"),
+        );
+        collection.add_item(
+            new_catalog_item("Roco", "62345", Scale::H0()),
+            new_purchased_info("2020-03-10", "150,50"),
+        );
+        collection.add_item(
+            new_catalog_item("Lenz", "40190", Scale::O()),
+            new_purchased_info("2021-09-21", "550"),
+        );
+        collection
+    }
+
+    mod collection_tests {
+        use super::*;
+
+        fn new_mixed_collection(dc_items: usize) -> Collection {
+            let mut collection = Collection::create_empty("My collection");
+            for i in 0..dc_items {
+                collection.add_item(
+                    new_catalog_item(
+                        "Roco",
+                        &format!("6{:04}", i),
+                        Scale::H0(),
+                    ),
+                    new_purchased_info("2020-01-10", "100"),
+                );
+            }
+            collection.add_item(
+                new_catalog_item_with_power_method(
+                    "Maerklin",
+                    "39656",
+                    Scale::H0(),
+                    PowerMethod::AC,
+                ),
+                new_purchased_info("2020-01-10", "300"),
+            );
+            collection.add_item(
+                new_catalog_item("Fleischmann", "7360", Scale::N()),
+                new_purchased_info("2020-01-10", "120"),
+            );
+            collection
+        }
+
+        #[test]
+        fn it_should_find_minority_power_methods_by_scale() {
+            let collection = new_mixed_collection(19);
+
+            let inconsistencies = collection.power_method_inconsistencies(10);
+            assert_eq!(1, inconsistencies.len());
+
+            let it = &inconsistencies[0];
+            assert_eq!("H0", it.scale());
+            assert_eq!(PowerMethod::DC, it.majority());
+            assert_eq!(PowerMethod::AC, it.minority());
+            assert_eq!(20, it.number_of_items());
+            assert_eq!(1, it.items().len());
+            assert_eq!(
+                "39656",
+                it.items()[0].catalog_item().item_number().value()
+            );
+        }
+
+        #[test]
+        fn it_should_pick_the_first_power_method_as_majority_on_a_tie() {
+            let collection = new_mixed_collection(1);
+
+            let inconsistencies = collection.power_method_inconsistencies(50);
+            assert_eq!(1, inconsistencies.len());
+
+            let it = &inconsistencies[0];
+            assert_eq!(PowerMethod::DC, it.majority());
+            assert_eq!(PowerMethod::AC, it.minority());
+            assert_eq!(2, it.number_of_items());
+        }
+
+        #[test]
+        fn it_should_ignore_power_methods_above_the_threshold() {
+            let collection = new_mixed_collection(3);
+
+            let inconsistencies = collection.power_method_inconsistencies(10);
+            assert!(inconsistencies.is_empty());
+        }
+    }
+
+    mod condition_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_parse_conditions() {
+            assert_eq!(Ok(Condition::New), "NEW".parse::<Condition>());
+            assert_eq!(Ok(Condition::LikeNew), "LIKE_NEW".parse::<Condition>());
+            assert_eq!(Ok(Condition::Used), "USED".parse::<Condition>());
+            assert_eq!(Ok(Condition::Damaged), "DAMAGED".parse::<Condition>());
+            assert!("BROKEN".parse::<Condition>().is_err());
+        }
+
+        #[test]
+        fn it_should_display_conditions() {
+            assert_eq!("LIKE_NEW", Condition::LikeNew.to_string());
+        }
+    }
+
+    mod sort_items_tests {
+        use super::*;
+
+        fn item_numbers(collection: &Collection) -> Vec<&str> {
+            collection
+                .get_items()
+                .iter()
+                .map(|it| it.catalog_item().item_number().value())
+                .collect()
+        }
+
+        #[test]
+        fn it_should_sort_the_items_by_price_descending() {
+            let mut collection = new_collection();
+            collection.sort_items_by(SortItemsBy::Price, true);
+            assert_eq!(
+                vec!["40190", "60023", "62345", "7360"],
+                item_numbers(&collection)
+            );
+        }
+
+        #[test]
+        fn it_should_sort_the_items_by_date() {
+            let mut collection = new_collection();
+            collection.sort_items_by(SortItemsBy::Date, false);
+            assert_eq!(
+                vec!["60023", "7360", "62345", "40190"],
+                item_numbers(&collection)
+            );
+        }
+
+        #[test]
+        fn it_should_sort_the_items_with_the_same_value_by_brand() {
+            let mut collection = new_collection();
+            collection.sort_items_by(SortItemsBy::Count, false);
+            assert_eq!(
+                vec!["60023", "7360", "40190", "62345"],
+                item_numbers(&collection)
+            );
+        }
+
+        #[test]
+        fn it_should_keep_the_brand_order_for_the_same_value_when_descending() {
+            let mut collection = new_collection();
+            collection.sort_items_by(SortItemsBy::Count, true);
+            assert_eq!(
+                vec!["60023", "7360", "40190", "62345"],
+                item_numbers(&collection)
+            );
+        }
+    }
+
+    mod sold_items_tests {
+        use super::*;
+
+        fn sold_on(date: &str, price: &str) -> SoldInfo {
+            SoldInfo::new(
+                NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+                price.parse::<Price>().unwrap(),
+            )
+        }
+
+        fn new_collection_with_sales() -> Collection {
+            let mut collection = new_collection();
+            collection.add_collection_item(
+                CollectionItem::new(
+                    new_catalog_item("Roco", "43000", Scale::H0()),
+                    new_purchased_info("2019-03-01", "100"),
+                )
+                .with_sold_info(
+                    sold_on("2022-05-01", "130").with_buyer("Mario"),
+                ),
+            );
+            collection.add_collection_item(
+                CollectionItem::new(
+                    new_catalog_item("Roco", "43001", Scale::H0()),
+                    new_purchased_info("2020-03-01", "80"),
+                )
+                .with_sold_info(sold_on("2021-09-01", "50")),
+            );
+            collection
+        }
+
+        #[test]
+        fn it_should_calculate_the_realized_gain_for_sold_items() {
+            let collection = new_collection_with_sales();
+
+            assert_eq!(None, collection.get(0).unwrap().realized_gain());
+            assert_eq!(
+                Some(Decimal::from(30)),
+                collection.get(4).unwrap().realized_gain()
+            );
+            assert_eq!(
+                Some(Decimal::from(-30)),
+                collection.get(5).unwrap().realized_gain()
+            );
+        }
+
+        #[test]
+        fn it_should_exclude_the_sold_items_from_the_stats() {
+            let collection = new_collection_with_sales();
+            let stats = CollectionStats::from_collection(&collection).unwrap();
+            let expected =
+                CollectionStats::from_collection(&new_collection()).unwrap();
+
+            assert_eq!(expected.size(), stats.size());
+            assert_eq!(expected.total_value(), stats.total_value());
+            assert_eq!(4, collection.without_sold().len());
+        }
+
+        #[test]
+        fn it_should_keep_the_purchase_history_stable_after_the_sales() {
+            let collection = new_collection_with_sales();
+            let stats =
+                CollectionStats::from_purchase_history(&collection).unwrap();
+
+            assert_eq!(6, stats.size());
+            assert_eq!(Decimal::new(122950, 2), stats.total_value());
+            let years: Vec<Year> =
+                stats.values_by_year().iter().map(|it| it.year).collect();
+            assert_eq!(vec![2019, 2020, 2021], years);
+        }
+
+        #[test]
+        fn it_should_report_the_sales_by_year() {
+            let collection = new_collection_with_sales();
+            let report = SalesReport::from_collection(&collection).unwrap();
+
+            assert_eq!(2, report.len());
+            assert_eq!(
+                "43001",
+                report.sales()[0].catalog_item().item_number().value()
+            );
+            assert_eq!(Decimal::ZERO, report.realized_gain());
+
+            let by_year = report.by_year();
+            assert_eq!(2021, by_year[0].year());
+            assert_eq!(Decimal::from(-30), by_year[0].realized_gain());
+            assert_eq!(2022, by_year[1].year());
+            assert_eq!(Decimal::from(130), by_year[1].sold_total());
+        }
+    }
+
+    mod filtered_tests {
+        use super::*;
+
+        fn purchased_before(date: &str) -> impl Fn(&CollectionItem) -> bool {
+            let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+            move |it| it.purchased_info().purchased_date() < &date
+        }
+
+        #[test]
+        fn it_should_keep_only_the_matching_items() {
+            let collection =
+                new_collection().filtered(purchased_before("2020-03-10"));
+
+            assert_eq!("My collection", collection.description());
+            assert_eq!(2, collection.len());
+        }
+
+        #[test]
+        fn it_should_split_the_stats_totals_between_the_date_ranges() {
+            let before = purchased_before("2020-03-10");
+            let full =
+                CollectionStats::from_collection(&new_collection()).unwrap();
+            let first = CollectionStats::from_collection(
+                &new_collection().filtered(|it| before(it)),
+            )
+            .unwrap();
+            let second = CollectionStats::from_collection(
+                &new_collection().filtered(|it| !before(it)),
+            )
+            .unwrap();
+
+            assert_eq!(Decimal::new(34900, 2), first.total_value());
+            assert_eq!(
+                full.total_value(),
+                first.total_value() + second.total_value()
+            );
+            assert_eq!(full.size(), first.size() + second.size());
+            assert_eq!(
+                full.number_of_locomotives(),
+                first.number_of_locomotives() + second.number_of_locomotives()
+            );
+        }
+    }
+
+    mod by_price_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_iterate_the_items_from_the_most_expensive() {
+            let collection = new_collection();
+            let item_numbers: Vec<&str> = collection
+                .by_price()
+                .map(|it| it.catalog_item().item_number().value())
+                .collect();
+            assert_eq!(vec!["40190", "60023", "62345", "7360"], item_numbers);
+        }
+
+        #[test]
+        fn it_should_sort_the_items_with_the_same_price_by_purchase_date() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                new_catalog_item("Roco", "62345", Scale::H0()),
+                new_purchased_info("2021-03-10", "150"),
+            );
+            collection.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2019-05-02", "150"),
+            );
+            collection.add_item(
+                new_catalog_item("Lenz", "40190", Scale::O()),
+                PurchasedInfo::without_price(
+                    "Treni&Treni",
+                    NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                ),
+            );
+
+            let item_numbers: Vec<&str> = collection
+                .by_price()
+                .map(|it| it.catalog_item().item_number().value())
+                .collect();
+            assert_eq!(vec!["60023", "62345"], item_numbers);
+        }
+    }
+
+    mod top_purchases_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_select_the_most_expensive_purchases() {
+            let collection = new_collection();
+            let top =
+                TopPurchases::from_collection(&collection, 2, &[]).unwrap();
+
+            assert_eq!(2, top.len());
+            assert_eq!(Decimal::new(104950, 2), top.total_value());
+
+            let first = &top.purchases()[0];
+            assert_eq!(
+                "40190",
+                first.item().catalog_item().item_number().value()
+            );
+            assert_eq!(Decimal::new(5241, 2), first.percentage());
+            assert_eq!(
+                "60023",
+                top.purchases()[1]
+                    .item()
+                    .catalog_item()
+                    .item_number()
+                    .value()
+            );
+        }
+
+        #[test]
+        fn it_should_calculate_the_percentages_on_the_whole_collection() {
+            let collection = new_collection();
+            let top = TopPurchases::from_collection(
+                &collection,
+                10,
+                &[Category::PassengerCars],
+            )
+            .unwrap();
+
+            assert!(top.is_empty());
+            assert_eq!(Decimal::new(104950, 2), top.total_value());
+        }
+    }
+
+    mod value_delta_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_calculate_the_value_delta_for_items() {
+            let collection = new_collection();
+            let item = collection.get(0).unwrap();
+
+            let appreciated = "250".parse::<Price>().unwrap();
+            assert_eq!(Some(Decimal::from(21)), item.value_delta(&appreciated));
+            assert_eq!(
+                Some(Decimal::new(917, 2)),
+                item.value_change(&appreciated)
+            );
+
+            let depreciated = "190,50".parse::<Price>().unwrap();
+            assert_eq!(
+                Some(Decimal::new(-3850, 2)),
+                item.value_delta(&depreciated)
+            );
+            assert_eq!(
+                Some(Decimal::new(-1681, 2)),
+                item.value_change(&depreciated)
+            );
+        }
+
+        #[test]
+        fn it_should_not_calculate_the_value_delta_without_a_price() {
+            let item = CollectionItem::new(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                PurchasedInfo::without_price(
+                    "Treni&Treni",
+                    NaiveDate::from_ymd_opt(2020, 1, 1).unwrap(),
+                ),
+            );
+
+            let reference = "100".parse::<Price>().unwrap();
+            assert_eq!(None, item.value_delta(&reference));
+            assert_eq!(None, item.value_change(&reference));
+        }
+
+        #[test]
+        fn it_should_not_calculate_the_value_delta_in_other_currencies() {
+            let collection = new_collection();
+            let item = collection.get(0).unwrap();
+
+            let reference = "250 USD".parse::<Price>().unwrap();
+            assert_eq!(None, item.value_delta(&reference));
+            assert_eq!(None, item.value_change(&reference));
+        }
+    }
+
+    mod tags_tests {
+        use super::*;
+
+        fn tagged_item(item_number: &str, tags: &[&str]) -> CollectionItem {
+            CollectionItem::new(
+                new_catalog_item("ACME", item_number, Scale::H0()),
+                new_purchased_info("2019-05-02", "229,00"),
+            )
+            .with_tags(tags.iter().map(|t| t.to_string()).collect())
+        }
+
+        #[test]
+        fn it_should_match_the_tags_ignoring_case() {
+            let item = tagged_item("60023", &["Weathered", "FS"]);
+            assert!(item.has_tag("weathered"));
+            assert!(item.has_tag("fs"));
+            assert!(!item.has_tag("digital"));
+        }
+
+        #[test]
+        fn it_should_count_the_items_for_each_tag() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_collection_item(tagged_item(
+                "60023",
+                &["Weathered", "weathered", "FS"],
+            ));
+            collection.add_collection_item(tagged_item("60024", &["fs"]));
+            collection.add_collection_item(tagged_item("60025", &[]));
+
+            let tags = collection.tags();
+            assert_eq!(2, tags.len());
+            assert_eq!(Some(&2), tags.get("fs"));
+            assert_eq!(Some(&1), tags.get("weathered"));
+        }
+
+        #[test]
+        fn it_should_keep_the_item_notes() {
+            let item = tagged_item("60023", &[]).with_notes("Missing the box");
+            assert_eq!(Some("Missing the box"), item.notes());
+        }
+    }
+
+    mod decoder_summary_tests {
+        use super::*;
+
+        fn new_card(
+            with_decoder: bool,
+            dcc_interface: Option<DccInterface>,
+        ) -> DepotCard {
+            DepotCard::new(
+                "E.656",
+                "E.656 210",
+                None,
+                None,
+                &Railway::new("FS"),
+                &Epoch::IV,
+                "ACME",
+                &ItemNumber::new("60023").unwrap(),
+                with_decoder,
+                dcc_interface,
+                None,
+                None,
+            )
+        }
+
+        #[test]
+        fn it_should_count_the_locomotives_to_equip_by_dcc_interface() {
+            let depot = Depot {
+                locomotives: vec![
+                    new_card(false, Some(DccInterface::Nem652)),
+                    new_card(false, Some(DccInterface::Nem652)),
+                    new_card(true, Some(DccInterface::Nem652)),
+                    new_card(false, Some(DccInterface::Plux22)),
+                    new_card(false, None),
+                ],
+            };
+
+            let summary = depot.decoder_summary();
+            assert_eq!(4, summary.total());
+            assert_eq!(1, summary.unknown_socket());
+            assert_eq!(
+                Some(&2),
+                summary.by_interface().get(&DccInterface::Nem652)
+            );
+            assert_eq!(
+                Some(&1),
+                summary.by_interface().get(&DccInterface::Plux22)
+            );
+        }
+
+        #[test]
+        fn it_should_list_the_decoders_to_buy_for_a_mixed_fleet() {
+            let depot = Depot {
+                locomotives: vec![
+                    new_card(false, Some(DccInterface::Nem652)),
+                    new_card(true, Some(DccInterface::Nem652)),
+                    new_card(false, Some(DccInterface::Plux22)),
+                    new_card(false, Some(DccInterface::Nem652)),
+                    new_card(true, None),
+                    new_card(false, None),
+                ],
+            };
+
+            let list = depot.decoder_shopping_list();
+            assert_eq!(4, list.total());
+            assert_eq!(2, list.by_interface().len());
+            assert_eq!(
+                Some(2),
+                list.by_interface()
+                    .get(&DccInterface::Nem652)
+                    .map(|it| it.len())
+            );
+            assert_eq!(
+                Some(1),
+                list.by_interface()
+                    .get(&DccInterface::Plux22)
+                    .map(|it| it.len())
+            );
+            assert_eq!(1, list.unknown_socket().len());
+            assert!(list
+                .by_interface()
+                .values()
+                .flatten()
+                .chain(list.unknown_socket())
+                .all(|it| !it.with_decoder()));
+        }
+
+        #[test]
+        fn it_should_list_no_decoders_when_every_locomotive_is_equipped() {
+            let depot = Depot {
+                locomotives: vec![new_card(true, Some(DccInterface::Nem652))],
+            };
+
+            assert_eq!(0, depot.decoder_shopping_list().total());
+        }
+    }
+
+    mod depot_filter_tests {
+        use super::*;
+
+        fn new_card(
+            railway: &str,
+            epoch: Epoch,
+            with_decoder: bool,
+        ) -> DepotCard {
+            DepotCard::new(
+                "E.656",
+                "E.656 210",
+                None,
+                None,
+                &Railway::new(railway),
+                &epoch,
+                "ACME",
+                &ItemNumber::new("60023").unwrap(),
+                with_decoder,
+                None,
+                None,
+                None,
+            )
+        }
+
+        #[test]
+        fn it_should_match_everything_with_an_empty_filter() {
+            let filter = DepotFilter::new();
+            assert!(filter.matches(&new_card("FS", Epoch::IV, true)));
+            assert!(filter.matches(&new_card("DB", Epoch::III, false)));
+        }
+
+        #[test]
+        fn it_should_compose_the_depot_filters() {
+            let filter = DepotFilter::new()
+                .with_railway("fs")
+                .with_epoch(Epoch::IV)
+                .with_decoder(true);
+
+            assert!(filter.matches(&new_card("FS", Epoch::IV, true)));
+            assert!(filter.matches(&new_card(
+                "FS",
+                Epoch::Multiple(vec![Epoch::IV, Epoch::V]),
+                true
+            )));
+            assert!(!filter.matches(&new_card("DB", Epoch::IV, true)));
+            assert!(!filter.matches(&new_card("FS", Epoch::III, true)));
+            assert!(!filter.matches(&new_card("FS", Epoch::IV, false)));
+        }
+
+        #[test]
+        fn it_should_filter_the_depot_cards() {
+            let mut depot = Depot {
+                locomotives: vec![
+                    new_card("FS", Epoch::IV, true),
+                    new_card("FS", Epoch::IV, false),
+                ],
+            };
+
+            depot.filter(&DepotFilter::new().with_decoder(false));
+            assert_eq!(1, depot.len());
+            assert!(!depot.locomotives()[0].with_decoder());
+
+            depot.filter(&DepotFilter::new().with_railway("DB"));
+            assert!(depot.is_empty());
+        }
+
+        #[test]
+        fn it_should_filter_the_depot_cards_by_dcc_interface() {
+            let card = DepotCard::new(
+                "D.141",
+                "D.141 1004",
+                None,
+                None,
+                &Railway::new("FS"),
+                &Epoch::V,
+                "Piko",
+                &ItemNumber::new("52444").unwrap(),
+                true,
+                Some(DccInterface::Plux22),
+                None,
+                None,
+            );
+
+            let filter =
+                DepotFilter::new().with_dcc_interface(DccInterface::Plux22);
+            assert!(filter.matches(&card));
+            assert!(!filter.matches(&new_card("FS", Epoch::V, true)));
+
+            let filter =
+                DepotFilter::new().with_dcc_interface(DccInterface::Next18);
+            assert!(!filter.matches(&card));
+        }
+
+        #[test]
+        fn it_should_empty_the_depot_without_the_dcc_interface() {
+            let mut depot = Depot {
+                locomotives: vec![
+                    new_card("FS", Epoch::IV, true),
+                    new_card("DB", Epoch::IV, false),
+                ],
+            };
+
+            depot.filter(
+                &DepotFilter::new().with_dcc_interface(DccInterface::Plux22),
+            );
+            assert!(depot.is_empty());
+        }
+    }
+
+    mod depot_with_trains_tests {
+        use super::*;
+        use crate::domain::catalog::categories::TrainType;
+
+        fn new_collection_with_train() -> Collection {
+            let railcar = RollingStockBuilder::train()
+                .with_type_name("ALn 668")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_train_type(TrainType::Railcars)
+                .with_control(Control::Dcc)
+                .build()
+                .unwrap();
+
+            let mut collection = new_collection();
+            collection.add_item(
+                CatalogItem::new(
+                    Brand::new("ACME"),
+                    ItemNumber::new("70123").unwrap(),
+                    String::from("Automotrice ALn 668"),
+                    vec![railcar],
+                    PowerMethod::DC,
+                    Scale::H0(),
+                    None,
+                    1,
+                ),
+                new_purchased_info("2022-02-02", "250"),
+            );
+            collection
+        }
+
+        #[test]
+        fn it_should_exclude_trains_from_the_depot_by_default() {
+            let depot = Depot::from_collection(&new_collection_with_train());
+            assert_eq!(4, depot.len());
+        }
+
+        #[test]
+        fn it_should_include_trains_in_the_depot() {
+            let depot = Depot::from_collection_with_trains(
+                &new_collection_with_train(),
+            );
+            assert_eq!(5, depot.len());
+
+            let card = depot
+                .locomotives()
+                .iter()
+                .find(|it| it.class_name() == "ALn 668")
+                .unwrap();
+            assert_eq!("", card.road_number());
+            assert!(card.with_decoder());
+        }
+
+        #[test]
+        fn it_should_include_dcc_equipped_multiple_units_in_the_depot() {
+            let emu = RollingStockBuilder::train()
+                .with_type_name("ETR 220")
+                .with_road_number("ETR 220 004")
+                .with_n_of_elements(4)
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_train_type(TrainType::ElectricMultipleUnits)
+                .with_control(Control::DccSound)
+                .with_dcc_interface(DccInterface::Next18)
+                .with_dcc_address(DccAddress::new(220).unwrap())
+                .build()
+                .unwrap();
+
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                CatalogItem::new(
+                    Brand::new("ACME"),
+                    ItemNumber::new("70220").unwrap(),
+                    String::from("Elettrotreno ETR 220"),
+                    vec![emu],
+                    PowerMethod::DC,
+                    Scale::H0(),
+                    None,
+                    1,
+                ),
+                new_purchased_info("2022-02-02", "450"),
+            );
+
+            assert!(Depot::from_collection(&collection)
+                .locomotives()
+                .is_empty());
+
+            let depot = Depot::from_collection_with_trains(&collection);
+            assert_eq!(1, depot.len());
+
+            let card = &depot.locomotives()[0];
+            assert_eq!("ETR 220", card.class_name());
+            assert_eq!("ETR 220 004", card.road_number());
+            assert!(card.with_decoder());
+            assert_eq!(Some(DccInterface::Next18), card.dcc_interface());
+            assert_eq!(DccAddress::new(220).ok(), card.dcc_address());
+        }
+
+        #[test]
+        fn it_should_exclude_the_train_elements_without_a_decoder() {
+            let power_car = RollingStockBuilder::train()
+                .with_type_name("ALe 601")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_train_type(TrainType::ElectricMultipleUnits)
+                .with_control(Control::Dcc)
+                .build()
+                .unwrap();
+            let coach = RollingStockBuilder::train()
+                .with_type_name("Le 601")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_train_type(TrainType::ElectricMultipleUnits)
+                .build()
+                .unwrap();
+
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                CatalogItem::new(
+                    Brand::new("ACME"),
+                    ItemNumber::new("70601").unwrap(),
+                    String::from("Elettromotrice ALe 601 con rimorchiata"),
+                    vec![power_car, coach],
+                    PowerMethod::DC,
+                    Scale::H0(),
+                    None,
+                    1,
+                ),
+                new_purchased_info("2022-02-02", "350"),
+            );
+
+            let mut depot = Depot::from_collection_with_trains(&collection);
+            depot.filter(&DepotFilter::new().with_decoder(true));
+            assert_eq!(1, depot.len());
+            assert_eq!("ALe 601", depot.locomotives()[0].class_name());
+
+            let depot = Depot::from_collection_with_trains(&collection);
+            assert_eq!(1, depot.len());
+        }
+    }
+
+    mod empty_collection_stats_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_calculate_zeroed_stats_for_empty_collections() {
+            let collection = Collection::create_empty("My collection");
+            assert!(collection.is_empty());
+
+            let stats = CollectionStats::from_collection(&collection).unwrap();
+            assert!(stats.is_empty());
+            assert_eq!(Decimal::ZERO, stats.total_value());
+            assert_eq!(0, stats.number_of_rolling_stocks());
+            assert!(stats.values_by_year().is_empty());
+            assert_eq!(None, stats.average_price());
+            assert_eq!(None, stats.median_price());
+        }
+
+        #[test]
+        fn it_should_calculate_empty_stats_when_every_item_is_sold() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_collection_item(
+                CollectionItem::new(
+                    new_catalog_item("ACME", "60023", Scale::H0()),
+                    new_purchased_info("2019-05-02", "229,00"),
+                )
+                .with_sold_info(SoldInfo::without_price(
+                    NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+                )),
+            );
+            assert!(!collection.is_empty());
+
+            let stats = CollectionStats::from_collection(&collection).unwrap();
+            assert!(stats.is_empty());
+            let history =
+                CollectionStats::from_purchase_history(&collection).unwrap();
+            assert!(!history.is_empty());
+        }
+    }
+
+    mod category_totals_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_count_the_rolling_stocks_by_category() {
+            let count = CollectionCount::from_collection(&new_collection());
+
+            assert_eq!(4, count.number_of_items());
+            assert_eq!(4, count.number_of_rolling_stocks());
+            assert_eq!(
+                &[
+                    (Category::Locomotives, 4),
+                    (Category::Trains, 0),
+                    (Category::FreightCars, 0),
+                    (Category::PassengerCars, 0),
+                ],
+                count.by_category()
+            );
+        }
+
+        #[test]
+        fn it_should_count_the_items_with_prices_in_different_currencies() {
+            let mut collection = new_collection();
+            collection.add_item(
+                new_catalog_item("Roco", "62346", Scale::H0()),
+                new_purchased_info("2020-03-10", "150 USD"),
+            );
+            assert!(CollectionStats::from_collection(&collection).is_err());
+
+            let count = CollectionCount::from_collection(&collection);
+            assert_eq!(5, count.number_of_items());
+            assert_eq!(5, count.number_of_rolling_stocks());
+        }
+    }
+
+    mod price_stats_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_calculate_the_price_stats() {
+            let stats =
+                CollectionStats::from_collection(&new_collection()).unwrap();
+
+            assert_eq!(Some(Decimal::new(26238, 2)), stats.average_price());
+            assert_eq!(Some(Decimal::from(120)), stats.min_price());
+            assert_eq!(Some(Decimal::from(550)), stats.max_price());
+        }
+
+        #[test]
+        fn it_should_calculate_the_median_price_for_even_items() {
+            let stats =
+                CollectionStats::from_collection(&new_collection()).unwrap();
+
+            // (150,50 + 229) / 2
+            assert_eq!(Some(Decimal::new(18975, 2)), stats.median_price());
+        }
+
+        #[test]
+        fn it_should_calculate_the_median_price_for_odd_items() {
+            let mut collection = new_collection();
+            collection.add_item(
+                new_catalog_item("Roco", "62346", Scale::H0()),
+                new_purchased_info("2022-01-10", "30"),
+            );
+            let stats = CollectionStats::from_collection(&collection).unwrap();
+
+            assert_eq!(Some(Decimal::new(15050, 2)), stats.median_price());
+        }
+
+        #[test]
+        fn it_should_not_calculate_the_price_stats_without_prices() {
+            let collection = Collection::create_empty("My collection");
+            let stats = CollectionStats::from_collection(&collection).unwrap();
+
+            assert_eq!(None, stats.average_price());
+            assert_eq!(None, stats.median_price());
+            assert_eq!(None, stats.min_price());
+            assert_eq!(None, stats.max_price());
+        }
+
+        #[test]
+        fn it_should_calculate_the_price_distribution() {
+            let mut collection = new_collection();
+            collection.add_item(
+                new_catalog_item("Roco", "62346", Scale::H0()),
+                new_purchased_info("2022-01-10", "50"),
+            );
+            let stats = CollectionStats::from_collection(&collection).unwrap();
+            let distribution = stats.price_distribution();
+
+            let counts: Vec<usize> = distribution
+                .buckets()
+                .iter()
+                .map(|it| it.number_of_items())
+                .collect();
+            assert_eq!(vec![0, 1, 2, 1, 1], counts);
+
+            let labels: Vec<String> = distribution
+                .buckets()
+                .iter()
+                .map(|it| it.to_string())
+                .collect();
+            assert_eq!(
+                vec!["< 50", "50-100", "100-200", "200-500", ">= 500"],
+                labels
+            );
+            assert_eq!(
+                Decimal::from(40),
+                distribution.percentage(&distribution.buckets()[2])
+            );
+        }
+
+        #[test]
+        fn it_should_scale_the_price_distribution_to_the_currency() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                new_catalog_item("Kato", "3060", Scale::N()),
+                new_purchased_info("2022-01-10", "12000 JPY"),
+            );
+            let stats = CollectionStats::from_collection(&collection).unwrap();
+            let distribution = stats.price_distribution();
+
+            assert_eq!(Currency::JPY, distribution.currency());
+            assert_eq!(1, distribution.buckets()[2].number_of_items());
+            assert_eq!("10000-20000", distribution.buckets()[2].to_string());
+        }
+    }
+
+    mod total_length_tests {
+        use super::*;
+        use crate::domain::collecting::fixtures::new_catalog_item_with_count;
+
+        fn new_locomotive_with_length(length: u32) -> RollingStock {
+            new_locomotive_builder("E.656 210")
+                .with_length_over_buffer(LengthOverBuffer::new(length))
+                .build()
+                .unwrap()
+        }
+
+        #[test]
+        fn it_should_sum_the_rolling_stocks_length() {
+            let mut collection = new_collection();
+            collection.add_item(
+                new_catalog_item_with_count(
+                    "70001",
+                    vec![
+                        new_locomotive_with_length(210),
+                        new_locomotive_with_length(303),
+                    ],
+                    1,
+                ),
+                new_purchased_info("2022-01-10", "300"),
+            );
+            collection.add_item(
+                new_catalog_item_with_count(
+                    "70002",
+                    vec![new_locomotive_with_length(195)],
+                    2,
+                ),
+                new_purchased_info("2022-01-10", "200"),
+            );
+
+            assert_eq!(903, collection.total_length_mm());
+            assert_eq!(
+                903,
+                CollectionStats::from_collection(&collection)
+                    .unwrap()
+                    .total_length_mm()
+            );
+        }
+
+        #[test]
+        fn it_should_treat_missing_lengths_as_zero() {
+            assert_eq!(0, new_collection().total_length_mm());
+        }
+
+        #[test]
+        fn it_should_find_the_average_and_the_longest_length() {
+            let mut collection = new_collection();
+            collection.add_item(
+                new_catalog_item_with_count(
+                    "70001",
+                    vec![new_locomotive_with_length(303)],
+                    1,
+                ),
+                new_purchased_info("2022-01-10", "300"),
+            );
+            collection.add_item(
+                new_catalog_item_with_count(
+                    "70002",
+                    vec![new_locomotive_with_length(195)],
+                    2,
+                ),
+                new_purchased_info("2022-01-10", "200"),
+            );
+
+            let stats = CollectionStats::from_collection(&collection).unwrap();
+            assert_eq!(Some(Decimal::from(231)), stats.average_length_mm());
+            assert_eq!(
+                Some(("My catalog item", LengthOverBuffer::new(303))),
+                stats.longest_item()
+            );
+        }
+
+        #[test]
+        fn it_should_have_no_average_without_lengths() {
+            let stats =
+                CollectionStats::from_collection(&new_collection()).unwrap();
+            assert_eq!(None, stats.average_length_mm());
+            assert_eq!(None, stats.longest_item());
+        }
+    }
+
+    mod roster_tests {
+        use super::*;
+        use crate::domain::catalog::categories::PassengerCarType;
+
+        fn new_roster_collection() -> Collection {
+            let coach = RollingStockBuilder::passenger_car()
+                .with_type_name("UIC-Z")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_passenger_car_type(PassengerCarType::OpenCoach)
+                .with_livery("bandiera")
+                .with_length_over_buffer(LengthOverBuffer::new(303))
+                .build()
+                .unwrap();
+            let locomotive = RollingStockBuilder::locomotive()
+                .with_class_name("Re 460")
+                .with_road_number("Re 460 005")
+                .with_railway(Railway::new("SBB"))
+                .with_epoch(Epoch::V)
+                .with_locomotive_type(LocomotiveType::ElectricLocomotive)
+                .build()
+                .unwrap();
+
+            let mut collection = new_collection();
+            collection.add_item(
+                CatalogItem::new(
+                    Brand::new("Roco"),
+                    ItemNumber::new("45123").unwrap(),
+                    String::from("Carrozza UIC-Z"),
+                    vec![coach],
+                    PowerMethod::DC,
+                    Scale::H0(),
+                    None,
+                    1,
+                ),
+                new_purchased_info("2022-02-02", "45"),
+            );
+            collection.add_item(
+                CatalogItem::new(
+                    Brand::new("Roco"),
+                    ItemNumber::new("73460").unwrap(),
+                    String::from("Lokomotive Re 460"),
+                    vec![locomotive],
+                    PowerMethod::AC,
+                    Scale::H0(),
+                    None,
+                    1,
+                ),
+                new_purchased_info("2022-03-02", "250"),
+            );
+            collection
+        }
+
+        #[test]
+        fn it_should_list_every_rolling_stock_sorted_by_railway_and_name() {
+            let collection = new_roster_collection();
+            let roster = Roster::from_collection(&collection);
+
+            let names: Vec<(&str, &str)> = roster
+                .rolling_stocks()
+                .iter()
+                .map(|rs| (rs.railway().name(), Roster::name(rs)))
+                .collect();
+            assert_eq!(
+                vec![
+                    ("FS", "E.656"),
+                    ("FS", "E.656"),
+                    ("FS", "E.656"),
+                    ("FS", "E.656"),
+                    ("FS", "UIC-Z"),
+                    ("SBB", "Re 460"),
+                ],
+                names
+            );
+        }
+
+        #[test]
+        fn it_should_filter_the_roster_by_category() {
+            let collection = new_roster_collection();
+            let mut roster = Roster::from_collection(&collection);
+            roster.filter_by_category(&[Category::PassengerCars]);
+
+            assert_eq!(1, roster.len());
+            let coach = roster.rolling_stocks()[0];
+            assert_eq!(Some("UIC-Z"), coach.type_name());
+            assert_eq!(
+                Some(303),
+                coach.length_over_buffer().map(|l| l.value())
+            );
+        }
+
+        #[test]
+        fn it_should_keep_every_rolling_stock_without_categories() {
+            let collection = new_roster_collection();
+            let mut roster = Roster::from_collection(&collection);
+            roster.filter_by_category(&[]);
+            assert_eq!(6, roster.len());
+        }
+    }
+
+    mod collection_stats_by_scale_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_group_the_collection_stats_by_scale() {
+            let stats =
+                CollectionStatsByScale::from_collection(&new_collection())
+                    .unwrap();
+
+            let values = stats.values();
+            assert_eq!(3, values.len());
+            assert_eq!(&Scale::H0(), values[0].scale());
+            assert_eq!(2, values[0].number_of_items());
+            assert_eq!(Decimal::new(37950, 2), values[0].total_value());
+        }
+
+        #[test]
+        fn it_should_sort_the_collection_stats_by_scale_ratio() {
+            let mut stats =
+                CollectionStatsByScale::from_collection(&new_collection())
+                    .unwrap();
+            stats.sort_by_ratio();
+
+            let scales: Vec<&str> =
+                stats.values().iter().map(|it| it.scale().name()).collect();
+            assert_eq!(vec!["O", "H0", "N"], scales);
+        }
+    }
+
+    mod keep_top_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_collapse_the_other_brands() {
+            let mut stats =
+                CollectionStatsByBrand::from_collection(&new_collection())
+                    .unwrap();
+            assert_eq!(4, stats.values().len());
+
+            stats.keep_top(2);
+
+            let brands: Vec<&str> =
+                stats.values().iter().map(|it| it.brand()).collect();
+            assert_eq!(vec!["ACME", "Lenz"], brands);
+
+            let others = stats.others().unwrap();
+            assert_eq!(2, others.number_of_groups());
+            assert_eq!(2, others.number_of_items());
+            assert_eq!(Decimal::new(27050, 2), others.total_value());
+        }
+
+        #[test]
+        fn it_should_keep_every_group_when_they_are_not_more_than_n() {
+            let mut stats =
+                CollectionStatsByScale::from_collection(&new_collection())
+                    .unwrap();
+            stats.keep_top(3);
+
+            assert_eq!(3, stats.values().len());
+            assert_eq!(None, stats.others());
+        }
+
+        #[test]
+        fn it_should_collapse_the_other_scales() {
+            let mut stats =
+                CollectionStatsByScale::from_collection(&new_collection())
+                    .unwrap();
+            stats.keep_top(1);
+
+            assert_eq!(Scale::O(), *stats.values()[0].scale());
+            assert_eq!(
+                Decimal::new(49950, 2),
+                stats.others().unwrap().total_value()
+            );
+        }
+    }
+
+    mod collection_stats_by_railway_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_count_multiple_railways_toward_each_of_them() {
+            let coach = RollingStockBuilder::passenger_car()
+                .with_type_name("Corail")
+                .with_road_number("61 87 20-70 100-2")
+                .with_railway("DB/SNCF".parse::<Railway>().unwrap())
+                .with_epoch(Epoch::V)
+                .build()
+                .unwrap();
+
+            let mut collection = new_collection();
+            collection.add_item(
+                CatalogItem::new(
+                    Brand::new("Roco"),
+                    ItemNumber::new("74561").unwrap(),
+                    String::from("Carrozza DB/SNCF"),
+                    vec![coach],
+                    PowerMethod::DC,
+                    Scale::H0(),
+                    None,
+                    1,
+                ),
+                new_purchased_info("2022-03-03", "60"),
+            );
+
+            let stats =
+                CollectionStatsByRailway::from_collection(&collection).unwrap();
+
+            let values = stats.values();
+            let railways: Vec<&str> =
+                values.iter().map(|it| it.railway()).collect();
+            assert_eq!(vec!["DB", "FS", "SNCF"], railways);
+
+            assert_eq!(1, values[0].number_of_items());
+            assert_eq!(Decimal::from(60), values[0].total_value());
+            assert_eq!(1, values[2].number_of_items());
+            assert_eq!(Decimal::from(60), values[2].total_value());
+        }
+
+        #[test]
+        fn it_should_group_the_same_railway_written_in_different_ways() {
+            let locomotive = RollingStockBuilder::locomotive()
+                .with_class_name("E.444")
+                .with_road_number("E.444 005")
+                .with_railway(
+                    "Ferrovie dello Stato".parse::<Railway>().unwrap(),
+                )
+                .with_epoch(Epoch::IV)
+                .with_locomotive_type(LocomotiveType::ElectricLocomotive)
+                .build()
+                .unwrap();
+
+            let mut collection = new_collection();
+            collection.add_item(
+                CatalogItem::new(
+                    Brand::new("Rivarossi"),
+                    ItemNumber::new("HR2345").unwrap(),
+                    String::from("Locomotiva elettrica E.444"),
+                    vec![locomotive],
+                    PowerMethod::DC,
+                    Scale::H0(),
+                    None,
+                    1,
+                ),
+                new_purchased_info("2022-03-03", "100"),
+            );
+
+            let stats =
+                CollectionStatsByRailway::from_collection(&collection).unwrap();
+
+            let values = stats.values();
+            assert_eq!(1, values.len());
+            assert_eq!("FS", values[0].railway());
+            assert_eq!(5, values[0].number_of_items());
+        }
+    }
+
+    mod find_duplicates_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_find_the_duplicated_catalog_items() {
+            let mut collection = new_collection();
+            collection.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2021-01-01", "229,00"),
+            );
+            collection.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2022-01-01", "229,00"),
+            );
+
+            let duplicates = collection.find_duplicates();
+            assert_eq!(1, duplicates.len());
+
+            let (catalog_item, count) = duplicates[0];
+            assert_eq!("60023", catalog_item.item_number().value());
+            assert_eq!(3, count);
+        }
+
+        #[test]
+        fn it_should_find_no_duplicates() {
+            assert!(new_collection().find_duplicates().is_empty());
+        }
+
+        #[test]
+        fn it_should_group_the_duplicated_items() {
+            let mut collection = new_collection();
+            collection.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2021-01-01", "199,00"),
+            );
+
+            let groups = collection.duplicated_items();
+            assert_eq!(1, groups.len());
+            assert_eq!(2, groups[0].len());
+
+            let prices: Vec<String> = groups[0]
+                .iter()
+                .map(|it| it.purchased_info().price().unwrap().to_string())
+                .collect();
+            assert_eq!(vec!["229.00 EUR", "199.00 EUR"], prices);
+            assert!(new_collection().duplicated_items().is_empty());
+        }
+
+        fn new_catalog_item_with_locomotive(
+            brand: &str,
+            item_number: &str,
+            road_number: &str,
+        ) -> CatalogItem {
+            CatalogItem::new(
+                Brand::new(brand),
+                ItemNumber::new(item_number).unwrap(),
+                String::from("My catalog item"),
+                vec![new_locomotive(road_number)],
+                PowerMethod::DC,
+                Scale::H0(),
+                None,
+                1,
+            )
+        }
+
+        #[test]
+        fn it_should_find_the_locomotives_with_the_same_road_number() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                new_catalog_item_with_locomotive("ACME", "60023", "E.656 210"),
+                new_purchased_info("2019-05-02", "229,00"),
+            );
+            collection.add_item(
+                new_catalog_item_with_locomotive("ACME", "60456", "E.656 210"),
+                new_purchased_info("2021-01-01", "249,00"),
+            );
+            collection.add_item(
+                new_catalog_item_with_locomotive("ACME", "60024", "E.656 211"),
+                new_purchased_info("2021-01-01", "229,00"),
+            );
+
+            assert!(collection.duplicated_items().is_empty());
+
+            let duplicates = collection.duplicated_locomotives();
+            assert_eq!(1, duplicates.len());
+            assert_eq!("E.656", duplicates[0].class_name());
+            assert_eq!("E.656 210", duplicates[0].road_number());
+
+            let item_numbers: Vec<&str> = duplicates[0]
+                .items()
+                .iter()
+                .map(|it| it.catalog_item().item_number().value())
+                .collect();
+            assert_eq!(vec!["60023", "60456"], item_numbers);
+        }
+
+        #[test]
+        fn it_should_find_the_catalog_items_in_the_collection() {
+            let collection = new_collection();
+
+            let item = new_catalog_item("acme", "60023", Scale::H0());
+            assert!(collection.contains_catalog_item(&item));
+            assert_eq!(
+                "229.00 EUR",
+                collection
+                    .find_catalog_item(&item)
+                    .and_then(|it| it.purchased_info().price())
+                    .unwrap()
+                    .to_string()
+            );
+            assert!(!collection.contains_catalog_item(&new_catalog_item(
+                "ACME",
+                "60024",
+                Scale::H0()
+            )));
+        }
+
+        #[test]
+        fn it_should_find_the_locomotives_by_road_number() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                new_catalog_item_with_locomotive("ACME", "60023", "E.656 210"),
+                new_purchased_info("2019-05-02", "229,00"),
+            );
+
+            let (item, rs) = collection
+                .find_rolling_stock_by_road_number("e.656", " E.656 210")
+                .unwrap();
+            assert_eq!("60023", item.catalog_item().item_number().value());
+            assert_eq!(Some("E.656 210"), rs.road_number());
+            assert!(collection
+                .find_rolling_stock_by_road_number("E.656", "E.656 211")
+                .is_none());
+            assert!(collection
+                .find_rolling_stock_by_road_number("E.655", "E.656 210")
+                .is_none());
+        }
+    }
+
+    mod search_tests {
+        use super::*;
+
+        fn new_passenger_car(livery: &str) -> RollingStock {
+            RollingStockBuilder::passenger_car()
+                .with_type_name("UIC-Z")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_livery(livery)
+                .build()
+                .unwrap()
+        }
+
+        #[test]
+        fn it_should_search_the_catalog_item_fields() {
+            let collection = new_collection();
+
+            let matches = collection.search("fleisch");
+            assert_eq!(1, matches.len());
+            assert_eq!("brand", matches[0].field());
+
+            let matches = collection.search("62345");
+            assert_eq!(1, matches.len());
+            assert_eq!("item number", matches[0].field());
+        }
+
+        #[test]
+        fn it_should_search_the_rolling_stock_fields_ignoring_case() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2019-05-02", "229,00"),
+            );
+            collection.add_item(
+                CatalogItem::new(
+                    Brand::new("Roco"),
+                    ItemNumber::new("45123").unwrap(),
+                    String::from("Carrozza"),
+                    vec![new_passenger_car("Bandiera")],
+                    PowerMethod::DC,
+                    Scale::H0(),
+                    None,
+                    1,
+                ),
+                new_purchased_info("2020-01-10", "45,50"),
+            );
+
+            let matches = collection.search("e.656 210");
+            assert_eq!(1, matches.len());
+            assert_eq!("road number", matches[0].field());
+            assert_eq!(
+                "60023",
+                matches[0].item().catalog_item().item_number().value()
+            );
+
+            let matches = collection.search("bandiera");
+            assert_eq!(1, matches.len());
+            assert_eq!("livery", matches[0].field());
+
+            assert!(collection.search("not found").is_empty());
+        }
+    }
+
+    mod spending_timeline_tests {
+        use super::*;
+
+        fn periods(timeline: &SpendingTimeline) -> Vec<String> {
+            timeline.entries().iter().map(|it| it.period()).collect()
+        }
+
+        #[test]
+        fn it_should_sum_the_purchases_by_year() {
+            let timeline = SpendingTimeline::from_collection(
+                &new_collection(),
+                false,
+                None,
+                None,
+            )
+            .unwrap();
+
+            assert_eq!(vec!["2019", "2020", "2021"], periods(&timeline));
+
+            let entry = &timeline.entries()[1];
+            assert_eq!(2, entry.number_of_items());
+            assert_eq!(Decimal::new(27050, 2), entry.amount_spent());
+            assert_eq!(Decimal::new(49950, 2), entry.cumulative_total());
+            assert_eq!(Some(Decimal::new(13525, 2)), entry.average_price());
+        }
+
+        #[test]
+        fn it_should_average_the_price_by_catalog_item() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                CatalogItem::new(
+                    Brand::new("ACME"),
+                    ItemNumber::new("55001").unwrap(),
+                    String::from("My set"),
+                    vec![new_locomotive("E.656 210")],
+                    PowerMethod::DC,
+                    Scale::H0(),
+                    None,
+                    3,
+                ),
+                new_purchased_info("2020-03-01", "300"),
+            );
+
+            let timeline = SpendingTimeline::from_collection(
+                &collection,
+                false,
+                None,
+                None,
+            )
+            .unwrap();
+            let entry = &timeline.entries()[0];
+            assert_eq!(1, entry.number_of_items());
+            assert_eq!(Some(Decimal::from(300)), entry.average_price());
+        }
+
+        #[test]
+        fn it_should_include_the_years_without_purchases() {
+            let timeline = SpendingTimeline::from_collection(
+                &new_collection(),
+                false,
+                Some(2018),
+                Some(2022),
+            )
+            .unwrap();
+
+            assert_eq!(
+                vec!["2018", "2019", "2020", "2021", "2022"],
+                periods(&timeline)
+            );
+            let empty = &timeline.entries()[0];
+            assert_eq!(0, empty.number_of_items());
+            assert_eq!(None, empty.average_price());
+            assert_eq!(
+                Decimal::new(104950, 2),
+                timeline.entries()[4].cumulative_total()
+            );
+        }
+
+        #[test]
+        fn it_should_fill_the_months_without_purchases() {
+            let timeline = SpendingTimeline::from_collection(
+                &new_collection(),
+                true,
+                Some(2020),
+                Some(2020),
+            )
+            .unwrap();
+
+            assert_eq!(12, timeline.entries().len());
+            assert_eq!("2020-01", timeline.entries()[0].period());
+            assert_eq!(0, timeline.entries()[1].number_of_items());
+            assert_eq!(
+                Decimal::new(49950, 2),
+                timeline.entries()[11].cumulative_total()
+            );
+        }
+
+        #[test]
+        fn it_should_start_the_cumulative_total_before_the_lower_bound() {
+            let timeline = SpendingTimeline::from_collection(
+                &new_collection(),
+                false,
+                Some(2021),
+                None,
+            )
+            .unwrap();
+
+            assert_eq!(vec!["2021"], periods(&timeline));
+            assert_eq!(
+                Decimal::new(104950, 2),
+                timeline.entries()[0].cumulative_total()
+            );
+        }
+    }
+
+    mod purchases_by_month_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_group_the_purchases_by_year_and_month() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2019-05-02", "229,00"),
+            );
+            collection.add_item(
+                new_catalog_item("Roco", "45123", Scale::H0()),
+                new_purchased_info("2019-05-20", "45,50"),
+            );
+            collection.add_item(
+                new_catalog_item("Piko", "52444", Scale::H0()),
+                new_purchased_info("2021-12-01", "150,00"),
+            );
+
+            let purchases =
+                PurchasesByMonth::from_collection(&collection).unwrap();
+            assert_eq!(vec![2019, 2021], purchases.years());
+
+            let may = purchases.get(2019, 5).unwrap();
+            assert_eq!(2, may.number_of_items());
+            assert_eq!(Decimal::new(27450, 2), may.total_value());
+            assert_eq!(None, purchases.get(2019, 6));
+
+            assert_eq!(2, purchases.max_number_of_items());
+            assert_eq!(Decimal::new(27450, 2), purchases.max_total_value());
+        }
+    }
+
+    mod merge_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_merge_collections() {
+            let mut collection = new_collection();
+
+            let mut other = Collection::create_empty("Other collection");
+            other.set_modified(3, other.modified_date);
+            other.add_item(
+                new_catalog_item("Piko", "52444", Scale::H0()),
+                new_purchased_info("2022-01-01", "150"),
+            );
+
+            let report = collection.merge(other);
+            assert_eq!(5, collection.len());
+            assert_eq!(3, collection.version());
+            assert!(report.duplicates().is_empty());
+            assert!(report.collisions().is_empty());
+        }
+
+        #[test]
+        fn it_should_keep_the_latest_modified_date() {
+            let date = |value: &str| {
+                NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S")
+                    .unwrap()
+            };
+            let mut collection = Collection::create_empty("2022");
+            collection.set_modified(2, date("2023-01-10 10:00:00"));
+            let mut other = Collection::create_empty("2021");
+            other.set_modified(1, date("2022-01-05 10:00:00"));
+
+            collection.merge(other);
+            assert_eq!(
+                &date("2023-01-10 10:00:00"),
+                collection.modified_date()
+            );
+            assert_eq!(2, collection.version());
+
+            let mut other = Collection::create_empty("2023");
+            other.set_modified(1, date("2024-02-01 10:00:00"));
+
+            collection.merge(other);
+            assert_eq!(
+                &date("2024-02-01 10:00:00"),
+                collection.modified_date()
+            );
+        }
+
+        #[test]
+        fn it_should_tell_duplicates_from_item_number_collisions() {
+            let mut collection = new_collection();
+
+            let mut other = Collection::create_empty("Other collection");
+            // a genuine duplicate: same brand and item number
+            other.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2022-01-01", "229,00"),
+            );
+            // a different brand reusing the same item number
+            other.add_item(
+                new_catalog_item("Rivarossi", "60023", Scale::H0()),
+                new_purchased_info("2022-01-01", "199,00"),
+            );
+
+            let report = collection.merge(other);
+            assert_eq!(6, collection.len());
+
+            assert_eq!(1, report.duplicates().len());
+            assert!(!report.duplicates_skipped());
+            assert_eq!("ACME", report.duplicates()[0].brand().name());
+
+            assert_eq!(1, report.collisions().len());
+            let collision = &report.collisions()[0];
+            assert_eq!("60023", collision.item_number().value());
+            assert_eq!("ACME", collision.brand().name());
+            assert_eq!("Rivarossi", collision.other_brand().name());
+        }
+    }
+
+    mod merge_rejecting_duplicates_tests {
+        use super::*;
+
+        fn new_collection_with_repeated_item() -> Collection {
+            let mut collection = Collection::create_empty("Other collection");
+            collection.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2022-01-01", "229,00"),
+            );
+            collection.add_item(
+                new_catalog_item("Piko", "52444", Scale::H0()),
+                new_purchased_info("2022-01-01", "150"),
+            );
+            collection.add_item(
+                new_catalog_item("Piko", "52444", Scale::H0()),
+                new_purchased_info("2022-03-01", "150"),
+            );
+            collection
+        }
+
+        #[test]
+        fn it_should_keep_the_repeated_items_within_one_collection() {
+            let mut collection = new_collection();
+
+            let report = collection.merge(new_collection_with_repeated_item());
+            assert_eq!(7, collection.len());
+            assert_eq!(1, report.duplicates().len());
+            assert_eq!("60023", report.duplicates()[0].item_number().value());
+        }
+
+        #[test]
+        fn it_should_skip_only_the_items_already_in_the_collection() {
+            let mut collection = new_collection();
+
+            let report =
+                collection.merge_rejecting_duplicates(
+                    new_collection_with_repeated_item(),
+                );
+            assert_eq!(6, collection.len());
+            assert!(report.duplicates_skipped());
+            assert_eq!(1, report.duplicates().len());
+        }
+
+        #[test]
+        fn it_should_sum_the_stats_for_collections_with_repeated_items() {
+            let parts = [new_collection(), new_collection_with_repeated_item()];
+            let sizes: usize = parts.iter().map(|it| it.len()).sum();
+
+            let [mut merged, other] = parts;
+            merged.merge(other);
+            let stats = CollectionStats::from_collection(&merged).unwrap();
+            assert_eq!(sizes, stats.size());
+        }
+    }
+
+    mod collection_diff_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_find_no_differences_for_the_same_collection() {
+            let old = new_collection();
+            let new = new_collection();
+
+            let diff = CollectionDiff::between(&old, &new);
+            assert!(diff.is_empty());
+        }
+
+        #[test]
+        fn it_should_find_the_added_and_removed_items() {
+            let old = new_collection();
+            let mut new = Collection::create_empty("My collection");
+            new.add_item(
+                new_catalog_item("acme", "60023", Scale::H0()),
+                new_purchased_info("2019-05-02", "229,00"),
+            );
+            new.add_item(
+                new_catalog_item("Piko", "52444", Scale::H0()),
+                new_purchased_info("2022-01-01", "150"),
+            );
+
+            let diff = CollectionDiff::between(&old, &new);
+
+            let item_numbers = |items: &[&CollectionItem]| {
+                items
+                    .iter()
+                    .map(|it| {
+                        it.catalog_item().item_number().value().to_owned()
+                    })
+                    .collect::<Vec<String>>()
+            };
+            assert_eq!(vec!["52444"], item_numbers(diff.added()));
+            assert_eq!(
+                vec!["7360", "62345", "40190"],
+                item_numbers(diff.removed())
+            );
+            assert!(diff.changed().is_empty());
+        }
+
+        #[test]
+        fn it_should_find_the_changed_items() {
+            let old = new_collection();
+            let mut new = Collection::create_empty("My collection");
+            new.add_collection_item(
+                CollectionItem::new(
+                    new_catalog_item("ACME", "60023", Scale::H0()),
+                    PurchasedInfo::new(
+                        "Tecnomodel",
+                        NaiveDate::from_ymd_opt(2019, 5, 2).unwrap(),
+                        "199,00".parse().unwrap(),
+                    ),
+                )
+                .with_storage("Box 1"),
+            );
+            new.add_item(
+                new_catalog_item("Fleischmann", "7360", Scale::N()),
+                new_purchased_info("2020-01-10", "120"),
+            );
+
+            let diff = CollectionDiff::between(&old, &new);
+
+            assert_eq!(1, diff.changed().len());
+            let changes = diff.changed()[0].changes();
+            assert_eq!(
+                vec!["price", "shop", "storage"],
+                changes.iter().map(|it| it.field()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                "price: 229.00 EUR -> 199.00 EUR",
+                changes[0].to_string()
+            );
+            assert_eq!("-", changes[2].old_value());
+            assert_eq!("Box 1", changes[2].new_value());
+        }
+
+        #[test]
+        fn it_should_find_the_changes_to_the_rolling_stocks_and_the_collector_values(
+        ) {
+            let old = CollectionItem::new(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2019-05-02", "229,00"),
+            );
+            let new = CollectionItem::new(
+                CatalogItem::new(
+                    Brand::new("ACME"),
+                    ItemNumber::new("60023").unwrap(),
+                    String::from("My catalog item"),
+                    vec![new_locomotive("E.656 001")],
+                    PowerMethod::DC,
+                    Scale::H0(),
+                    None,
+                    1,
+                ),
+                new_purchased_info("2019-05-02", "229"),
+            )
+            .with_notes("Repainted")
+            .with_tags(vec![String::from("fs")])
+            .with_sold_info(
+                SoldInfo::new(
+                    NaiveDate::from_ymd_opt(2023, 3, 1).unwrap(),
+                    "250".parse().unwrap(),
+                )
+                .with_buyer("John"),
+            );
+
+            let changes = old.changes_to(&new);
+            assert_eq!(
+                vec![
+                    "road numbers",
+                    "notes",
+                    "tags",
+                    "sold date",
+                    "sold price",
+                    "buyer"
+                ],
+                changes.iter().map(|it| it.field()).collect::<Vec<_>>()
+            );
+            assert_eq!(
+                "road numbers: E.656 210 -> E.656 001",
+                changes[0].to_string()
+            );
+            assert_eq!("John", changes[5].new_value());
+        }
+
+        #[test]
+        fn it_should_compare_the_price_amounts_not_their_rounded_values() {
+            let old = CollectionItem::new(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2019-05-02", "199.99"),
+            );
+            let new = CollectionItem::new(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2019-05-02", "199,9940"),
+            );
+
+            let changes = old.changes_to(&new);
+            assert_eq!(1, changes.len());
+            assert_eq!("price", changes[0].field());
+        }
+
+        #[test]
+        fn it_should_match_the_same_catalog_item_in_order() {
+            let mut old = Collection::create_empty("My collection");
+            old.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2019-05-02", "229,00"),
+            );
+            let mut new = Collection::create_empty("My collection");
+            new.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2019-05-02", "229,00"),
+            );
+            new.add_item(
+                new_catalog_item("ACME", "60023", Scale::H0()),
+                new_purchased_info("2022-01-01", "250,00"),
+            );
+
+            let diff = CollectionDiff::between(&old, &new);
+            assert_eq!(1, diff.added().len());
+            assert!(diff.removed().is_empty());
+            assert!(diff.changed().is_empty());
+        }
+    }
+
+    mod warranty_tests {
+        use super::*;
+
+        fn as_of() -> NaiveDate {
+            NaiveDate::from_ymd_opt(2023, 6, 1).unwrap()
+        }
+
+        #[test]
+        fn it_should_calculate_the_warranty_expiry() {
+            let purchase = new_purchased_info("2022-01-15", "100")
+                .with_receipt("R-2022-001")
+                .with_warranty_months(24);
+
+            assert_eq!(Some("R-2022-001"), purchase.receipt());
+            assert_eq!(
+                NaiveDate::from_ymd_opt(2024, 1, 15),
+                purchase.warranty_expiry()
+            );
+            assert!(purchase.is_under_warranty(&as_of()));
+        }
+
+        #[test]
+        fn it_should_find_expired_warranties() {
+            let purchase = new_purchased_info("2021-01-15", "100")
+                .with_warranty_months(24);
+            assert!(!purchase.is_under_warranty(&as_of()));
+
+            let no_warranty = new_purchased_info("2023-05-01", "100");
+            assert_eq!(None, no_warranty.warranty_expiry());
+            assert!(!no_warranty.is_under_warranty(&as_of()));
+        }
+
+        #[test]
+        fn it_should_list_the_items_under_warranty_sorted_by_expiry() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                new_catalog_item("ACME", "60001", Scale::H0()),
+                new_purchased_info("2023-01-01", "100")
+                    .with_warranty_months(24),
+            );
+            collection.add_item(
+                new_catalog_item("ACME", "60002", Scale::H0()),
+                new_purchased_info("2020-01-01", "100")
+                    .with_warranty_months(24),
+            );
+            collection.add_item(
+                new_catalog_item("ACME", "60003", Scale::H0()),
+                new_purchased_info("2023-03-01", "100")
+                    .with_warranty_months(12),
+            );
+
+            let items: Vec<&str> = collection
+                .items_under_warranty(&as_of())
+                .iter()
+                .map(|it| it.catalog_item().item_number().value())
+                .collect();
+            assert_eq!(vec!["60003", "60001"], items);
+        }
+    }
+
+    mod depot_tests {
+        use super::*;
+
+        fn new_card(road_number: &str, dcc_address: u16) -> DepotCard {
+            DepotCard::new(
+                "E.656",
+                road_number,
+                None,
+                None,
+                &Railway::new("FS"),
+                &Epoch::IV,
+                "ACME",
+                &ItemNumber::new("60023").unwrap(),
+                true,
+                None,
+                DccAddress::new(dcc_address).ok(),
+                None,
+            )
+        }
+
+        #[test]
+        fn it_should_find_dcc_address_conflicts() {
+            let depot = Depot {
+                locomotives: vec![
+                    new_card("E.656 210", 656),
+                    new_card("E.656 291", 656),
+                    new_card("E.656 077", 77),
+                    new_card("E.656 077", 77),
+                ],
+            };
+
+            let conflicts = depot.address_conflicts();
+            assert_eq!(1, conflicts.len());
+
+            let (address, cards) = &conflicts[0];
+            assert_eq!(656, address.value());
+            assert_eq!(2, cards.len());
+        }
+
+        #[test]
+        fn it_should_remove_the_duplicated_cards() {
+            let mut depot = Depot {
+                locomotives: vec![
+                    new_card("E.656 291", 291),
+                    new_card("E.656 077", 77),
+                    new_card("E.656 291", 656),
+                    new_card("E.656 077", 78),
+                ],
+            };
+
+            assert_eq!(2, depot.dedup());
+
+            let cards: Vec<(&str, u16)> = depot
+                .locomotives()
+                .iter()
+                .map(|it| (it.road_number(), it.dcc_address().unwrap().value()))
+                .collect();
+            assert_eq!(vec![("E.656 077", 77), ("E.656 291", 291)], cards);
+        }
+
+        #[test]
+        fn it_should_find_no_conflicts_without_dcc_addresses() {
+            let depot = Depot {
+                locomotives: vec![
+                    new_card("E.656 210", 0),
+                    new_card("E.656 291", 0),
+                ],
+            };
+
+            assert!(depot.address_conflicts().is_empty());
+        }
     }
 }
@@ -0,0 +1,59 @@
+//! The test fixtures shared by the collecting modules.
+use chrono::NaiveDate;
+
+use crate::domain::catalog::{
+    brands::Brand,
+    catalog_items::{CatalogItem, ItemNumber, PowerMethod},
+    categories::FreightCarType,
+    railways::Railway,
+    rolling_stocks::{Epoch, RollingStock, RollingStockBuilder},
+    scales::Scale,
+};
+use crate::domain::collecting::{collections::PurchasedInfo, Price};
+
+/// Creates a new H0 catalog item from ACME, with one piece.
+pub(crate) fn new_catalog_item(
+    item_number: &str,
+    rolling_stocks: Vec<RollingStock>,
+) -> CatalogItem {
+    new_catalog_item_with_count(item_number, rolling_stocks, 1)
+}
+
+pub(crate) fn new_catalog_item_with_count(
+    item_number: &str,
+    rolling_stocks: Vec<RollingStock>,
+    count: u8,
+) -> CatalogItem {
+    CatalogItem::new(
+        Brand::new("ACME"),
+        ItemNumber::new(item_number).unwrap(),
+        String::from("My catalog item"),
+        rolling_stocks,
+        PowerMethod::DC,
+        Scale::H0(),
+        None,
+        count,
+    )
+}
+
+pub(crate) fn new_purchased_info(
+    shop: &str,
+    date: &str,
+    price: &str,
+) -> PurchasedInfo {
+    PurchasedInfo::new(
+        shop,
+        NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+        price.parse::<Price>().unwrap(),
+    )
+}
+
+pub(crate) fn new_freight_car() -> RollingStock {
+    RollingStockBuilder::freight_car()
+        .with_type_name("Gbhs")
+        .with_railway(Railway::new("FS"))
+        .with_epoch(Epoch::IV)
+        .with_freight_car_type(FreightCarType::SlidingWallBoxcars)
+        .build()
+        .unwrap()
+}
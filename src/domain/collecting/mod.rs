@@ -1,22 +1,291 @@
 pub mod collections;
+#[cfg(test)]
+pub(crate) mod fixtures;
+pub mod shops;
+pub mod valuations;
 pub mod wish_lists;
 
 use rust_decimal::prelude::*;
 use std::fmt;
+use std::ops;
 use std::str;
+use thiserror::Error;
+
+/// The currencies for the prices.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum Currency {
+    #[default]
+    EUR,
+    USD,
+    GBP,
+    CHF,
+    JPY,
+}
+
+impl Currency {
+    /// Returns the symbol for this currency
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Currency::EUR => "€",
+            Currency::USD => "$",
+            Currency::GBP => "£",
+            Currency::CHF => "CHF",
+            Currency::JPY => "¥",
+        }
+    }
+}
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+impl str::FromStr for Currency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "EUR" => Ok(Currency::EUR),
+            "USD" => Ok(Currency::USD),
+            "GBP" => Ok(Currency::GBP),
+            "CHF" => Ok(Currency::CHF),
+            "JPY" => Ok(Currency::JPY),
+            _ => Err(format!("Invalid currency: '{}' is not supported", s)),
+        }
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// A price, as an amount in a currency.
+///
+/// The prices are ordered by amount first, then by currency: the ordering
+/// does not convert the amounts, so it is only meaningful between prices in
+/// the same currency (ie 10 USD comes before 20 EUR).
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Default)]
 pub struct Price {
     amount: Decimal,
-    currency: String,
+    currency: Currency,
 }
 
 impl Price {
+    pub fn new(amount: Decimal, currency: Currency) -> Self {
+        Price { amount, currency }
+    }
+
     pub fn euro(amount: Decimal) -> Self {
-        Price {
-            amount,
-            currency: "EUR".to_owned(),
+        Price::new(amount, Currency::EUR)
+    }
+
+    /// Returns the zero price in the given currency
+    pub fn zero(currency: Currency) -> Self {
+        Price::new(Decimal::ZERO, currency)
+    }
+
+    /// Checks whether the price amount is zero
+    pub fn is_zero(&self) -> bool {
+        self.amount.is_zero()
+    }
+
+    /// Adds the two prices, failing when they are in different currencies
+    pub fn checked_add(&self, other: &Price) -> Result<Price, PriceError> {
+        if self.currency != other.currency {
+            return Err(PriceError::MixedCurrencies(
+                self.currency,
+                other.currency,
+            ));
+        }
+
+        Ok(Price::new(self.amount + other.amount, self.currency))
+    }
+
+    /// Subtracts the other price, failing when they are in different currencies
+    pub fn checked_sub(&self, other: &Price) -> Result<Price, PriceError> {
+        if self.currency != other.currency {
+            return Err(PriceError::MixedCurrencies(
+                self.currency,
+                other.currency,
+            ));
+        }
+
+        Ok(Price::new(self.amount - other.amount, self.currency))
+    }
+
+    /// Returns this price rounded to two decimals, with the midpoint values
+    /// rounded away from zero (ie 195.005 is 195.01).
+    ///
+    /// The amounts keep their full precision for the calculations, they are
+    /// rounded only to be displayed.
+    pub fn rounded(&self) -> Price {
+        let amount = self
+            .amount
+            .round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero);
+        Price::new(amount, self.currency)
+    }
+
+    /// The price amount
+    pub fn amount(&self) -> Decimal {
+        self.amount
+    }
+
+    /// The price currency
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// Sums the prices, failing when they are not all in the same currency.
+    /// No prices sum to zero euro.
+    pub fn try_sum<I>(prices: I) -> Result<Price, PriceError>
+    where
+        I: IntoIterator<Item = Price>,
+    {
+        let mut it = prices.into_iter();
+        let mut total = match it.next() {
+            Some(price) => price,
+            None => return Ok(Price::zero(Currency::EUR)),
+        };
+
+        for price in it {
+            total = total.checked_add(&price)?;
+        }
+
+        Ok(total)
+    }
+
+    /// Normalizes the amount to use `.` as the decimal separator and no
+    /// thousands separators (ie "1.234,56" and "1,234.56" are both "1234.56").
+    ///
+    /// The last separator is the decimal one, unless it appears more than
+    /// once (ie "1.234.567") or it is the only one and it is followed by a
+    /// group of three digits (ie "1.234", but not "0.995"); the other
+    /// separators are for the thousands, and they must be followed by groups
+    /// of exactly three digits.
+    /// Returns `None` when the separators are mixed up (ie "1.234,56,78") or
+    /// the thousands groups are not valid (ie "1.23.4").
+    fn normalize_amount(amount: &str) -> Option<String> {
+        let is_separator = |c: char| c == '.' || c == ',';
+        let separators: Vec<char> =
+            amount.chars().filter(|c| is_separator(*c)).collect();
+        let (integer, decimals) = match separators.split_last() {
+            None => (amount, None),
+            Some((last, [])) if !Price::is_thousands_group(amount, *last) => {
+                let (integer, decimals) = amount.split_once(*last)?;
+                (integer, Some(decimals))
+            }
+            Some((last, others)) if others.iter().all(|c| c == last) => {
+                (amount, None)
+            }
+            Some((last, others)) if others.iter().all(|c| c != last) => {
+                let (integer, decimals) = amount.rsplit_once(*last)?;
+                (integer, Some(decimals))
+            }
+            Some(_) => return None,
+        };
+
+        let groups: Vec<&str> = integer.split(is_separator).collect();
+        if let Some((first, others)) = groups.split_first() {
+            let first = first.trim_start_matches('-');
+            if !others.is_empty()
+                && (first.is_empty()
+                    || first.len() > 3
+                    || others.iter().any(|group| group.len() != 3))
+            {
+                return None;
+            }
         }
+
+        let mut normalized = groups.concat();
+        if let Some(decimals) = decimals {
+            normalized.push('.');
+            normalized.push_str(decimals);
+        }
+        Some(normalized)
+    }
+
+    // Checks whether the only separator in the amount is for the thousands,
+    // that is it follows one to three digits (but not a zero) and it is
+    // followed by three digits
+    fn is_thousands_group(amount: &str, separator: char) -> bool {
+        match amount.split_once(separator) {
+            Some((integer, group)) => {
+                let integer = integer.trim_start_matches('-');
+                (1..=3).contains(&integer.len())
+                    && integer != "0"
+                    && group.len() == 3
+                    && group.chars().all(|c| c.is_ascii_digit())
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the only currency used by all these prices, euro when there
+    /// are no prices.
+    pub fn common_currency<'a, I>(prices: I) -> Result<Currency, PriceError>
+    where
+        I: IntoIterator<Item = &'a Price>,
+    {
+        let mut currency = None;
+        for price in prices {
+            match currency {
+                None => currency = Some(price.currency),
+                Some(c) if c != price.currency => {
+                    return Err(PriceError::MixedCurrencies(c, price.currency))
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(currency.unwrap_or_default())
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PriceError {
+    #[error("Cannot sum prices in different currencies ({0} and {1})")]
+    MixedCurrencies(Currency, Currency),
+}
+
+/// Adds two prices in the same currency.
+///
+/// # Panics
+///
+/// Panics when the prices are in different currencies, use `checked_add` to
+/// handle this case.
+impl ops::Add for Price {
+    type Output = Price;
+
+    fn add(self, other: Price) -> Price {
+        self.checked_add(&other)
+            .expect("cannot add prices in different currencies")
+    }
+}
+
+impl ops::AddAssign for Price {
+    fn add_assign(&mut self, other: Price) {
+        *self = self.clone() + other;
+    }
+}
+
+/// Subtracts two prices in the same currency.
+///
+/// # Panics
+///
+/// Panics when the prices are in different currencies, use `checked_sub` to
+/// handle this case.
+impl ops::Sub for Price {
+    type Output = Price;
+
+    fn sub(self, other: Price) -> Price {
+        self.checked_sub(&other)
+            .expect("cannot subtract prices in different currencies")
+    }
+}
+
+impl ops::Mul<u32> for Price {
+    type Output = Price;
+
+    fn mul(self, quantity: u32) -> Price {
+        Price::new(self.amount * Decimal::from(quantity), self.currency)
     }
 }
 
@@ -31,30 +300,91 @@ impl str::FromStr for Price {
         let mut it = s.split_ascii_whitespace();
         let amount = it
             .next()
-            .map(|s| s.replace(',', "."))
-            .map(|amount| Decimal::from_str(&amount))
-            .unwrap();
+            .ok_or_else(|| "Invalid price: cannot be empty".to_owned())?;
+        let amount = Price::normalize_amount(amount)
+            .and_then(|amount| Decimal::from_str(&amount).ok())
+            .ok_or_else(|| format!("Invalid price: '{}' is not a number", s))?;
+        if amount.is_sign_negative() && !amount.is_zero() {
+            return Err(format!("Invalid price: '{}' cannot be negative", s));
+        }
+        let currency = match it.next() {
+            Some(currency) => currency.parse::<Currency>().map_err(|_| {
+                format!(
+                    "Invalid price: unexpected '{}' in '{}' (expected a currency like EUR)",
+                    currency, s
+                )
+            })?,
+            None => Currency::default(),
+        };
+        if let Some(token) = it.next() {
+            return Err(format!(
+                "Invalid price: unexpected '{}' in '{}'",
+                token, s
+            ));
+        }
 
-        Ok(Price {
-            amount: amount.unwrap(),
-            currency: String::from("EUR"),
-        })
+        Ok(Price { amount, currency })
     }
 }
 
-impl core::iter::Sum for Price {
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        let total_amount = iter.map(|it| it.amount).sum();
-        Price {
-            amount: total_amount,
-            currency: String::from("EUR"), //TODO: fixme
-        }
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} {}", self.rounded().amount, self.currency)
     }
 }
 
-impl fmt::Display for Price {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {}", self.amount, self.currency)
+// the displayed amount is rounded to two decimals, the serialized one is not
+impl_serde_with_str!(Price, "price", |it: &Price| {
+    // three decimals would be read back as a thousands group (ie "1.234")
+    let amount = it.amount.normalize();
+    if amount.scale() == 3 {
+        format!("{:.4} {}", amount, it.currency)
+    } else {
+        format!("{} {}", amount, it.currency)
+    }
+});
+
+/// The conventions to format the price amounts.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// Dot as decimal separator and comma as thousands separator ("1,234.50")
+    #[default]
+    English,
+
+    /// Comma as decimal separator and dot as thousands separator ("1.234,50")
+    European,
+}
+
+impl Price {
+    /// Formats this price with two decimals, using the separators for the
+    /// given locale (like "1.234,50 EUR").
+    pub fn format_localized(&self, locale: Locale) -> String {
+        let (decimal_separator, thousands_separator) = match locale {
+            Locale::English => ('.', ','),
+            Locale::European => (',', '.'),
+        };
+
+        let amount = format!("{:.2}", self.amount.abs());
+        let (integer_part, decimal_part) =
+            amount.split_once('.').unwrap_or((&amount, "00"));
+
+        let mut grouped = String::new();
+        for (i, digit) in integer_part.chars().enumerate() {
+            if i > 0 && (integer_part.len() - i) % 3 == 0 {
+                grouped.push(thousands_separator);
+            }
+            grouped.push(digit);
+        }
+
+        let sign = if self.amount.is_sign_negative() && !self.amount.is_zero() {
+            "-"
+        } else {
+            ""
+        };
+        format!(
+            "{}{}{}{} {}",
+            sign, grouped, decimal_separator, decimal_part, self.currency
+        )
     }
 }
 
@@ -66,4 +396,324 @@ mod tests {
     fn run_me() {
         assert_eq!(1, 1);
     }
+
+    #[test]
+    fn it_should_parse_prices() {
+        let price = "229,50".parse::<Price>();
+        assert_eq!(Ok(Price::euro(Decimal::new(22950, 2))), price);
+    }
+
+    #[test]
+    fn it_should_serialize_prices_as_strings() {
+        let price = Price::euro(Decimal::new(22950, 2));
+
+        let json = serde_json::to_string(&price).unwrap();
+        assert_eq!("\"229.5 EUR\"", json);
+        assert_eq!(price, serde_json::from_str::<Price>(&json).unwrap());
+    }
+
+    #[test]
+    fn it_should_serialize_the_unrounded_amounts() {
+        let price = Price::euro(Decimal::new(199995, 3));
+
+        let json = serde_json::to_string(&price).unwrap();
+        assert_eq!("\"199.9950 EUR\"", json);
+        assert_eq!(price, serde_json::from_str::<Price>(&json).unwrap());
+    }
+
+    #[test]
+    fn it_should_return_the_price_amount_and_currency() {
+        let price = Price::euro(Decimal::new(19990, 2));
+        assert_eq!(Decimal::new(19990, 2), price.amount());
+        assert_eq!(Currency::EUR, price.currency());
+    }
+
+    #[test]
+    fn it_should_read_amount_and_currency_from_parsed_prices() {
+        let price = "199,90 EUR".parse::<Price>().unwrap();
+        assert_eq!(Decimal::new(19990, 2), price.amount());
+        assert_eq!(Currency::EUR, price.currency());
+    }
+
+    #[test]
+    fn it_should_parse_prices_in_other_currencies() {
+        assert_eq!(
+            Ok(Price::new(Decimal::new(15000, 2), Currency::USD)),
+            "150.00 USD".parse::<Price>()
+        );
+        assert_eq!(
+            Ok(Price::new(Decimal::from(99), Currency::GBP)),
+            "99 GBP".parse::<Price>()
+        );
+        assert_eq!(Ok(Price::euro(Decimal::from(120))), "120".parse::<Price>());
+    }
+
+    #[test]
+    fn it_should_parse_prices_with_thousands_separators() {
+        let amount = Decimal::new(123456, 2);
+        assert_eq!(Ok(Price::euro(amount)), "1.234,56".parse::<Price>());
+        assert_eq!(Ok(Price::euro(amount)), "1,234.56 EUR".parse::<Price>());
+        assert_eq!(
+            Ok(Price::euro(Decimal::from(1234567))),
+            "1.234.567".parse::<Price>()
+        );
+        assert_eq!(
+            Ok(Price::euro(Decimal::new(22950, 2))),
+            "229.50".parse::<Price>()
+        );
+        assert_eq!(
+            Ok(Price::euro(Decimal::from(1234))),
+            "1.234".parse::<Price>()
+        );
+        assert_eq!(
+            Ok(Price::euro(Decimal::from(1500))),
+            "1,500 EUR".parse::<Price>()
+        );
+        assert_eq!(
+            Ok(Price::euro(Decimal::new(995, 3))),
+            "0.995".parse::<Price>()
+        );
+    }
+
+    #[test]
+    fn it_should_fail_to_parse_prices_with_invalid_thousands_groups() {
+        assert!("1.23.4".parse::<Price>().is_err());
+        assert!("1.2345.678".parse::<Price>().is_err());
+        assert!("1,23.45".parse::<Price>().is_err());
+        assert!("1234.567,89".parse::<Price>().is_err());
+    }
+
+    #[test]
+    fn it_should_fail_to_parse_negative_prices() {
+        assert_eq!(
+            Err(String::from("Invalid price: '-5,00' cannot be negative")),
+            "-5,00".parse::<Price>()
+        );
+        assert_eq!(Ok(Price::euro(Decimal::ZERO)), "0,00".parse::<Price>());
+    }
+
+    #[test]
+    fn it_should_fail_to_parse_prices_that_are_not_numbers() {
+        assert!("NaN".parse::<Price>().is_err());
+        assert!("inf".parse::<Price>().is_err());
+        assert!("1.234,56,78".parse::<Price>().is_err());
+    }
+
+    #[test]
+    fn it_should_fail_to_parse_prices_with_trailing_text() {
+        assert_eq!(
+            Err(String::from(
+                "Invalid price: unexpected 'euro' in '195 euro please' (expected a currency like EUR)"
+            )),
+            "195 euro please".parse::<Price>()
+        );
+        assert_eq!(
+            Err(String::from(
+                "Invalid price: unexpected 'please' in '195 EUR please'"
+            )),
+            "195 EUR please".parse::<Price>()
+        );
+        assert!("195 EUR USD".parse::<Price>().is_err());
+    }
+
+    #[test]
+    fn it_should_parse_prices_with_an_optional_currency() {
+        let expected = Ok(Price::euro(Decimal::from(195)));
+        assert_eq!(expected, "195".parse::<Price>());
+        assert_eq!(expected, "195,00".parse::<Price>());
+        assert_eq!(expected, "195 EUR".parse::<Price>());
+        assert_eq!(expected, " 195  EUR ".parse::<Price>());
+    }
+
+    #[test]
+    fn it_should_fail_to_parse_prices_with_unknown_currencies() {
+        assert!("150.00 XYZ".parse::<Price>().is_err());
+    }
+
+    #[test]
+    fn it_should_round_prices_to_two_decimals() {
+        let price = |s: &str| s.parse::<Price>().unwrap();
+
+        assert_eq!(price("195"), price("195").rounded());
+        assert_eq!(price("195.5"), price("195.5").rounded());
+        assert_eq!(price("196"), price("195.9990").rounded());
+        assert_eq!(price("195.01"), price("195.0050").rounded());
+        assert_eq!(Currency::USD, price("195.9990 USD").rounded().currency());
+    }
+
+    #[test]
+    fn it_should_display_the_rounded_prices() {
+        let price = |s: &str| s.parse::<Price>().unwrap();
+
+        assert_eq!("195.00 EUR", price("195").to_string());
+        assert_eq!("195.50 EUR", price("195.5").to_string());
+        assert_eq!("196.00 EUR", price("195.9990").to_string());
+    }
+
+    #[test]
+    fn it_should_round_only_the_displayed_sums() {
+        let prices = vec![
+            Price::euro(Decimal::new(5, 3)),
+            Price::euro(Decimal::new(5, 3)),
+        ];
+
+        let total = Price::try_sum(prices).unwrap();
+        assert_eq!(Decimal::new(10, 3), total.amount());
+        assert_eq!("0.01 EUR", total.to_string());
+    }
+
+    #[test]
+    fn it_should_sum_prices() {
+        let prices = vec![
+            Price::euro(Decimal::new(19990, 2)),
+            Price::euro(Decimal::new(10, 0)),
+        ];
+        let total = Price::try_sum(prices);
+        assert_eq!(Ok(Price::euro(Decimal::new(20990, 2))), total);
+    }
+
+    #[test]
+    fn it_should_sum_prices_keeping_their_currency() {
+        let prices = vec![
+            Price::new(Decimal::from(99), Currency::GBP),
+            Price::new(Decimal::from(1), Currency::GBP),
+        ];
+        let total = Price::try_sum(prices).unwrap();
+        assert_eq!("100.00 GBP", total.to_string());
+    }
+
+    #[test]
+    fn it_should_sum_no_prices_to_zero_euro() {
+        let total = Price::try_sum(Vec::new()).unwrap();
+        assert_eq!(Price::euro(Decimal::ZERO), total);
+        assert_eq!("0.00 EUR", total.to_string());
+    }
+
+    #[test]
+    fn it_should_refuse_to_sum_prices_in_different_currencies() {
+        let prices = vec![
+            Price::euro(Decimal::from(10)),
+            Price::new(Decimal::from(10), Currency::USD),
+        ];
+        let result = Price::try_sum(prices);
+        assert_eq!(
+            Err(PriceError::MixedCurrencies(Currency::EUR, Currency::USD)),
+            result
+        );
+        assert_eq!(
+            "Cannot sum prices in different currencies (EUR and USD)",
+            result.unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn it_should_add_and_subtract_prices() {
+        let price = Price::euro(Decimal::new(19990, 2));
+        let resale = Price::euro(Decimal::from(150));
+
+        assert_eq!(
+            Price::euro(Decimal::new(34990, 2)),
+            price.clone() + resale.clone()
+        );
+        assert_eq!(Price::euro(Decimal::new(4990, 2)), price - resale);
+    }
+
+    #[test]
+    fn it_should_multiply_prices_by_a_quantity() {
+        let price = Price::new(Decimal::new(4550, 2), Currency::GBP);
+        assert_eq!(
+            Price::new(Decimal::new(13650, 2), Currency::GBP),
+            price * 3
+        );
+    }
+
+    #[test]
+    fn it_should_check_zero_prices() {
+        assert!(Price::zero(Currency::USD).is_zero());
+        assert_eq!(Currency::USD, Price::zero(Currency::USD).currency());
+        assert!(!Price::euro(Decimal::ONE).is_zero());
+    }
+
+    #[test]
+    fn it_should_fail_to_add_prices_in_different_currencies() {
+        let euro = Price::euro(Decimal::from(10));
+        let dollars = Price::new(Decimal::from(10), Currency::USD);
+
+        assert_eq!(Ok(Price::euro(Decimal::from(20))), euro.checked_add(&euro));
+        assert_eq!(
+            Err(PriceError::MixedCurrencies(Currency::EUR, Currency::USD)),
+            euro.checked_add(&dollars)
+        );
+    }
+
+    #[test]
+    fn it_should_fail_to_subtract_prices_in_different_currencies() {
+        let euro = Price::euro(Decimal::from(10));
+        let pounds = Price::new(Decimal::from(4), Currency::GBP);
+
+        assert_eq!(
+            Ok(Price::euro(Decimal::from(6))),
+            euro.checked_sub(&Price::euro(Decimal::from(4)))
+        );
+        assert_eq!(
+            Err(PriceError::MixedCurrencies(Currency::EUR, Currency::GBP)),
+            euro.checked_sub(&pounds)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot add prices in different currencies")]
+    fn it_should_panic_adding_prices_in_different_currencies() {
+        let _ = Price::euro(Decimal::from(10))
+            + Price::new(Decimal::from(10), Currency::USD);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot subtract prices in different currencies")]
+    fn it_should_panic_subtracting_prices_in_different_currencies() {
+        let _ = Price::euro(Decimal::from(10))
+            - Price::new(Decimal::from(10), Currency::GBP);
+    }
+
+    #[test]
+    fn it_should_find_the_common_currency() {
+        let gbp = Price::new(Decimal::from(99), Currency::GBP);
+        let usd = Price::new(Decimal::from(99), Currency::USD);
+
+        assert_eq!(Ok(Currency::EUR), Price::common_currency(&[]));
+        assert_eq!(
+            Ok(Currency::GBP),
+            Price::common_currency(&[gbp.clone(), gbp.clone()])
+        );
+        assert!(Price::common_currency(&[gbp, usd]).is_err());
+    }
+
+    #[test]
+    fn it_should_display_prices_with_two_decimals() {
+        assert_eq!("190.00 EUR", Price::euro(Decimal::from(190)).to_string());
+        assert_eq!(
+            "190.50 EUR",
+            "190,50".parse::<Price>().unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn it_should_format_prices_for_the_locale() {
+        let price = Price::euro(Decimal::new(123450, 2));
+        assert_eq!("1,234.50 EUR", price.format_localized(Locale::English));
+        assert_eq!("1.234,50 EUR", price.format_localized(Locale::European));
+
+        let price = Price::new(Decimal::new(-1234567, 1), Currency::USD);
+        assert_eq!("-123,456.70 USD", price.format_localized(Locale::English));
+
+        let price = Price::euro(Decimal::from(99));
+        assert_eq!("99,00 EUR", price.format_localized(Locale::European));
+    }
+
+    #[test]
+    fn it_should_fail_to_parse_invalid_prices() {
+        assert!("".parse::<Price>().is_err());
+        assert!("   ".parse::<Price>().is_err());
+        assert!("abc".parse::<Price>().is_err());
+    }
 }
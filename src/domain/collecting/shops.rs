@@ -0,0 +1,257 @@
+//! This module contains the statistics grouped by shop, for both the purchases
+//! in a collection and the prices in a wish list.
+use crate::domain::collecting::collections::Collection;
+use crate::domain::collecting::wish_lists::WishList;
+use crate::domain::collecting::{Currency, Price, PriceError};
+
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+use std::cmp;
+
+/// The statistics grouped by shop, the shop names differing only in case or
+/// in the surrounding whitespace are the same shop.
+#[derive(Debug, PartialEq)]
+pub struct ShopStats {
+    currency: Currency,
+    values: Vec<ShopSummary>,
+}
+
+impl ShopStats {
+    /// Creates the statistics for the purchases in the collection, sorted by shop name.
+    pub fn from_collection(
+        collection: &Collection,
+    ) -> Result<Self, PriceError> {
+        let currency = collection.currency()?;
+        let mut stats = ShopStats {
+            currency,
+            values: Vec::new(),
+        };
+
+        for item in collection.get_items() {
+            let purchase = item.purchased_info();
+            stats.sum(
+                purchase.shop(),
+                purchase.price(),
+                Some(*purchase.purchased_date()),
+            );
+        }
+
+        stats.sort_by_name();
+        Ok(stats)
+    }
+
+    /// Creates the statistics for the prices in the wish list, sorted by shop name.
+    ///
+    /// Every shop offering an item counts that item once, with its latest
+    /// price; the purchase dates are not available.
+    pub fn from_wish_list(wish_list: &WishList) -> Result<Self, PriceError> {
+        let currency = wish_list.currency()?;
+        let mut stats = ShopStats {
+            currency,
+            values: Vec::new(),
+        };
+
+        for item in wish_list.get_items() {
+            for price_info in item.latest_price_per_shop() {
+                stats.sum(price_info.shop(), Some(price_info.price()), None);
+            }
+        }
+
+        stats.sort_by_name();
+        Ok(stats)
+    }
+
+    fn sum(
+        &mut self,
+        shop: &str,
+        price: Option<&Price>,
+        date: Option<NaiveDate>,
+    ) {
+        let shop = shop.trim();
+        let key = shop.to_lowercase();
+        let position = match self.values.iter().position(|it| it.key == key) {
+            Some(position) => position,
+            None => {
+                self.values.push(ShopSummary::new(shop, key));
+                self.values.len() - 1
+            }
+        };
+        self.values[position].sum(price, date);
+    }
+
+    fn sort_by_name(&mut self) {
+        self.values.sort_by(|a, b| a.key.cmp(&b.key));
+    }
+
+    /// Sorts the shops by the total amount, from the highest one; the shops
+    /// with the same total are kept in their current order.
+    pub fn sort_by_total(&mut self) {
+        self.values.sort_by_key(|it| cmp::Reverse(it.total));
+    }
+
+    /// The currency for the amounts
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn values(&self) -> &Vec<ShopSummary> {
+        &self.values
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ShopSummary {
+    shop: String,
+    key: String,
+    number_of_items: u16,
+    number_of_prices: u16,
+    total: Decimal,
+    first_purchase: Option<NaiveDate>,
+    last_purchase: Option<NaiveDate>,
+}
+
+impl ShopSummary {
+    fn new(shop: &str, key: String) -> Self {
+        ShopSummary {
+            shop: shop.to_owned(),
+            key,
+            number_of_items: 0,
+            number_of_prices: 0,
+            total: Decimal::ZERO,
+            first_purchase: None,
+            last_purchase: None,
+        }
+    }
+
+    fn sum(&mut self, price: Option<&Price>, date: Option<NaiveDate>) {
+        self.number_of_items += 1;
+        if let Some(price) = price {
+            self.number_of_prices += 1;
+            self.total += price.amount();
+        }
+        if let Some(date) = date {
+            self.first_purchase =
+                Some(self.first_purchase.map_or(date, |it| it.min(date)));
+            self.last_purchase =
+                Some(self.last_purchase.map_or(date, |it| it.max(date)));
+        }
+    }
+
+    /// The shop name, as written for its first purchase
+    pub fn shop(&self) -> &str {
+        &self.shop
+    }
+
+    pub fn number_of_items(&self) -> u16 {
+        self.number_of_items
+    }
+
+    /// The total amount for this shop
+    pub fn total(&self) -> Decimal {
+        self.total
+    }
+
+    /// The average price, for the items with a price
+    pub fn average_price(&self) -> Option<Decimal> {
+        if self.number_of_prices == 0 {
+            None
+        } else {
+            Some(
+                (self.total / Decimal::from(self.number_of_prices)).round_dp(2),
+            )
+        }
+    }
+
+    pub fn first_purchase(&self) -> Option<NaiveDate> {
+        self.first_purchase
+    }
+
+    pub fn last_purchase(&self) -> Option<NaiveDate> {
+        self.last_purchase
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::collecting::collections::PurchasedInfo;
+    use crate::domain::collecting::fixtures::{
+        new_catalog_item, new_purchased_info,
+    };
+    use crate::domain::collecting::wish_lists::{PriceInfo, Priority};
+
+    fn new_collection() -> Collection {
+        let mut collection = Collection::create_empty("My collection");
+        collection.add_item(
+            new_catalog_item("60023", vec![]),
+            new_purchased_info("Treni&Treni", "2020-06-01", "200"),
+        );
+        collection.add_item(
+            new_catalog_item("60024", vec![]),
+            new_purchased_info("Tecnomodel", "2021-06-01", "300"),
+        );
+        collection.add_item(
+            new_catalog_item("60025", vec![]),
+            new_purchased_info(" treni&treni ", "2019-01-10", "150"),
+        );
+        collection.add_item(
+            new_catalog_item("60026", vec![]),
+            PurchasedInfo::without_price(
+                "TRENI&TRENI",
+                NaiveDate::from_ymd_opt(2022, 3, 1).unwrap(),
+            ),
+        );
+        collection
+    }
+
+    #[test]
+    fn it_should_merge_the_shops_differing_in_case_and_whitespace() {
+        let stats = ShopStats::from_collection(&new_collection()).unwrap();
+
+        assert_eq!(2, stats.values().len());
+        let shop = &stats.values()[1];
+        assert_eq!("Treni&Treni", shop.shop());
+        assert_eq!(3, shop.number_of_items());
+        assert_eq!(Decimal::from(350), shop.total());
+        assert_eq!(Some(Decimal::from(175)), shop.average_price());
+        assert_eq!(NaiveDate::from_ymd_opt(2019, 1, 10), shop.first_purchase());
+        assert_eq!(NaiveDate::from_ymd_opt(2022, 3, 1), shop.last_purchase());
+    }
+
+    #[test]
+    fn it_should_sort_the_shops_by_total() {
+        let mut stats = ShopStats::from_collection(&new_collection()).unwrap();
+        stats.sort_by_total();
+
+        let shops: Vec<&str> =
+            stats.values().iter().map(|it| it.shop()).collect();
+        assert_eq!(vec!["Treni&Treni", "Tecnomodel"], shops);
+    }
+
+    #[test]
+    fn it_should_calculate_the_shop_stats_for_wish_lists() {
+        let mut wish_list = WishList::create_empty("My wish list");
+        wish_list.add_item(
+            new_catalog_item("60001", vec![]),
+            Priority::High,
+            vec![
+                PriceInfo::new("Treni&Treni", "200".parse().unwrap()),
+                PriceInfo::new("Tecnomodel", "210".parse().unwrap()),
+            ],
+        );
+        wish_list.add_item(
+            new_catalog_item("60002", vec![]),
+            Priority::Low,
+            vec![PriceInfo::new("tecnomodel", "90".parse().unwrap())],
+        );
+
+        let stats = ShopStats::from_wish_list(&wish_list).unwrap();
+
+        let shop = &stats.values()[0];
+        assert_eq!("Tecnomodel", shop.shop());
+        assert_eq!(2, shop.number_of_items());
+        assert_eq!(Decimal::from(300), shop.total());
+        assert_eq!(Some(Decimal::from(150)), shop.average_price());
+        assert_eq!(None, shop.first_purchase());
+    }
+}
@@ -0,0 +1,761 @@
+//! This module contains the methods to estimate the value of a collection.
+//!
+//! Each valuation method is a strategy over the collection items:
+//! * purchase: the price paid for the items;
+//! * replacement: the price paid, increased by a yearly rate since the purchase;
+//! * market: the price paid, scaled by a multiplier for each category;
+//! * reference: the reference prices (ie from a wish list), falling back to the price paid.
+use crate::domain::catalog::{
+    brands::Brand,
+    catalog_items::{CatalogItem, ItemNumber},
+    categories::Category,
+};
+use crate::domain::collecting::collections::{Collection, CollectionItem};
+use crate::domain::collecting::wish_lists::WishList;
+use crate::domain::collecting::{Currency, Price, PriceError};
+
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::prelude::*;
+use std::collections::BTreeMap;
+
+/// A method to estimate the value for collection items
+pub trait ValuationMethod {
+    /// The name for this valuation method
+    fn name(&self) -> &str;
+
+    /// Returns the estimated value for the collection item
+    fn value_of(&self, item: &CollectionItem) -> Decimal;
+}
+
+/// The items are valued at their purchase price.
+#[derive(Debug, Default)]
+pub struct PurchaseValuation;
+
+impl ValuationMethod for PurchaseValuation {
+    fn name(&self) -> &str {
+        "purchase"
+    }
+
+    fn value_of(&self, item: &CollectionItem) -> Decimal {
+        item.purchased_info()
+            .price()
+            .map(Price::amount)
+            .unwrap_or_default()
+    }
+}
+
+/// The items are valued at the cost to replace them, that is the purchase price
+/// compounded by a yearly rate (ie the inflation) for every full year since the purchase.
+#[derive(Debug)]
+pub struct ReplacementValuation {
+    yearly_rate: Decimal,
+    as_of: NaiveDate,
+}
+
+impl ReplacementValuation {
+    /// Creates a new replacement valuation, the yearly rate is a percentage (ie 2 for 2%).
+    pub fn new(yearly_rate: Decimal, as_of: NaiveDate) -> Self {
+        ReplacementValuation { yearly_rate, as_of }
+    }
+
+    fn years_since(&self, date: &NaiveDate) -> u32 {
+        let mut years = self.as_of.year() - date.year();
+        if (self.as_of.month(), self.as_of.day()) < (date.month(), date.day()) {
+            years -= 1;
+        }
+        years.max(0) as u32
+    }
+}
+
+impl ValuationMethod for ReplacementValuation {
+    fn name(&self) -> &str {
+        "replacement"
+    }
+
+    fn value_of(&self, item: &CollectionItem) -> Decimal {
+        let purchase = item.purchased_info();
+        let factor = Decimal::ONE + self.yearly_rate / Decimal::ONE_HUNDRED;
+
+        let mut value = purchase.price().map(Price::amount).unwrap_or_default();
+        for _ in 0..self.years_since(purchase.purchased_date()) {
+            value *= factor;
+        }
+        value.round_dp(2)
+    }
+}
+
+/// The items are valued at their market price, estimated applying a multiplier to the
+/// purchase price for each category. Categories without a multiplier keep the price paid.
+#[derive(Debug, Default)]
+pub struct MarketValuation {
+    multipliers: BTreeMap<Category, Decimal>,
+}
+
+impl MarketValuation {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the multiplier for the given category.
+    pub fn with_multiplier(
+        mut self,
+        category: Category,
+        value: Decimal,
+    ) -> Self {
+        self.multipliers.insert(category, value);
+        self
+    }
+
+    /// Returns the multiplier for the given category
+    pub fn multiplier(&self, category: Category) -> Decimal {
+        self.multipliers
+            .get(&category)
+            .copied()
+            .unwrap_or(Decimal::ONE)
+    }
+}
+
+impl ValuationMethod for MarketValuation {
+    fn name(&self) -> &str {
+        "market"
+    }
+
+    fn value_of(&self, item: &CollectionItem) -> Decimal {
+        let category = item.catalog_item().category();
+        let price = item
+            .purchased_info()
+            .price()
+            .map(Price::amount)
+            .unwrap_or_default();
+        (price * self.multiplier(category)).round_dp(2)
+    }
+}
+
+/// The reference prices for the catalog items, matched by brand and item number.
+#[derive(Debug, Default)]
+pub struct ReferencePrices {
+    prices: BTreeMap<(Brand, ItemNumber), Price>,
+}
+
+impl ReferencePrices {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates the reference prices from the wish list, using the max among
+    /// the latest shop prices for each wish list item.
+    pub fn from_wish_list(wish_list: &WishList) -> Self {
+        let mut reference_prices = ReferencePrices::new();
+        for item in wish_list.get_items() {
+            if let Some((_, max)) = item.latest_price_range() {
+                reference_prices = reference_prices
+                    .with_price(item.catalog_item(), max.price());
+            }
+        }
+        reference_prices
+    }
+
+    /// Sets the reference price for the catalog item, keeping the highest one
+    /// when the catalog item has already a price.
+    pub fn with_price(
+        mut self,
+        catalog_item: &CatalogItem,
+        price: &Price,
+    ) -> Self {
+        let key = (
+            catalog_item.brand().clone(),
+            catalog_item.item_number().clone(),
+        );
+        match self.prices.get(&key) {
+            Some(current) if current >= price => {}
+            _ => {
+                self.prices.insert(key, price.clone());
+            }
+        }
+        self
+    }
+
+    /// Returns the reference price for the catalog item, if any
+    pub fn price_of(&self, catalog_item: &CatalogItem) -> Option<&Price> {
+        self.prices.get(&(
+            catalog_item.brand().clone(),
+            catalog_item.item_number().clone(),
+        ))
+    }
+
+    pub fn len(&self) -> usize {
+        self.prices.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prices.is_empty()
+    }
+}
+
+impl ValuationMethod for ReferencePrices {
+    fn name(&self) -> &str {
+        "reference"
+    }
+
+    fn value_of(&self, item: &CollectionItem) -> Decimal {
+        match self.price_of(item.catalog_item()) {
+            Some(price) => price.amount(),
+            None => PurchaseValuation.value_of(item),
+        }
+    }
+}
+
+/// The current value for each collection item, using the reference prices when
+/// available, compared with the price paid. The sold items are not included.
+#[derive(Debug)]
+pub struct CurrentValueReport<'a> {
+    currency: Currency,
+    items: Vec<CurrentValue<'a>>,
+    paid_total: Decimal,
+    current_total: Decimal,
+}
+
+impl<'a> CurrentValueReport<'a> {
+    pub fn from_collection(
+        collection: &'a Collection,
+        reference_prices: &ReferencePrices,
+    ) -> Result<Self, PriceError> {
+        let currency =
+            Price::common_currency(collection.owned_items().flat_map(|it| {
+                it.purchased_info()
+                    .price()
+                    .into_iter()
+                    .chain(reference_prices.price_of(it.catalog_item()))
+            }))?;
+
+        let items: Vec<CurrentValue> = collection
+            .owned_items()
+            .map(|item| CurrentValue {
+                item,
+                paid: PurchaseValuation.value_of(item),
+                current_value: reference_prices.value_of(item),
+                has_reference_price: reference_prices
+                    .price_of(item.catalog_item())
+                    .is_some(),
+            })
+            .collect();
+
+        let paid_total = items.iter().map(|it| it.paid).sum();
+        let current_total = items.iter().map(|it| it.current_value).sum();
+
+        Ok(CurrentValueReport {
+            currency,
+            items,
+            paid_total,
+            current_total,
+        })
+    }
+
+    /// The currency for the collection values
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn items(&self) -> &[CurrentValue<'a>] {
+        &self.items
+    }
+
+    /// The total price paid for the collection items
+    pub fn paid_total(&self) -> Decimal {
+        self.paid_total
+    }
+
+    /// The total current value for the collection items
+    pub fn current_total(&self) -> Decimal {
+        self.current_total
+    }
+
+    /// The difference between the current value and the price paid
+    pub fn difference(&self) -> Decimal {
+        self.current_total - self.paid_total
+    }
+}
+
+#[derive(Debug)]
+pub struct CurrentValue<'a> {
+    item: &'a CollectionItem,
+    paid: Decimal,
+    current_value: Decimal,
+    has_reference_price: bool,
+}
+
+impl<'a> CurrentValue<'a> {
+    pub fn item(&self) -> &'a CollectionItem {
+        self.item
+    }
+
+    pub fn paid(&self) -> Decimal {
+        self.paid
+    }
+
+    pub fn current_value(&self) -> Decimal {
+        self.current_value
+    }
+
+    /// Checks whether the current value comes from a reference price, rather
+    /// than from the price paid
+    pub fn has_reference_price(&self) -> bool {
+        self.has_reference_price
+    }
+
+    /// The difference between the current value and the price paid
+    pub fn difference(&self) -> Decimal {
+        self.current_value - self.paid
+    }
+}
+
+/// The insurance report lists every item still owned with its purchase price
+/// and the estimated cost to replace it.
+///
+/// The items without a replacement value are valued at the purchase price,
+/// scaled by the markup when it is provided; the items without a price and
+/// without a replacement value are listed apart, and left out of the totals.
+#[derive(Debug)]
+pub struct InsuranceReport<'a> {
+    currency: Currency,
+    items: Vec<InsuredItem<'a>>,
+    unknown_value: Vec<&'a CollectionItem>,
+    purchase_total: Decimal,
+    replacement_total: Decimal,
+}
+
+impl<'a> InsuranceReport<'a> {
+    pub fn from_collection(
+        collection: &'a Collection,
+        markup: Option<Decimal>,
+    ) -> Result<Self, PriceError> {
+        let currency =
+            Price::common_currency(collection.owned_items().flat_map(|it| {
+                it.purchased_info()
+                    .price()
+                    .into_iter()
+                    .chain(it.replacement_value())
+            }))?;
+
+        let (known, unknown_value): (Vec<_>, Vec<_>) =
+            collection.owned_items().partition(|item| {
+                item.purchased_info().price().is_some()
+                    || item.replacement_value().is_some()
+            });
+
+        let items: Vec<InsuredItem> = known
+            .into_iter()
+            .map(|item| {
+                let purchase_price = PurchaseValuation.value_of(item);
+                let replacement_value = match item.replacement_value() {
+                    Some(value) => value.amount(),
+                    None => match markup {
+                        Some(markup) => (purchase_price * markup).round_dp(2),
+                        None => purchase_price,
+                    },
+                };
+                InsuredItem {
+                    item,
+                    replacement_value,
+                }
+            })
+            .collect();
+
+        let purchase_total = items
+            .iter()
+            .map(|it| PurchaseValuation.value_of(it.item))
+            .sum();
+        let replacement_total =
+            items.iter().map(|it| it.replacement_value).sum();
+
+        Ok(InsuranceReport {
+            currency,
+            items,
+            unknown_value,
+            purchase_total,
+            replacement_total,
+        })
+    }
+
+    /// The currency for the collection values
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn items(&self) -> &[InsuredItem<'a>] {
+        &self.items
+    }
+
+    /// The items without a price and without a replacement value
+    pub fn unknown_value(&self) -> &[&'a CollectionItem] {
+        &self.unknown_value
+    }
+
+    /// The total price paid for the items
+    pub fn purchase_total(&self) -> Decimal {
+        self.purchase_total
+    }
+
+    /// The total replacement value for the items
+    pub fn replacement_total(&self) -> Decimal {
+        self.replacement_total
+    }
+}
+
+#[derive(Debug)]
+pub struct InsuredItem<'a> {
+    item: &'a CollectionItem,
+    replacement_value: Decimal,
+}
+
+impl<'a> InsuredItem<'a> {
+    pub fn item(&self) -> &'a CollectionItem {
+        self.item
+    }
+
+    /// The estimated cost to replace this item
+    pub fn replacement_value(&self) -> Decimal {
+        self.replacement_value
+    }
+}
+
+/// The collection value, estimated using a valuation method; the sold items
+/// are not included.
+#[derive(Debug, PartialEq)]
+pub struct CollectionValuation {
+    method: String,
+    currency: Currency,
+    total_value: Decimal,
+    by_category: Vec<CategoryValuation>,
+}
+
+impl CollectionValuation {
+    pub fn from_collection(
+        collection: &Collection,
+        method: &dyn ValuationMethod,
+    ) -> Result<Self, PriceError> {
+        let currency = Price::common_currency(
+            collection
+                .owned_items()
+                .filter_map(|it| it.purchased_info().price()),
+        )?;
+        let mut by_category: BTreeMap<Category, CategoryValuation> =
+            BTreeMap::new();
+
+        for item in collection.owned_items() {
+            let category = item.catalog_item().category();
+            let value = method.value_of(item);
+
+            let entry = by_category.entry(category).or_insert_with(|| {
+                CategoryValuation {
+                    category,
+                    number_of_items: 0,
+                    value: Decimal::ZERO,
+                }
+            });
+            entry.number_of_items += item.catalog_item().count() as u16;
+            entry.value += value;
+        }
+
+        let by_category: Vec<CategoryValuation> =
+            by_category.into_values().collect();
+        let total_value = by_category.iter().map(|it| it.value).sum();
+
+        Ok(CollectionValuation {
+            method: method.name().to_owned(),
+            currency,
+            total_value,
+            by_category,
+        })
+    }
+
+    /// The name for the valuation method
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// The currency for the collection value
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn total_value(&self) -> Decimal {
+        self.total_value
+    }
+
+    pub fn by_category(&self) -> &Vec<CategoryValuation> {
+        &self.by_category
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CategoryValuation {
+    category: Category,
+    number_of_items: u16,
+    value: Decimal,
+}
+
+impl CategoryValuation {
+    pub fn category(&self) -> Category {
+        self.category
+    }
+
+    pub fn number_of_items(&self) -> u16 {
+        self.number_of_items
+    }
+
+    pub fn value(&self) -> Decimal {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::catalog::{
+        categories::LocomotiveType,
+        railways::Railway,
+        rolling_stocks::{Epoch, RollingStockBuilder},
+    };
+    use crate::domain::collecting::{
+        collections::{PurchasedInfo, SoldInfo},
+        fixtures::{new_catalog_item, new_freight_car, new_purchased_info},
+        Price,
+    };
+
+    fn new_collection() -> Collection {
+        let locomotive = RollingStockBuilder::locomotive()
+            .with_class_name("E.656")
+            .with_road_number("E.656 210")
+            .with_railway(Railway::new("FS"))
+            .with_epoch(Epoch::IV)
+            .with_locomotive_type(LocomotiveType::ElectricLocomotive)
+            .build()
+            .unwrap();
+        let freight_car = new_freight_car();
+
+        let mut collection = Collection::create_empty("My collection");
+        collection.add_item(
+            new_catalog_item("60023", vec![locomotive]),
+            new_purchased_info("Treni&Treni", "2020-06-01", "200"),
+        );
+        collection.add_item(
+            new_catalog_item("45000", vec![freight_car]),
+            new_purchased_info("Treni&Treni", "2021-06-01", "50"),
+        );
+        collection
+    }
+
+    #[test]
+    fn it_should_value_the_collection_at_the_purchase_price() {
+        let valuation = CollectionValuation::from_collection(
+            &new_collection(),
+            &PurchaseValuation,
+        )
+        .unwrap();
+
+        assert_eq!("purchase", valuation.method());
+        assert_eq!(Decimal::from(250), valuation.total_value());
+        assert_eq!(2, valuation.by_category().len());
+        assert_eq!(
+            Category::Locomotives,
+            valuation.by_category()[0].category()
+        );
+        assert_eq!(Decimal::from(200), valuation.by_category()[0].value());
+    }
+
+    #[test]
+    fn it_should_value_the_collection_at_the_replacement_cost() {
+        let as_of = NaiveDate::from_ymd_opt(2022, 7, 1).unwrap();
+        let method = ReplacementValuation::new(Decimal::TEN, as_of);
+        let valuation =
+            CollectionValuation::from_collection(&new_collection(), &method)
+                .unwrap();
+
+        // 200 * 1.1 * 1.1 + 50 * 1.1
+        assert_eq!("replacement", valuation.method());
+        assert_eq!(Decimal::new(29700, 2), valuation.total_value());
+    }
+
+    #[test]
+    fn it_should_value_the_collection_at_the_reference_prices() {
+        let collection = new_collection();
+        let reference_prices = ReferencePrices::new().with_price(
+            collection.get_items()[0].catalog_item(),
+            &"260".parse::<Price>().unwrap(),
+        );
+        let valuation = CollectionValuation::from_collection(
+            &collection,
+            &reference_prices,
+        )
+        .unwrap();
+
+        assert_eq!("reference", valuation.method());
+        assert_eq!(Decimal::from(310), valuation.total_value());
+    }
+
+    #[test]
+    fn it_should_keep_the_highest_reference_price() {
+        let collection = new_collection();
+        let catalog_item = collection.get_items()[0].catalog_item();
+        let reference_prices = ReferencePrices::new()
+            .with_price(catalog_item, &"260".parse::<Price>().unwrap())
+            .with_price(catalog_item, &"240".parse::<Price>().unwrap());
+
+        assert_eq!(1, reference_prices.len());
+        assert_eq!(
+            Some(&"260".parse::<Price>().unwrap()),
+            reference_prices.price_of(catalog_item)
+        );
+    }
+
+    #[test]
+    fn it_should_compare_the_current_value_with_the_price_paid() {
+        let collection = new_collection();
+        let reference_prices = ReferencePrices::new().with_price(
+            collection.get_items()[0].catalog_item(),
+            &"260".parse::<Price>().unwrap(),
+        );
+        let report =
+            CurrentValueReport::from_collection(&collection, &reference_prices)
+                .unwrap();
+
+        assert_eq!(Decimal::from(250), report.paid_total());
+        assert_eq!(Decimal::from(310), report.current_total());
+        assert_eq!(Decimal::from(60), report.difference());
+
+        let items = report.items();
+        assert!(items[0].has_reference_price());
+        assert_eq!(Decimal::from(60), items[0].difference());
+        assert!(!items[1].has_reference_price());
+        assert_eq!(Decimal::ZERO, items[1].difference());
+    }
+
+    #[test]
+    fn it_should_fail_to_compare_reference_prices_in_other_currencies() {
+        let collection = new_collection();
+        let reference_prices = ReferencePrices::new().with_price(
+            collection.get_items()[0].catalog_item(),
+            &"260 USD".parse::<Price>().unwrap(),
+        );
+        let result =
+            CurrentValueReport::from_collection(&collection, &reference_prices);
+
+        assert_eq!(
+            PriceError::MixedCurrencies(Currency::EUR, Currency::USD),
+            result.unwrap_err()
+        );
+    }
+
+    #[test]
+    fn it_should_list_the_replacement_values_for_insurance() {
+        let mut collection = new_collection();
+        collection.add_collection_item(
+            CollectionItem::new(
+                new_catalog_item("45001", vec![new_freight_car()]),
+                new_purchased_info("Treni&Treni", "2021-06-01", "40"),
+            )
+            .with_replacement_value("65".parse::<Price>().unwrap()),
+        );
+
+        let report =
+            InsuranceReport::from_collection(&collection, None).unwrap();
+        assert_eq!(Decimal::from(290), report.purchase_total());
+        assert_eq!(Decimal::from(315), report.replacement_total());
+        assert_eq!(Decimal::from(200), report.items()[0].replacement_value());
+        assert_eq!(Decimal::from(65), report.items()[2].replacement_value());
+    }
+
+    #[test]
+    fn it_should_list_the_items_without_any_value_apart() {
+        let mut collection = new_collection();
+        collection.add_item(
+            new_catalog_item("45001", vec![new_freight_car()]),
+            PurchasedInfo::without_price(
+                "Treni&Treni",
+                NaiveDate::from_ymd_opt(2021, 6, 1).unwrap(),
+            ),
+        );
+
+        let report =
+            InsuranceReport::from_collection(&collection, None).unwrap();
+        assert_eq!(2, report.items().len());
+        assert_eq!(1, report.unknown_value().len());
+        assert_eq!(
+            "45001",
+            report.unknown_value()[0]
+                .catalog_item()
+                .item_number()
+                .value()
+        );
+        assert_eq!(Decimal::from(250), report.replacement_total());
+    }
+
+    #[test]
+    fn it_should_scale_the_missing_replacement_values_by_the_markup() {
+        let mut collection = new_collection();
+        collection.add_collection_item(
+            CollectionItem::new(
+                new_catalog_item("45001", vec![new_freight_car()]),
+                new_purchased_info("Treni&Treni", "2021-06-01", "40"),
+            )
+            .with_replacement_value("65".parse::<Price>().unwrap()),
+        );
+
+        let report = InsuranceReport::from_collection(
+            &collection,
+            Some(Decimal::new(12, 1)),
+        )
+        .unwrap();
+        // (200 + 50) * 1.2 + 65
+        assert_eq!(Decimal::from(365), report.replacement_total());
+        assert_eq!(Decimal::from(240), report.items()[0].replacement_value());
+    }
+
+    #[test]
+    fn it_should_value_the_collection_at_the_market_price() {
+        let method = MarketValuation::new()
+            .with_multiplier(Category::Locomotives, Decimal::new(15, 1));
+        let valuation =
+            CollectionValuation::from_collection(&new_collection(), &method)
+                .unwrap();
+
+        assert_eq!("market", valuation.method());
+        assert_eq!(Decimal::from(350), valuation.total_value());
+        assert_eq!(Decimal::from(50), valuation.by_category()[1].value());
+    }
+
+    #[test]
+    fn it_should_leave_the_sold_items_out_of_the_valuations() {
+        let mut collection = new_collection();
+        collection.add_collection_item(
+            CollectionItem::new(
+                new_catalog_item("45001", vec![new_freight_car()]),
+                new_purchased_info("Treni&Treni", "2021-06-01", "40"),
+            )
+            .with_sold_info(SoldInfo::new(
+                NaiveDate::from_ymd_opt(2022, 1, 10).unwrap(),
+                "45".parse::<Price>().unwrap(),
+            )),
+        );
+
+        let valuation = CollectionValuation::from_collection(
+            &collection,
+            &PurchaseValuation,
+        )
+        .unwrap();
+        assert_eq!(Decimal::from(250), valuation.total_value());
+
+        let report =
+            InsuranceReport::from_collection(&collection, None).unwrap();
+        assert_eq!(2, report.items().len());
+        assert_eq!(Decimal::from(250), report.purchase_total());
+
+        let report = CurrentValueReport::from_collection(
+            &collection,
+            &ReferencePrices::new(),
+        )
+        .unwrap();
+        assert_eq!(2, report.items().len());
+    }
+}
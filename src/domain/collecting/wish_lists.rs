@@ -1,52 +1,297 @@
-use collections::HashMap;
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use collections::{BTreeMap, HashMap, HashSet};
+use heck::ToShoutySnakeCase;
 use rust_decimal::prelude::*;
 use std::cmp;
 use std::collections;
-use std::default;
 use std::fmt;
+use std::iter;
 use std::str;
 
-use crate::domain::catalog::catalog_items::CatalogItem;
+use crate::domain::catalog::{
+    catalog_items::CatalogItem, categories::Category,
+    rolling_stocks::RollingStock,
+};
 
-use super::Price;
+use super::collections::{Collection, CollectionItem};
+use super::{Currency, Price, PriceError};
 
 #[derive(Debug)]
 pub struct WishList {
     name: String,
     version: u8,
+    modified_date: NaiveDateTime,
     items: Vec<WishListItem>,
+    keys: HashSet<(String, String)>,
+    duplicates_policy: DuplicatesPolicy,
+}
+
+/// What to do when an item already in the wish list (same brand and item
+/// number) is added again.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum DuplicatesPolicy {
+    /// Keeps a single item, merging the prices and keeping the highest priority
+    #[default]
+    Merge,
+
+    /// Leaves the existing item untouched, rejecting the new one
+    Reject,
+}
+
+/// The outcome for adding an item to a wish list.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AddItemStatus {
+    /// The item was not in the wish list
+    Added,
+
+    /// The item was already in the wish list, and it was merged with the existing one
+    Merged,
+
+    /// The item was already in the wish list, and it was rejected
+    Rejected,
 }
 
 impl WishList {
-    pub fn new(name: &str, version: u8) -> Self {
+    pub fn new(name: &str, version: u8, modified_date: NaiveDateTime) -> Self {
         WishList {
             name: name.to_owned(),
             version,
+            modified_date,
+            items: Vec::new(),
+            keys: HashSet::new(),
+            duplicates_policy: DuplicatesPolicy::default(),
+        }
+    }
+
+    /// Creates an empty wish list.
+    pub fn create_empty(name: &str) -> Self {
+        WishList {
+            name: name.to_owned(),
+            version: 1,
+            modified_date: Utc::now().naive_local(),
             items: Vec::new(),
+            keys: HashSet::new(),
+            duplicates_policy: DuplicatesPolicy::default(),
         }
     }
 
+    /// Sets the policy for the items added twice to this wish list.
+    pub fn with_duplicates_policy(mut self, policy: DuplicatesPolicy) -> Self {
+        self.duplicates_policy = policy;
+        self
+    }
+
+    /// Updates the modification fields (version and modified_date) for this wish list.
+    pub fn set_modified(
+        &mut self,
+        new_version: u8,
+        modified_date: NaiveDateTime,
+    ) {
+        self.version = new_version;
+        self.modified_date = modified_date;
+    }
+
+    /// Adds the item to this wish list, when the same brand and item number is
+    /// already included the duplicates policy decides what happens.
     pub fn add_item(
         &mut self,
         catalog_item: CatalogItem,
         priority: Priority,
         prices: Vec<PriceInfo>,
-    ) {
-        let item = WishListItem {
+    ) -> AddItemStatus {
+        self.add_wish_list_item(WishListItem::new(
             catalog_item,
             priority,
             prices,
-        };
-        self.items.push(item);
+        ))
+    }
+
+    /// Adds the item to this wish list, keeping its availability; when the
+    /// same brand and item number is already included the duplicates policy
+    /// decides what happens.
+    pub fn add_wish_list_item(&mut self, item: WishListItem) -> AddItemStatus {
+        let key = WishList::key_of(&item.catalog_item);
+        if !self.keys.contains(&key) {
+            self.keys.insert(key);
+            self.items.push(item);
+            return AddItemStatus::Added;
+        }
+
+        if self.duplicates_policy == DuplicatesPolicy::Reject {
+            return AddItemStatus::Rejected;
+        }
+
+        let existing = self
+            .items
+            .iter_mut()
+            .find(|it| it.catalog_item == item.catalog_item)
+            .expect("the wish list keys are out of sync");
+        existing.priority = cmp::min(existing.priority, item.priority);
+        existing.availability = existing.availability.or(item.availability);
+        for price in item.prices {
+            if !existing.prices.contains(&price) {
+                existing.prices.push(price);
+            }
+        }
+        AddItemStatus::Merged
+    }
+
+    fn key_of(catalog_item: &CatalogItem) -> (String, String) {
+        (
+            catalog_item.brand().name().to_owned(),
+            catalog_item.item_number().value().to_owned(),
+        )
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    pub fn modified_date(&self) -> &NaiveDateTime {
+        &self.modified_date
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
     }
 
     pub fn get_items(&self) -> &Vec<WishListItem> {
         &self.items
     }
 
+    /// Returns the currency used for every price in this wish list, the
+    /// budget cannot sum prices in different currencies.
+    pub fn currency(&self) -> Result<Currency, PriceError> {
+        Price::common_currency(
+            self.items
+                .iter()
+                .flat_map(|it| it.prices().iter().map(|p| p.price())),
+        )
+    }
+
+    /// Removes the item with the given brand (ignoring case) and item number,
+    /// returning it when found.
+    pub fn remove_item(
+        &mut self,
+        brand: &str,
+        item_number: &str,
+    ) -> Option<WishListItem> {
+        let index = self.items.iter().position(|it| {
+            let ci = it.catalog_item();
+            ci.brand().name().eq_ignore_ascii_case(brand)
+                && ci.item_number().value() == item_number
+        })?;
+        let item = self.items.remove(index);
+        self.keys.remove(&WishList::key_of(&item.catalog_item));
+        Some(item)
+    }
+
+    /// Returns the items in this wish list with the given item number, from any brand
+    pub fn find_by_item_number(&self, item_number: &str) -> Vec<&WishListItem> {
+        self.items
+            .iter()
+            .filter(|it| it.catalog_item.item_number().value() == item_number)
+            .collect()
+    }
+
+    /// Returns the items in this wish list with the given priority
+    pub fn items_by_priority(
+        &self,
+        priority: Priority,
+    ) -> impl Iterator<Item = &WishListItem> {
+        self.items.iter().filter(move |it| it.priority == priority)
+    }
+
     pub fn sort_items(&mut self) {
         self.items.sort();
     }
+
+    /// Returns the wish list items already owned in the collection, with the
+    /// same brand and item number; with `by_road_number` also the items
+    /// including a locomotive with the class name and road number of an
+    /// owned one.
+    pub fn already_owned<'a>(
+        &'a self,
+        collection: &'a Collection,
+        by_road_number: bool,
+    ) -> Vec<OwnedMatch<'a>> {
+        let mut matches = Vec::new();
+        for item in self.items.iter() {
+            if let Some(owned) =
+                collection.find_catalog_item(&item.catalog_item)
+            {
+                matches.push(OwnedMatch {
+                    item,
+                    owned,
+                    locomotive: None,
+                });
+                continue;
+            }
+
+            if !by_road_number {
+                continue;
+            }
+
+            for rs in item.catalog_item.rolling_stocks() {
+                if let (Some(class_name), Some(road_number)) =
+                    (rs.class_name(), rs.road_number())
+                {
+                    if let Some((owned, locomotive)) = collection
+                        .find_rolling_stock_by_road_number(
+                            class_name,
+                            road_number,
+                        )
+                    {
+                        matches.push(OwnedMatch {
+                            item,
+                            owned,
+                            locomotive: Some(locomotive),
+                        });
+                    }
+                }
+            }
+        }
+        matches
+    }
+}
+
+/// A wish list item matching an item already in the collection.
+#[derive(Debug)]
+pub struct OwnedMatch<'a> {
+    item: &'a WishListItem,
+    owned: &'a CollectionItem,
+    locomotive: Option<&'a RollingStock>,
+}
+
+impl<'a> OwnedMatch<'a> {
+    /// The wish list item
+    pub fn item(&self) -> &'a WishListItem {
+        self.item
+    }
+
+    /// The collection item already owned
+    pub fn owned(&self) -> &'a CollectionItem {
+        self.owned
+    }
+
+    /// The owned locomotive with the same class name and road number, `None`
+    /// when the match is by brand and item number
+    pub fn locomotive(&self) -> Option<&'a RollingStock> {
+        self.locomotive
+    }
+
+    /// Checks whether the match is by class name and road number
+    pub fn is_by_road_number(&self) -> bool {
+        self.locomotive.is_some()
+    }
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -54,9 +299,29 @@ pub struct WishListItem {
     catalog_item: CatalogItem,
     priority: Priority,
     prices: Vec<PriceInfo>,
+    availability: Option<Availability>,
 }
 
 impl WishListItem {
+    pub fn new(
+        catalog_item: CatalogItem,
+        priority: Priority,
+        prices: Vec<PriceInfo>,
+    ) -> Self {
+        WishListItem {
+            catalog_item,
+            priority,
+            prices,
+            availability: None,
+        }
+    }
+
+    /// Sets the availability for this item.
+    pub fn with_availability(mut self, availability: Availability) -> Self {
+        self.availability = Some(availability);
+        self
+    }
+
     pub fn catalog_item(&self) -> &CatalogItem {
         &self.catalog_item
     }
@@ -65,10 +330,46 @@ impl WishListItem {
         self.priority
     }
 
+    /// The availability for this item, `None` when unknown
+    pub fn availability(&self) -> Option<Availability> {
+        self.availability
+    }
+
+    /// Checks whether the item can still be purchased, the items with an
+    /// unknown availability are considered purchasable.
+    pub fn is_purchasable(&self) -> bool {
+        self.availability.map_or(true, |it| it.is_purchasable())
+    }
+
+    /// Consumes this item, returning its catalog item
+    pub fn into_catalog_item(self) -> CatalogItem {
+        self.catalog_item
+    }
+
     pub fn prices(&self) -> &Vec<PriceInfo> {
         &self.prices
     }
 
+    /// The price for this item with the given pricing strategy, using only
+    /// the latest price for each shop; `None` when the item has no prices at
+    /// all.
+    pub fn price_with(&self, pricing: PricingStrategy) -> Option<Decimal> {
+        let (min, max) = self.latest_price_range()?;
+        let price = match pricing {
+            PricingStrategy::Min => min.price().amount(),
+            PricingStrategy::Max => max.price().amount(),
+            PricingStrategy::Average => {
+                let latest = self.latest_price_per_shop();
+                let total: Decimal =
+                    latest.iter().map(|it| it.price().amount()).sum();
+                (total / Decimal::from(latest.len())).round_dp(2)
+            }
+        };
+        Some(price)
+    }
+
+    /// The price range for every price observation, including the older
+    /// prices from the same shop.
     pub fn price_range(&self) -> Option<(&PriceInfo, &PriceInfo)> {
         if self.prices.is_empty() {
             None
@@ -79,6 +380,45 @@ impl WishListItem {
             ))
         }
     }
+
+    /// The price observations for this item, sorted by date; the undated
+    /// prices come first, in their original order.
+    pub fn price_history(&self) -> Vec<&PriceInfo> {
+        let mut history: Vec<&PriceInfo> = self.prices.iter().collect();
+        history.sort_by_key(|it| it.date());
+        history
+    }
+
+    /// The latest price for each shop (the shop names differing only in case
+    /// or in the surrounding whitespace are the same shop), in the order the
+    /// shops first appear. Among the undated prices, the last one listed is
+    /// the latest.
+    pub fn latest_price_per_shop(&self) -> Vec<&PriceInfo> {
+        let mut latest: Vec<(String, &PriceInfo)> = Vec::new();
+        for price_info in self.price_history() {
+            let key = price_info.shop().trim().to_lowercase();
+            match latest.iter_mut().find(|(shop, _)| *shop == key) {
+                Some((_, it)) => *it = price_info,
+                None => latest.push((key, price_info)),
+            }
+        }
+        latest.into_iter().map(|(_, it)| it).collect()
+    }
+
+    /// The price range with only the latest price for each shop.
+    pub fn latest_price_range(&self) -> Option<(&PriceInfo, &PriceInfo)> {
+        let latest = self.latest_price_per_shop();
+        Some((latest.iter().min().copied()?, latest.iter().max().copied()?))
+    }
+
+    /// The first and the latest price observations, from any shop; `None`
+    /// with less than two observations.
+    pub fn price_trend(&self) -> Option<(&PriceInfo, &PriceInfo)> {
+        match self.price_history().as_slice() {
+            [first, .., latest] => Some((*first, *latest)),
+            _ => None,
+        }
+    }
 }
 
 impl cmp::PartialOrd for WishListItem {
@@ -101,11 +441,18 @@ pub enum Priority {
     Low,
 }
 
+impl Priority {
+    /// Returns all the priorities, from the highest to the lowest
+    pub fn values() -> [Priority; 3] {
+        [Priority::High, Priority::Normal, Priority::Low]
+    }
+}
+
 impl str::FromStr for Priority {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
+        match s.to_uppercase().as_str() {
             "HIGH" => Ok(Priority::High),
             "NORMAL" => Ok(Priority::Normal),
             "LOW" => Ok(Priority::Low),
@@ -122,10 +469,56 @@ impl fmt::Display for Priority {
     }
 }
 
+impl_serde_with_str!(Priority, "priority");
+
+/// The availability for a catalog item, from its announcement until it can
+/// no longer be found in the shops.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub enum Availability {
+    Announced,
+    Available,
+    SoldOut,
+    Discontinued,
+}
+
+impl Availability {
+    /// Checks whether the items with this availability can still be purchased
+    pub fn is_purchasable(&self) -> bool {
+        matches!(self, Availability::Announced | Availability::Available)
+    }
+}
+
+impl str::FromStr for Availability {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().replace(['-', ' '], "_").as_str() {
+            "ANNOUNCED" => Ok(Availability::Announced),
+            "AVAILABLE" => Ok(Availability::Available),
+            "SOLD_OUT" => Ok(Availability::SoldOut),
+            "DISCONTINUED" => Ok(Availability::Discontinued),
+            _ => Err(format!(
+                "Invalid availability '{}' (expected ANNOUNCED, AVAILABLE, SOLD_OUT or DISCONTINUED)",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Availability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = format!("{:?}", self);
+        write!(f, "{}", s.to_shouty_snake_case())
+    }
+}
+
+impl_serde_with_str!(Availability, "availability");
+
 #[derive(Debug, PartialEq, Eq)]
 pub struct PriceInfo {
     shop: String,
     price: Price,
+    date: Option<NaiveDate>,
 }
 
 impl PriceInfo {
@@ -133,9 +526,16 @@ impl PriceInfo {
         PriceInfo {
             shop: shop.to_owned(),
             price,
+            date: None,
         }
     }
 
+    /// Sets the date when the price was observed.
+    pub fn with_date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+
     pub fn shop(&self) -> &str {
         &self.shop
     }
@@ -143,6 +543,11 @@ impl PriceInfo {
     pub fn price(&self) -> &Price {
         &self.price
     }
+
+    /// The date when the price was observed, `None` for the undated prices
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.date
+    }
 }
 
 impl cmp::PartialOrd for PriceInfo {
@@ -157,38 +562,381 @@ impl cmp::Ord for PriceInfo {
     }
 }
 
+/// How to price the wish list items with more than one price.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum PricingStrategy {
+    /// The lowest price, the best case
+    Min,
+
+    /// The highest price, the worst case
+    #[default]
+    Max,
+
+    /// The average of the prices, rounded to two decimals
+    Average,
+}
+
+impl str::FromStr for PricingStrategy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "min" => Ok(PricingStrategy::Min),
+            "max" => Ok(PricingStrategy::Max),
+            "average" | "avg" => Ok(PricingStrategy::Average),
+            _ => Err(anyhow!(
+                "Invalid value for pricing ['min', 'max', 'average']"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for PricingStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PricingStrategy::Min => write!(f, "min"),
+            PricingStrategy::Max => write!(f, "max"),
+            PricingStrategy::Average => write!(f, "average"),
+        }
+    }
+}
+
+/// The budget required for a wish list, from the best case (every item at
+/// its lowest price) to the worst case (every item at its highest price).
+///
+/// The items sold out or discontinued are left out of the budget, unless
+/// they are explicitly included, and they are summed apart.
 #[derive(Debug)]
 pub struct WishListBudget {
-    budget: Decimal,
-    by_priority: HashMap<Priority, Decimal>,
+    currency: Currency,
+    pricing: PricingStrategy,
+    by_priority_min: HashMap<Priority, Decimal>,
+    by_priority_max: HashMap<Priority, Decimal>,
+    by_priority_average: HashMap<Priority, Decimal>,
+    unavailable: BudgetStats,
 }
 
 impl WishListBudget {
-    pub fn from_wish_list(wishlist: &WishList) -> Self {
-        let mut map: HashMap<Priority, Decimal> = HashMap::new();
+    /// Creates the budget for the wish list, with the worst case pricing.
+    pub fn from_wish_list(wishlist: &WishList) -> Result<Self, PriceError> {
+        Self::from_wish_list_with_pricing(
+            wishlist,
+            PricingStrategy::default(),
+            false,
+        )
+    }
 
-        for it in wishlist.get_items() {
-            let amount = if let Some((_, max)) = it.price_range() {
-                max.price.amount
-            } else {
-                Decimal::new(0, 0)
+    /// Creates the budget for the wish list, the overall budget uses the
+    /// given pricing strategy.
+    pub fn from_wish_list_with_pricing(
+        wishlist: &WishList,
+        pricing: PricingStrategy,
+        include_unavailable: bool,
+    ) -> Result<Self, PriceError> {
+        let currency = wishlist.currency()?;
+        let mut by_priority_min = HashMap::new();
+        let mut by_priority_max = HashMap::new();
+        let mut by_priority_average = HashMap::new();
+        let mut unavailable = BudgetStats::default();
+        if !include_unavailable {
+            for item in wishlist
+                .get_items()
+                .iter()
+                .filter(|it| !it.is_purchasable())
+            {
+                unavailable.sum(item);
+            }
+        }
+
+        for priority in Priority::values() {
+            let total = |pricing: PricingStrategy| -> Decimal {
+                wishlist
+                    .items_by_priority(priority)
+                    .filter(|it| include_unavailable || it.is_purchasable())
+                    .filter_map(|it| it.price_with(pricing))
+                    .sum()
             };
 
-            let en = map.entry(it.priority()).or_insert(amount);
-            *en += amount;
+            by_priority_min.insert(priority, total(PricingStrategy::Min));
+            by_priority_max.insert(priority, total(PricingStrategy::Max));
+            by_priority_average
+                .insert(priority, total(PricingStrategy::Average));
         }
 
-        WishListBudget {
-            budget: Decimal::new(0, 0),
-            by_priority: map,
-        }
+        Ok(WishListBudget {
+            currency,
+            pricing,
+            by_priority_min,
+            by_priority_max,
+            by_priority_average,
+            unavailable,
+        })
+    }
+
+    /// The items sold out or discontinued, left out of the budget
+    pub fn unavailable(&self) -> &BudgetStats {
+        &self.unavailable
+    }
+
+    /// The currency for the budget amounts
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// The pricing strategy for the overall budget
+    pub fn pricing(&self) -> PricingStrategy {
+        self.pricing
     }
 
+    /// The overall budget, with the pricing strategy
+    pub fn budget(&self) -> Decimal {
+        Priority::values()
+            .iter()
+            .map(|priority| self.by_priority(*priority))
+            .sum()
+    }
+
+    /// The budget for the given priority, with the pricing strategy
     pub fn by_priority(&self, priority: Priority) -> Decimal {
-        *self
-            .by_priority
+        self.by_priority_with(priority, self.pricing)
+    }
+
+    /// The budget for the given priority, with another pricing strategy
+    pub fn by_priority_with(
+        &self,
+        priority: Priority,
+        pricing: PricingStrategy,
+    ) -> Decimal {
+        match pricing {
+            PricingStrategy::Min => self.by_priority_min(priority),
+            PricingStrategy::Max => self.by_priority_max(priority),
+            PricingStrategy::Average => self.by_priority_average(priority),
+        }
+    }
+
+    /// The best case budget for the given priority, with each item at its lowest price
+    pub fn by_priority_min(&self, priority: Priority) -> Decimal {
+        self.by_priority_min
+            .get(&priority)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The worst case budget for the given priority, with each item at its highest price
+    pub fn by_priority_max(&self, priority: Priority) -> Decimal {
+        self.by_priority_max
+            .get(&priority)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The budget for the given priority, with each item at its average price
+    pub fn by_priority_average(&self, priority: Priority) -> Decimal {
+        self.by_priority_average
             .get(&priority)
-            .unwrap_or(&Decimal::new(0, 0))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// The wish list items fitting within a budget limit.
+///
+/// This is a greedy fit: the items are taken by priority (from the highest)
+/// and then by price (from the cheapest), each one is included when its price
+/// fits in what is left of the budget. The items without any price are not
+/// free, they are listed apart as unknown, while the items sold out or
+/// discontinued are left out.
+#[derive(Debug)]
+pub struct BudgetPlan<'a> {
+    currency: Currency,
+    pricing: PricingStrategy,
+    limit: Decimal,
+    fitting: Vec<(&'a WishListItem, Decimal)>,
+    not_fitting: Vec<(&'a WishListItem, Decimal)>,
+    unknown_price: Vec<&'a WishListItem>,
+}
+
+impl<'a> BudgetPlan<'a> {
+    pub fn from_wish_list(
+        wishlist: &'a WishList,
+        limit: &Price,
+        pricing: PricingStrategy,
+    ) -> Result<Self, PriceError> {
+        let currency = Price::common_currency(
+            iter::once(limit).chain(
+                wishlist
+                    .get_items()
+                    .iter()
+                    .flat_map(|it| it.prices().iter().map(|p| p.price())),
+            ),
+        )?;
+
+        let mut candidates = Vec::new();
+        let mut unknown_price = Vec::new();
+        for item in wishlist.get_items().iter().filter(|it| it.is_purchasable())
+        {
+            match item.price_with(pricing) {
+                Some(price) => candidates.push((item, price)),
+                None => unknown_price.push(item),
+            }
+        }
+        candidates.sort_by(|(a, a_price), (b, b_price)| {
+            a.priority()
+                .cmp(&b.priority())
+                .then(a_price.cmp(b_price))
+                .then(a.cmp(b))
+        });
+        unknown_price
+            .sort_by(|a, b| a.priority().cmp(&b.priority()).then(a.cmp(b)));
+
+        let mut remainder = limit.amount();
+        let mut fitting = Vec::new();
+        let mut not_fitting = Vec::new();
+        for (item, price) in candidates {
+            if price <= remainder {
+                remainder -= price;
+                fitting.push((item, price));
+            } else {
+                not_fitting.push((item, price));
+            }
+        }
+
+        Ok(BudgetPlan {
+            currency,
+            pricing,
+            limit: limit.amount(),
+            fitting,
+            not_fitting,
+            unknown_price,
+        })
+    }
+
+    /// The currency for the budget amounts
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    /// The pricing strategy for the items
+    pub fn pricing(&self) -> PricingStrategy {
+        self.pricing
+    }
+
+    /// The budget limit
+    pub fn limit(&self) -> Decimal {
+        self.limit
+    }
+
+    /// The items fitting within the budget, with their price
+    pub fn fitting(&self) -> &[(&'a WishListItem, Decimal)] {
+        &self.fitting
+    }
+
+    /// The items left out of the budget, with their price
+    pub fn not_fitting(&self) -> &[(&'a WishListItem, Decimal)] {
+        &self.not_fitting
+    }
+
+    /// The items without any price
+    pub fn unknown_price(&self) -> &[&'a WishListItem] {
+        &self.unknown_price
+    }
+
+    /// The amount spent for the items fitting within the budget
+    pub fn spent(&self) -> Decimal {
+        self.fitting.iter().map(|(_, price)| price).sum()
+    }
+
+    /// What is left of the budget limit
+    pub fn remainder(&self) -> Decimal {
+        self.limit - self.spent()
+    }
+}
+
+/// The wish list statistics, by category and by priority.
+#[derive(Debug)]
+pub struct WishListStats {
+    currency: Currency,
+    by_category: BTreeMap<Category, BudgetStats>,
+    by_priority: BTreeMap<Priority, BudgetStats>,
+    totals: BudgetStats,
+}
+
+impl WishListStats {
+    pub fn from_wish_list(wishlist: &WishList) -> Result<Self, PriceError> {
+        let currency = wishlist.currency()?;
+        let mut by_category: BTreeMap<Category, BudgetStats> =
+            Category::values()
+                .iter()
+                .map(|c| (*c, BudgetStats::default()))
+                .collect();
+        let mut by_priority: BTreeMap<Priority, BudgetStats> =
+            Priority::values()
+                .iter()
+                .map(|p| (*p, BudgetStats::default()))
+                .collect();
+        let mut totals = BudgetStats::default();
+
+        for item in wishlist.get_items() {
+            let category = item.catalog_item().category();
+            by_category.entry(category).or_default().sum(item);
+            by_priority.entry(item.priority()).or_default().sum(item);
+            totals.sum(item);
+        }
+
+        Ok(WishListStats {
+            currency,
+            by_category,
+            by_priority,
+            totals,
+        })
+    }
+
+    /// The currency for the budget amounts
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn by_category(&self) -> &BTreeMap<Category, BudgetStats> {
+        &self.by_category
+    }
+
+    pub fn by_priority(&self) -> &BTreeMap<Priority, BudgetStats> {
+        &self.by_priority
+    }
+
+    pub fn totals(&self) -> &BudgetStats {
+        &self.totals
+    }
+}
+
+/// The number of items, together with their best and worst case budget.
+#[derive(Debug, PartialEq, Default)]
+pub struct BudgetStats {
+    number_of_items: u16,
+    min_budget: Decimal,
+    max_budget: Decimal,
+}
+
+impl BudgetStats {
+    // the wish list items are counted as catalog items, a set is one item
+    fn sum(&mut self, item: &WishListItem) {
+        self.number_of_items += 1;
+        if let Some((min, max)) = item.latest_price_range() {
+            self.min_budget += min.price().amount();
+            self.max_budget += max.price().amount();
+        }
+    }
+
+    pub fn number_of_items(&self) -> u16 {
+        self.number_of_items
+    }
+
+    pub fn min_budget(&self) -> Decimal {
+        self.min_budget
+    }
+
+    pub fn max_budget(&self) -> Decimal {
+        self.max_budget
     }
 }
 
@@ -207,6 +955,13 @@ mod tests {
             assert_eq!(Priority::Normal, p.unwrap());
         }
 
+        #[test]
+        fn it_should_parse_lowercase_string_as_priority() {
+            assert_eq!(Priority::High, "high".parse::<Priority>().unwrap());
+            assert_eq!(Priority::Low, "Low".parse::<Priority>().unwrap());
+            assert!("urgent".parse::<Priority>().is_err());
+        }
+
         #[test]
         fn it_should_produce_string_representations_for_priority() {
             let p = "NORMAL".parse::<Priority>().unwrap();
@@ -220,6 +975,891 @@ mod tests {
         }
     }
 
+    mod availability_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_parse_the_availability_values() {
+            assert_eq!(Ok(Availability::Announced), "ANNOUNCED".parse());
+            assert_eq!(Ok(Availability::SoldOut), "SOLD_OUT".parse());
+            assert_eq!(Ok(Availability::SoldOut), "sold-out".parse());
+            assert_eq!(Ok(Availability::Discontinued), "Discontinued".parse());
+            assert!("gone".parse::<Availability>().is_err());
+        }
+
+        #[test]
+        fn it_should_display_the_availability_values() {
+            assert_eq!("SOLD_OUT", Availability::SoldOut.to_string());
+            assert_eq!("AVAILABLE", Availability::Available.to_string());
+        }
+
+        #[test]
+        fn it_should_tell_the_purchasable_items() {
+            assert!(Availability::Announced.is_purchasable());
+            assert!(Availability::Available.is_purchasable());
+            assert!(!Availability::SoldOut.is_purchasable());
+            assert!(!Availability::Discontinued.is_purchasable());
+        }
+    }
+
+    mod wish_list_tests {
+        use super::*;
+        use crate::domain::catalog::{
+            brands::Brand,
+            catalog_items::{ItemNumber, PowerMethod},
+            scales::Scale,
+        };
+
+        fn new_catalog_item(item_number: &str) -> CatalogItem {
+            CatalogItem::new(
+                Brand::new("ACME"),
+                ItemNumber::new(item_number).unwrap(),
+                String::from("My catalog item"),
+                vec![],
+                PowerMethod::DC,
+                Scale::H0(),
+                None,
+                1,
+            )
+        }
+
+        #[test]
+        fn it_should_find_the_wish_list_items_by_priority() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![],
+            );
+            wish_list.add_item(
+                new_catalog_item("60002"),
+                Priority::Low,
+                vec![],
+            );
+            wish_list.add_item(
+                new_catalog_item("60003"),
+                Priority::High,
+                vec![],
+            );
+
+            let items: Vec<&str> = wish_list
+                .items_by_priority(Priority::High)
+                .map(|it| it.catalog_item().item_number().value())
+                .collect();
+            assert_eq!(vec!["60001", "60003"], items);
+            assert_eq!(
+                0,
+                wish_list.items_by_priority(Priority::Normal).count()
+            );
+        }
+
+        #[test]
+        fn it_should_update_the_wish_list_modification_fields() {
+            let modified_date = NaiveDateTime::parse_from_str(
+                "2023-10-01 10:00:00",
+                "%Y-%m-%d %H:%M:%S",
+            )
+            .unwrap();
+
+            let mut wish_list = WishList::create_empty("My wish list");
+            assert_eq!(1, wish_list.version());
+
+            wish_list.set_modified(2, modified_date);
+            assert_eq!(2, wish_list.version());
+            assert_eq!(&modified_date, wish_list.modified_date());
+        }
+
+        #[test]
+        fn it_should_remove_items_from_the_wish_list() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![],
+            );
+            wish_list.add_item(
+                new_catalog_item("60002"),
+                Priority::Low,
+                vec![],
+            );
+
+            let item = wish_list.remove_item("acme", "60002");
+            assert!(item.is_some());
+            assert_eq!(
+                "60002",
+                item.unwrap().into_catalog_item().item_number().value()
+            );
+            assert_eq!(1, wish_list.get_items().len());
+
+            assert_eq!(None, wish_list.remove_item("ACME", "99999"));
+        }
+
+        #[test]
+        fn it_should_find_the_items_by_item_number() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![],
+            );
+            wish_list.add_item(
+                new_catalog_item("60002"),
+                Priority::Low,
+                vec![],
+            );
+
+            let items = wish_list.find_by_item_number("60002");
+            assert_eq!(1, items.len());
+            assert_eq!("60002", items[0].catalog_item().item_number().value());
+            assert!(wish_list.find_by_item_number("99999").is_empty());
+        }
+
+        fn new_price_info(shop: &str, amount: i64) -> PriceInfo {
+            PriceInfo::new(shop, Price::euro(Decimal::new(amount, 0)))
+        }
+
+        #[test]
+        fn it_should_merge_the_items_added_twice() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            let status = wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::Low,
+                vec![new_price_info("Treni&Treni", 195)],
+            );
+            assert_eq!(AddItemStatus::Added, status);
+
+            let status = wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![
+                    new_price_info("Treni&Treni", 195),
+                    new_price_info("Tecnomodel", 189),
+                ],
+            );
+            assert_eq!(AddItemStatus::Merged, status);
+
+            assert_eq!(1, wish_list.get_items().len());
+            let item = &wish_list.get_items()[0];
+            assert_eq!(Priority::High, item.priority());
+            assert_eq!(2, item.prices.len());
+        }
+
+        #[test]
+        fn it_should_keep_the_highest_priority_merging_items() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![],
+            );
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::Low,
+                vec![],
+            );
+
+            assert_eq!(Priority::High, wish_list.get_items()[0].priority());
+        }
+
+        #[test]
+        fn it_should_reject_the_items_added_twice_when_strict() {
+            let mut wish_list = WishList::create_empty("My wish list")
+                .with_duplicates_policy(DuplicatesPolicy::Reject);
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::Low,
+                vec![new_price_info("Treni&Treni", 195)],
+            );
+
+            let status = wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![new_price_info("Tecnomodel", 189)],
+            );
+            assert_eq!(AddItemStatus::Rejected, status);
+
+            assert_eq!(1, wish_list.get_items().len());
+            let item = &wish_list.get_items()[0];
+            assert_eq!(Priority::Low, item.priority());
+            assert_eq!(1, item.prices.len());
+        }
+
+        #[test]
+        fn it_should_add_again_the_removed_items() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::Low,
+                vec![],
+            );
+            wish_list.remove_item("ACME", "60001");
+
+            let status = wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::Low,
+                vec![],
+            );
+            assert_eq!(AddItemStatus::Added, status);
+        }
+    }
+
+    mod wish_list_budget_tests {
+        use super::*;
+        use crate::domain::catalog::{
+            brands::Brand,
+            catalog_items::{ItemNumber, PowerMethod},
+            scales::Scale,
+        };
+
+        fn new_catalog_item(item_number: &str) -> CatalogItem {
+            CatalogItem::new(
+                Brand::new("ACME"),
+                ItemNumber::new(item_number).unwrap(),
+                String::from("My catalog item"),
+                vec![],
+                PowerMethod::DC,
+                Scale::H0(),
+                None,
+                1,
+            )
+        }
+
+        fn new_price_info(shop: &str, amount: i64) -> PriceInfo {
+            PriceInfo::new(shop, Price::euro(Decimal::new(amount, 0)))
+        }
+
+        #[test]
+        fn it_should_calculate_the_best_and_worst_case_budget() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![
+                    new_price_info("Treni&Treni", 210),
+                    new_price_info("Tecnomodel", 229),
+                ],
+            );
+            wish_list.add_item(
+                new_catalog_item("60002"),
+                Priority::High,
+                vec![new_price_info("Treni&Treni", 100)],
+            );
+            wish_list.add_item(
+                new_catalog_item("60003"),
+                Priority::Low,
+                vec![
+                    new_price_info("Treni&Treni", 45),
+                    new_price_info("Tecnomodel", 50),
+                ],
+            );
+
+            let budget = WishListBudget::from_wish_list(&wish_list).unwrap();
+            assert_eq!(
+                Decimal::from(310),
+                budget.by_priority_min(Priority::High)
+            );
+            assert_eq!(
+                Decimal::from(329),
+                budget.by_priority_max(Priority::High)
+            );
+            assert_eq!(
+                Decimal::from(45),
+                budget.by_priority_min(Priority::Low)
+            );
+            assert_eq!(
+                Decimal::from(50),
+                budget.by_priority_max(Priority::Low)
+            );
+            assert_eq!(Decimal::from(379), budget.budget());
+        }
+
+        #[test]
+        fn it_should_count_the_first_item_for_each_priority_once() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![new_price_info("Treni&Treni", 200)],
+            );
+            wish_list.add_item(
+                new_catalog_item("60002"),
+                Priority::Normal,
+                vec![new_price_info("Treni&Treni", 120)],
+            );
+            wish_list.add_item(
+                new_catalog_item("60003"),
+                Priority::Low,
+                vec![new_price_info("Treni&Treni", 45)],
+            );
+
+            let budget = WishListBudget::from_wish_list(&wish_list).unwrap();
+            assert_eq!(Decimal::from(200), budget.by_priority(Priority::High));
+            assert_eq!(
+                Decimal::from(120),
+                budget.by_priority(Priority::Normal)
+            );
+            assert_eq!(Decimal::from(45), budget.by_priority(Priority::Low));
+            assert_eq!(Decimal::from(365), budget.budget());
+        }
+
+        #[test]
+        fn it_should_count_the_items_without_prices_as_zero() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::Normal,
+                vec![],
+            );
+
+            let budget = WishListBudget::from_wish_list(&wish_list).unwrap();
+            assert_eq!(Decimal::ZERO, budget.by_priority_min(Priority::Normal));
+            assert_eq!(Decimal::ZERO, budget.by_priority_max(Priority::Normal));
+            assert_eq!(Decimal::ZERO, budget.budget());
+        }
+
+        #[test]
+        fn it_should_calculate_a_zeroed_budget_for_empty_wish_lists() {
+            let wish_list = WishList::create_empty("My wish list");
+            assert!(wish_list.is_empty());
+            assert_eq!(0, wish_list.len());
+
+            let budget = WishListBudget::from_wish_list(&wish_list).unwrap();
+            for priority in Priority::values() {
+                assert_eq!(Decimal::ZERO, budget.by_priority_min(priority));
+                assert_eq!(Decimal::ZERO, budget.by_priority_max(priority));
+            }
+            assert_eq!(Decimal::ZERO, budget.budget());
+        }
+
+        fn new_unavailable_wish_list() -> WishList {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![new_price_info("Treni&Treni", 200)],
+            );
+            wish_list.add_wish_list_item(
+                WishListItem::new(
+                    new_catalog_item("60002"),
+                    Priority::High,
+                    vec![
+                        new_price_info("Treni&Treni", 100),
+                        new_price_info("Tecnomodel", 120),
+                    ],
+                )
+                .with_availability(Availability::SoldOut),
+            );
+            wish_list.add_wish_list_item(
+                WishListItem::new(
+                    new_catalog_item("60003"),
+                    Priority::Low,
+                    vec![new_price_info("Treni&Treni", 50)],
+                )
+                .with_availability(Availability::Available),
+            );
+            wish_list
+        }
+
+        #[test]
+        fn it_should_exclude_the_unavailable_items_from_the_budget() {
+            let wish_list = new_unavailable_wish_list();
+
+            let budget = WishListBudget::from_wish_list(&wish_list).unwrap();
+            assert_eq!(Decimal::from(200), budget.by_priority(Priority::High));
+            assert_eq!(Decimal::from(250), budget.budget());
+            assert_eq!(1, budget.unavailable().number_of_items());
+            assert_eq!(Decimal::from(100), budget.unavailable().min_budget());
+            assert_eq!(Decimal::from(120), budget.unavailable().max_budget());
+        }
+
+        #[test]
+        fn it_should_include_the_unavailable_items_when_requested() {
+            let wish_list = new_unavailable_wish_list();
+
+            let budget = WishListBudget::from_wish_list_with_pricing(
+                &wish_list,
+                PricingStrategy::Max,
+                true,
+            )
+            .unwrap();
+            assert_eq!(Decimal::from(370), budget.budget());
+            assert_eq!(0, budget.unavailable().number_of_items());
+        }
+
+        #[test]
+        fn it_should_leave_the_unavailable_items_out_of_the_plan() {
+            let wish_list = new_unavailable_wish_list();
+
+            let plan = BudgetPlan::from_wish_list(
+                &wish_list,
+                &Price::euro(Decimal::from(1000)),
+                PricingStrategy::Max,
+            )
+            .unwrap();
+            assert_eq!(2, plan.fitting().len());
+            assert_eq!(Decimal::from(250), plan.spent());
+        }
+
+        #[test]
+        fn it_should_keep_the_known_availability_merging_items() {
+            let mut wish_list = new_unavailable_wish_list();
+            let status = wish_list.add_wish_list_item(
+                WishListItem::new(
+                    new_catalog_item("60001"),
+                    Priority::Low,
+                    vec![],
+                )
+                .with_availability(Availability::Announced),
+            );
+            assert_eq!(AddItemStatus::Merged, status);
+
+            let item = wish_list.find_by_item_number("60001")[0];
+            assert_eq!(Some(Availability::Announced), item.availability());
+            assert_eq!(Priority::High, item.priority());
+        }
+
+        #[test]
+        fn it_should_parse_the_pricing_strategies() {
+            assert_eq!(
+                PricingStrategy::Min,
+                "min".parse::<PricingStrategy>().unwrap()
+            );
+            assert_eq!(
+                PricingStrategy::Average,
+                "Average".parse::<PricingStrategy>().unwrap()
+            );
+            assert!("cheapest".parse::<PricingStrategy>().is_err());
+            assert_eq!(PricingStrategy::Max, PricingStrategy::default());
+        }
+
+        #[test]
+        fn it_should_calculate_the_budget_with_the_pricing_strategy() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![
+                    new_price_info("Treni&Treni", 210),
+                    new_price_info("Tecnomodel", 229),
+                    new_price_info("Modellbahn", 220),
+                ],
+            );
+            wish_list.add_item(
+                new_catalog_item("60002"),
+                Priority::Low,
+                vec![new_price_info("Treni&Treni", 100)],
+            );
+
+            let budget = WishListBudget::from_wish_list_with_pricing(
+                &wish_list,
+                PricingStrategy::Min,
+                false,
+            )
+            .unwrap();
+            assert_eq!(Decimal::from(310), budget.budget());
+            assert_eq!(Decimal::from(210), budget.by_priority(Priority::High));
+
+            let budget = WishListBudget::from_wish_list_with_pricing(
+                &wish_list,
+                PricingStrategy::Average,
+                false,
+            )
+            .unwrap();
+            assert_eq!(
+                Decimal::new(21967, 2),
+                budget.by_priority(Priority::High)
+            );
+            assert_eq!(Decimal::new(31967, 2), budget.budget());
+            assert_eq!(
+                Decimal::from(229),
+                budget.by_priority_max(Priority::High)
+            );
+        }
+
+        fn new_plan_wish_list() -> WishList {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001"),
+                Priority::High,
+                vec![new_price_info("Treni&Treni", 200)],
+            );
+            wish_list.add_item(
+                new_catalog_item("60002"),
+                Priority::Low,
+                vec![new_price_info("Treni&Treni", 45)],
+            );
+            wish_list.add_item(
+                new_catalog_item("60003"),
+                Priority::Normal,
+                vec![new_price_info("Treni&Treni", 150)],
+            );
+            wish_list.add_item(
+                new_catalog_item("60004"),
+                Priority::High,
+                vec![
+                    new_price_info("Treni&Treni", 100),
+                    new_price_info("Tecnomodel", 120),
+                ],
+            );
+            wish_list.add_item(
+                new_catalog_item("60005"),
+                Priority::High,
+                vec![],
+            );
+            wish_list
+        }
+
+        fn item_numbers<'a>(
+            items: &[(&'a WishListItem, Decimal)],
+        ) -> Vec<&'a str> {
+            items
+                .iter()
+                .map(|(it, _)| it.catalog_item().item_number().value())
+                .collect()
+        }
+
+        #[test]
+        fn it_should_fit_the_items_by_priority_and_price() {
+            let wish_list = new_plan_wish_list();
+
+            let plan = BudgetPlan::from_wish_list(
+                &wish_list,
+                &Price::euro(Decimal::from(350)),
+                PricingStrategy::Min,
+            )
+            .unwrap();
+
+            assert_eq!(
+                vec!["60004", "60001", "60002"],
+                item_numbers(plan.fitting())
+            );
+            assert_eq!(vec!["60003"], item_numbers(plan.not_fitting()));
+            assert_eq!(Decimal::from(345), plan.spent());
+            assert_eq!(Decimal::from(5), plan.remainder());
+        }
+
+        #[test]
+        fn it_should_use_the_pricing_strategy_for_the_fit() {
+            let wish_list = new_plan_wish_list();
+
+            let plan = BudgetPlan::from_wish_list(
+                &wish_list,
+                &Price::euro(Decimal::from(300)),
+                PricingStrategy::Max,
+            )
+            .unwrap();
+
+            assert_eq!(vec!["60004", "60003"], item_numbers(plan.fitting()));
+            assert_eq!(
+                vec!["60001", "60002"],
+                item_numbers(plan.not_fitting())
+            );
+            assert_eq!(Decimal::from(30), plan.remainder());
+        }
+
+        #[test]
+        fn it_should_list_the_items_without_prices_as_unknown() {
+            let wish_list = new_plan_wish_list();
+
+            let plan = BudgetPlan::from_wish_list(
+                &wish_list,
+                &Price::euro(Decimal::from(1000)),
+                PricingStrategy::Max,
+            )
+            .unwrap();
+
+            assert_eq!(4, plan.fitting().len());
+            assert!(plan.not_fitting().is_empty());
+            assert_eq!(1, plan.unknown_price().len());
+            assert_eq!(
+                "60005",
+                plan.unknown_price()[0].catalog_item().item_number().value()
+            );
+            assert_eq!(Decimal::from(485), plan.remainder());
+        }
+
+        #[test]
+        fn it_should_refuse_a_limit_in_another_currency() {
+            let wish_list = new_plan_wish_list();
+
+            let result = BudgetPlan::from_wish_list(
+                &wish_list,
+                &"300 USD".parse::<Price>().unwrap(),
+                PricingStrategy::Max,
+            );
+
+            assert_eq!(
+                Some(PriceError::MixedCurrencies(Currency::USD, Currency::EUR)),
+                result.err()
+            );
+        }
+    }
+
+    mod wish_list_stats_tests {
+        use super::*;
+        use crate::domain::catalog::{
+            brands::Brand,
+            catalog_items::{ItemNumber, PowerMethod},
+            categories::LocomotiveType,
+            railways::Railway,
+            rolling_stocks::{Epoch, RollingStock, RollingStockBuilder},
+            scales::Scale,
+        };
+
+        fn new_locomotive() -> RollingStock {
+            RollingStockBuilder::locomotive()
+                .with_class_name("E.656")
+                .with_road_number("E.656 210")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_locomotive_type(LocomotiveType::ElectricLocomotive)
+                .build()
+                .unwrap()
+        }
+
+        fn new_passenger_car() -> RollingStock {
+            RollingStockBuilder::passenger_car()
+                .with_type_name("UIC-Z")
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .build()
+                .unwrap()
+        }
+
+        fn new_catalog_item(
+            item_number: &str,
+            rolling_stock: RollingStock,
+        ) -> CatalogItem {
+            CatalogItem::new(
+                Brand::new("ACME"),
+                ItemNumber::new(item_number).unwrap(),
+                String::from("My catalog item"),
+                vec![rolling_stock],
+                PowerMethod::DC,
+                Scale::H0(),
+                None,
+                1,
+            )
+        }
+
+        fn new_price_info(shop: &str, amount: i64) -> PriceInfo {
+            PriceInfo::new(shop, Price::euro(Decimal::new(amount, 0)))
+        }
+
+        #[test]
+        fn it_should_calculate_the_wish_list_stats() {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001", new_locomotive()),
+                Priority::High,
+                vec![
+                    new_price_info("Treni&Treni", 210),
+                    new_price_info("Tecnomodel", 229),
+                ],
+            );
+            wish_list.add_item(
+                new_catalog_item("60002", new_locomotive()),
+                Priority::Low,
+                vec![new_price_info("Treni&Treni", 199)],
+            );
+            wish_list.add_item(
+                new_catalog_item("45123", new_passenger_car()),
+                Priority::Low,
+                vec![],
+            );
+            wish_list.add_item(
+                CatalogItem::new(
+                    Brand::new("ACME"),
+                    ItemNumber::new("55001").unwrap(),
+                    String::from("My set"),
+                    vec![new_passenger_car(), new_passenger_car()],
+                    PowerMethod::DC,
+                    Scale::H0(),
+                    None,
+                    2,
+                ),
+                Priority::Normal,
+                vec![],
+            );
+
+            let stats = WishListStats::from_wish_list(&wish_list).unwrap();
+
+            let locomotives = &stats.by_category()[&Category::Locomotives];
+            assert_eq!(2, locomotives.number_of_items());
+            assert_eq!(Decimal::from(409), locomotives.min_budget());
+            assert_eq!(Decimal::from(428), locomotives.max_budget());
+
+            let passenger_cars = &stats.by_category()[&Category::PassengerCars];
+            assert_eq!(2, passenger_cars.number_of_items());
+            assert_eq!(Decimal::ZERO, passenger_cars.max_budget());
+
+            let low = &stats.by_priority()[&Priority::Low];
+            assert_eq!(2, low.number_of_items());
+            assert_eq!(Decimal::from(199), low.max_budget());
+
+            assert_eq!(4, stats.totals().number_of_items());
+            assert_eq!(Decimal::from(428), stats.totals().max_budget());
+        }
+
+        #[test]
+        fn it_should_calculate_all_zero_stats_for_empty_wish_lists() {
+            let wish_list = WishList::create_empty("My wish list");
+
+            let stats = WishListStats::from_wish_list(&wish_list).unwrap();
+            assert_eq!(4, stats.by_category().len());
+            assert_eq!(3, stats.by_priority().len());
+            assert!(stats
+                .by_category()
+                .values()
+                .chain(stats.by_priority().values())
+                .all(|it| it == &BudgetStats::default()));
+            assert_eq!(&BudgetStats::default(), stats.totals());
+        }
+    }
+
+    mod already_owned_tests {
+        use super::*;
+        use crate::domain::catalog::{
+            brands::Brand,
+            catalog_items::{ItemNumber, PowerMethod},
+            categories::LocomotiveType,
+            railways::Railway,
+            rolling_stocks::{Epoch, RollingStockBuilder},
+            scales::Scale,
+        };
+        use crate::domain::collecting::collections::{
+            CollectionItem, PurchasedInfo, SoldInfo,
+        };
+        use chrono::NaiveDate;
+
+        fn new_locomotive(road_number: &str) -> RollingStock {
+            RollingStockBuilder::locomotive()
+                .with_class_name("E.656")
+                .with_road_number(road_number)
+                .with_railway(Railway::new("FS"))
+                .with_epoch(Epoch::IV)
+                .with_locomotive_type(LocomotiveType::ElectricLocomotive)
+                .build()
+                .unwrap()
+        }
+
+        fn new_catalog_item(
+            item_number: &str,
+            road_number: &str,
+        ) -> CatalogItem {
+            CatalogItem::new(
+                Brand::new("ACME"),
+                ItemNumber::new(item_number).unwrap(),
+                String::from("My catalog item"),
+                vec![new_locomotive(road_number)],
+                PowerMethod::DC,
+                Scale::H0(),
+                None,
+                1,
+            )
+        }
+
+        fn new_collection() -> Collection {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_item(
+                new_catalog_item("60023", "E.656 210"),
+                PurchasedInfo::without_price(
+                    "Treni&Treni",
+                    NaiveDate::from_ymd_opt(2019, 5, 2).unwrap(),
+                ),
+            );
+            collection
+        }
+
+        fn new_wish_list() -> WishList {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60023", "E.656 210"),
+                Priority::High,
+                vec![],
+            );
+            wish_list.add_item(
+                new_catalog_item("60456", "E.656 210"),
+                Priority::Normal,
+                vec![],
+            );
+            wish_list.add_item(
+                new_catalog_item("60024", "E.656 211"),
+                Priority::Low,
+                vec![],
+            );
+            wish_list
+        }
+
+        #[test]
+        fn it_should_find_the_items_already_owned() {
+            let collection = new_collection();
+            let wish_list = new_wish_list();
+
+            let matches = wish_list.already_owned(&collection, false);
+            assert_eq!(1, matches.len());
+            assert_eq!(
+                "60023",
+                matches[0].item().catalog_item().item_number().value()
+            );
+            assert!(!matches[0].is_by_road_number());
+        }
+
+        #[test]
+        fn it_should_find_the_locomotives_already_owned_by_road_number() {
+            let collection = new_collection();
+            let wish_list = new_wish_list();
+
+            let matches = wish_list.already_owned(&collection, true);
+            assert_eq!(2, matches.len());
+
+            let by_road_number = &matches[1];
+            assert!(by_road_number.is_by_road_number());
+            assert_eq!(
+                "60456",
+                by_road_number.item().catalog_item().item_number().value()
+            );
+            assert_eq!(
+                "60023",
+                by_road_number.owned().catalog_item().item_number().value()
+            );
+            assert_eq!(
+                Some("E.656 210"),
+                by_road_number.locomotive().and_then(|it| it.road_number())
+            );
+        }
+
+        #[test]
+        fn it_should_find_nothing_for_empty_collections() {
+            let collection = Collection::create_empty("My collection");
+
+            assert!(new_wish_list()
+                .already_owned(&collection, true)
+                .is_empty());
+        }
+
+        #[test]
+        fn it_should_not_report_the_sold_items_as_owned() {
+            let mut collection = Collection::create_empty("My collection");
+            collection.add_collection_item(
+                CollectionItem::new(
+                    new_catalog_item("60023", "E.656 210"),
+                    PurchasedInfo::without_price(
+                        "Treni&Treni",
+                        NaiveDate::from_ymd_opt(2019, 5, 2).unwrap(),
+                    ),
+                )
+                .with_sold_info(SoldInfo::without_price(
+                    NaiveDate::from_ymd_opt(2021, 3, 1).unwrap(),
+                )),
+            );
+
+            assert!(new_wish_list()
+                .already_owned(&collection, true)
+                .is_empty());
+        }
+    }
+
     mod price_info_tests {
         use super::*;
 
@@ -230,6 +1870,136 @@ mod tests {
 
             assert_eq!("Treni&Treni", pi.shop());
             assert_eq!(&price, pi.price());
+            assert_eq!(None, pi.date());
+        }
+    }
+
+    mod price_history_tests {
+        use super::*;
+        use crate::domain::collecting::fixtures::new_catalog_item;
+
+        fn new_price_info(shop: &str, amount: i64, date: &str) -> PriceInfo {
+            PriceInfo::new(shop, Price::euro(Decimal::new(amount, 0)))
+                .with_date(NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap())
+        }
+
+        fn new_item(prices: Vec<PriceInfo>) -> WishListItem {
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001", vec![]),
+                Priority::High,
+                prices,
+            );
+            wish_list.items.remove(0)
+        }
+
+        fn amounts(prices: &[&PriceInfo]) -> Vec<Decimal> {
+            prices.iter().map(|it| it.price().amount()).collect()
+        }
+
+        #[test]
+        fn it_should_sort_the_price_history_by_date() {
+            let item = new_item(vec![
+                new_price_info("Treni&Treni", 229, "2023-05-01"),
+                new_price_info("Tecnomodel", 210, "2023-01-10"),
+                PriceInfo::new("Tecnomodel", Price::euro(Decimal::from(250))),
+            ]);
+
+            assert_eq!(
+                vec![
+                    Decimal::from(250),
+                    Decimal::from(210),
+                    Decimal::from(229)
+                ],
+                amounts(&item.price_history())
+            );
+        }
+
+        #[test]
+        fn it_should_keep_the_latest_price_for_each_shop() {
+            let item = new_item(vec![
+                new_price_info("Treni&Treni", 199, "2023-05-01"),
+                new_price_info("Tecnomodel", 210, "2023-01-10"),
+                new_price_info("Treni&Treni", 249, "2023-01-01"),
+                new_price_info(" tecnomodel", 229, "2023-06-10"),
+            ]);
+
+            let latest = item.latest_price_per_shop();
+            assert_eq!(
+                vec![Decimal::from(199), Decimal::from(229)],
+                amounts(&latest)
+            );
+
+            let (min, max) = item.latest_price_range().unwrap();
+            assert_eq!(Decimal::from(199), min.price().amount());
+            assert_eq!(Decimal::from(229), max.price().amount());
+            let (min, max) = item.price_range().unwrap();
+            assert_eq!(Decimal::from(199), min.price().amount());
+            assert_eq!(Decimal::from(249), max.price().amount());
+        }
+
+        #[test]
+        fn it_should_price_the_items_with_the_latest_price_from_each_shop() {
+            let prices = || {
+                vec![
+                    new_price_info("Treni&Treni", 249, "2023-01-01"),
+                    new_price_info("Treni&Treni", 199, "2023-05-01"),
+                ]
+            };
+            let item = new_item(prices());
+
+            assert_eq!(
+                Some(Decimal::from(199)),
+                item.price_with(PricingStrategy::Max)
+            );
+            assert_eq!(
+                Some(Decimal::from(199)),
+                item.price_with(PricingStrategy::Average)
+            );
+
+            let mut wish_list = WishList::create_empty("My wish list");
+            wish_list.add_item(
+                new_catalog_item("60001", vec![]),
+                Priority::High,
+                prices(),
+            );
+            let stats = WishListStats::from_wish_list(&wish_list).unwrap();
+            assert_eq!(Decimal::from(199), stats.totals().min_budget());
+            assert_eq!(Decimal::from(199), stats.totals().max_budget());
+        }
+
+        #[test]
+        fn it_should_take_the_last_undated_price_as_the_latest() {
+            let item = new_item(vec![
+                PriceInfo::new("Treni&Treni", Price::euro(Decimal::from(229))),
+                PriceInfo::new("Treni&Treni", Price::euro(Decimal::from(219))),
+            ]);
+
+            assert_eq!(
+                vec![Decimal::from(219)],
+                amounts(&item.latest_price_per_shop())
+            );
+        }
+
+        #[test]
+        fn it_should_calculate_the_price_trend() {
+            let item = new_item(vec![
+                new_price_info("Treni&Treni", 229, "2023-05-01"),
+                new_price_info("Tecnomodel", 210, "2023-01-10"),
+                new_price_info("Treni&Treni", 199, "2023-09-01"),
+            ]);
+
+            let (first, latest) = item.price_trend().unwrap();
+            assert_eq!(Decimal::from(210), first.price().amount());
+            assert_eq!(Decimal::from(199), latest.price().amount());
+
+            let item = new_item(vec![new_price_info(
+                "Treni&Treni",
+                229,
+                "2023-05-01",
+            )]);
+            assert!(item.price_trend().is_none());
+            assert!(new_item(vec![]).latest_price_range().is_none());
         }
     }
 }
@@ -3,5 +3,41 @@
 //! The domain is separated in two main areas:
 //! * catalog: contains the basic information for a railway models;
 //! * collecting: everything related to collecting models, collections and wishlists.
+
+/// Implements `Serialize` and `Deserialize` for a value type using its string
+/// representation (`Display` and `FromStr`), the `$name` is used in the error
+/// messages for the invalid values. The optional `$to_string` replaces
+/// `Display` for the serialization, when the displayed value is lossy.
+macro_rules! impl_serde_with_str {
+    ($type:ty, $name:expr) => {
+        impl_serde_with_str!($type, $name, |it: &$type| it.to_string());
+    };
+    ($type:ty, $name:expr, $to_string:expr) => {
+        impl serde::Serialize for $type {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_str(&$to_string(self))
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = String::deserialize(deserializer)?;
+                value.parse::<$type>().map_err(|e| {
+                    serde::de::Error::custom(format!(
+                        "Invalid {} '{}': {}",
+                        $name, value, e
+                    ))
+                })
+            }
+        }
+    };
+}
+
 pub mod catalog;
 pub mod collecting;
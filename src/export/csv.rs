@@ -0,0 +1,207 @@
+//! This module contains the CSV exporter for collections.
+use crate::domain::collecting::collections::{Collection, TopPurchases};
+use crate::domain::collecting::valuations::InsuranceReport;
+use itertools::Itertools;
+use std::io::Write;
+
+/// How the purchase prices are written in the CSV records.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PriceColumns {
+    /// Separate `Amount` and `Currency` columns, ready for spreadsheet math.
+    Split,
+
+    /// A single `Price` column, with the amount followed by the currency.
+    Combined,
+
+    /// A single `Price` column, with the raw amount and no currency.
+    Numeric,
+}
+
+/// Writes the collection items as CSV records, with a header row.
+pub fn write_collection<W: Write>(
+    writer: W,
+    collection: &Collection,
+    price_columns: PriceColumns,
+) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(writer);
+
+    let mut header = vec![
+        "Brand",
+        "ItemNumber",
+        "Category",
+        "Description",
+        "Epoch",
+        "Scale",
+        "PowerMethod",
+        "Shop",
+        "Date",
+        "Count",
+    ];
+    match price_columns {
+        PriceColumns::Split => header.extend(["Amount", "Currency"]),
+        PriceColumns::Combined | PriceColumns::Numeric => header.push("Price"),
+    }
+    wtr.write_record(&header)?;
+
+    for it in collection.get_items().iter() {
+        let catalog_item = it.catalog_item();
+        let purchase = it.purchased_info();
+
+        let mut record = vec![
+            catalog_item.brand().name().to_owned(),
+            catalog_item.item_number().value().to_owned(),
+            catalog_item.category().to_string(),
+            catalog_item.description().to_owned(),
+            catalog_item.epochs().iter().join(", "),
+            catalog_item.scale().name().to_owned(),
+            catalog_item.power_method().to_string(),
+            purchase.shop().to_owned(),
+            purchase.purchased_date().format("%Y-%m-%d").to_string(),
+            catalog_item.count().to_string(),
+        ];
+        match (price_columns, purchase.price()) {
+            (PriceColumns::Split, Some(price)) => {
+                record.push(format!("{:.2}", price.rounded().amount()));
+                record.push(price.currency().to_string());
+            }
+            (PriceColumns::Split, None) => {
+                record.push(String::new());
+                record.push(String::new());
+            }
+            (PriceColumns::Combined, price) => {
+                record.push(price.map(|p| p.to_string()).unwrap_or_default())
+            }
+            (PriceColumns::Numeric, price) => record.push(
+                price.map(|p| p.amount().to_string()).unwrap_or_default(),
+            ),
+        }
+        wtr.write_record(&record)?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Writes the most expensive purchases as CSV records, with a header row.
+pub fn write_top_purchases<W: Write>(
+    writer: W,
+    top: &TopPurchases,
+) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(writer);
+
+    wtr.write_record([
+        "Brand",
+        "ItemNumber",
+        "Description",
+        "Shop",
+        "Date",
+        "Amount",
+        "Currency",
+        "Percentage",
+    ])?;
+
+    for purchase in top.purchases() {
+        let catalog_item = purchase.item().catalog_item();
+        let purchase_info = purchase.item().purchased_info();
+
+        wtr.write_record([
+            catalog_item.brand().name().to_owned(),
+            catalog_item.item_number().value().to_owned(),
+            catalog_item.description().to_owned(),
+            purchase_info.shop().to_owned(),
+            purchase_info
+                .purchased_date()
+                .format("%Y-%m-%d")
+                .to_string(),
+            purchase_info
+                .price()
+                .map(|p| format!("{:.2}", p.rounded().amount()))
+                .unwrap_or_default(),
+            top.currency().to_string(),
+            format!("{:.2}", purchase.percentage()),
+        ])?;
+    }
+
+    wtr.flush()?;
+    Ok(())
+}
+
+/// Writes the insurance report as CSV records, with a header row and a last
+/// row with the grand totals; the items without any value are written before
+/// the totals, with empty prices.
+pub fn write_insurance_report<W: Write>(
+    writer: W,
+    report: &InsuranceReport,
+) -> anyhow::Result<()> {
+    let mut wtr = csv::Writer::from_writer(writer);
+    let currency = report.currency().to_string();
+
+    wtr.write_record([
+        "Brand",
+        "ItemNumber",
+        "Description",
+        "RoadNumbers",
+        "PurchaseDate",
+        "PurchasePrice",
+        "ReplacementValue",
+        "Currency",
+    ])?;
+
+    for insured in report.items() {
+        let catalog_item = insured.item().catalog_item();
+        let purchase = insured.item().purchased_info();
+
+        wtr.write_record([
+            catalog_item.brand().name().to_owned(),
+            catalog_item.item_number().value().to_owned(),
+            catalog_item.description().to_owned(),
+            catalog_item
+                .rolling_stocks()
+                .iter()
+                .filter_map(|rs| rs.road_number())
+                .join("; "),
+            purchase.purchased_date().format("%Y-%m-%d").to_string(),
+            purchase
+                .price()
+                .map(|p| format!("{:.2}", p.rounded().amount()))
+                .unwrap_or_default(),
+            format!("{:.2}", insured.replacement_value()),
+            currency.clone(),
+        ])?;
+    }
+
+    for item in report.unknown_value() {
+        let catalog_item = item.catalog_item();
+        wtr.write_record([
+            catalog_item.brand().name().to_owned(),
+            catalog_item.item_number().value().to_owned(),
+            catalog_item.description().to_owned(),
+            catalog_item
+                .rolling_stocks()
+                .iter()
+                .filter_map(|rs| rs.road_number())
+                .join("; "),
+            item.purchased_info()
+                .purchased_date()
+                .format("%Y-%m-%d")
+                .to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+        ])?;
+    }
+
+    wtr.write_record([
+        "TOTAL".to_owned(),
+        String::new(),
+        String::new(),
+        String::new(),
+        String::new(),
+        format!("{:.2}", report.purchase_total()),
+        format!("{:.2}", report.replacement_total()),
+        currency,
+    ])?;
+
+    wtr.flush()?;
+    Ok(())
+}
@@ -0,0 +1,219 @@
+//! This module contains the JMRI roster exporter for the depot.
+use std::collections::HashMap;
+use std::io::Write;
+
+use crate::domain::collecting::collections::DepotCard;
+
+/// Writes the depot cards as a JMRI roster (`roster.xml`).
+///
+/// Every card becomes a `<locomotive>` entry. Locomotives without a decoder are still
+/// written, flagged in the comment and in the `decoder` attribute pair. JMRI requires
+/// unique roster ids, so repeated road numbers get a numeric suffix.
+pub fn write_roster<W: Write>(
+    mut writer: W,
+    cards: &[DepotCard],
+    owner: &str,
+) -> anyhow::Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<roster-config xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xsi:noNamespaceSchemaLocation="http://jmri.org/xml/schema/roster.xsd">"#
+    )?;
+    writeln!(writer, "  <roster>")?;
+
+    let mut ids: HashMap<String, usize> = HashMap::new();
+    for card in cards {
+        let id = roster_id(&mut ids, card);
+        write_locomotive(&mut writer, card, &id, owner)?;
+    }
+
+    writeln!(writer, "  </roster>")?;
+    writeln!(writer, "</roster-config>")?;
+
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_locomotive<W: Write>(
+    writer: &mut W,
+    card: &DepotCard,
+    id: &str,
+    owner: &str,
+) -> anyhow::Result<()> {
+    let comment = if card.with_decoder() {
+        ""
+    } else {
+        "No decoder installed"
+    };
+
+    writeln!(
+        writer,
+        r#"    <locomotive id="{}" fileName="{}" roadNumber="{}" roadName="{}" mfg="{}" owner="{}" model="{}" dccAddress="{}" comment="{}">"#,
+        escape(id),
+        escape(&file_name(id)),
+        escape(card.road_number()),
+        escape(card.railway().name()),
+        escape(card.brand()),
+        escape(owner),
+        escape(&model(card)),
+        card.dcc_address()
+            .map(|address| address.to_string())
+            .unwrap_or_default(),
+        comment,
+    )?;
+    writeln!(writer, "      <attributepairs>")?;
+    write_key_value(
+        writer,
+        "decoder",
+        if card.with_decoder() { "yes" } else { "no" },
+    )?;
+    if let Some(dcc) = card.dcc_interface() {
+        write_key_value(writer, "dccInterface", &dcc.to_string())?;
+    }
+    write_key_value(writer, "itemNumber", card.item_number().value())?;
+    writeln!(writer, "      </attributepairs>")?;
+    writeln!(writer, "    </locomotive>")?;
+    Ok(())
+}
+
+fn write_key_value<W: Write>(
+    writer: &mut W,
+    key: &str,
+    value: &str,
+) -> anyhow::Result<()> {
+    writeln!(
+        writer,
+        "        <keyvaluepair><key>{}</key><value>{}</value></keyvaluepair>",
+        escape(key),
+        escape(value)
+    )?;
+    Ok(())
+}
+
+// The roster id is the road number (or the class name, when missing), any
+// repeated id is disambiguated with a progressive suffix.
+fn roster_id(ids: &mut HashMap<String, usize>, card: &DepotCard) -> String {
+    let base = if card.road_number().is_empty() {
+        card.class_name().to_owned()
+    } else {
+        card.road_number().to_owned()
+    };
+
+    let count = ids.entry(base.clone()).or_insert(0);
+    *count += 1;
+    if *count == 1 {
+        base
+    } else {
+        format!("{} ({})", base, count)
+    }
+}
+
+fn file_name(id: &str) -> String {
+    let name: String = id
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("{}.xml", name)
+}
+
+fn model(card: &DepotCard) -> String {
+    match card.series() {
+        Some(series) => format!("{} {}", card.class_name(), series),
+        None => card.class_name().to_owned(),
+    }
+}
+
+fn escape(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&apos;"),
+            _ => output.push(c),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod jmri_roster_tests {
+        use super::*;
+        use crate::domain::catalog::{
+            catalog_items::ItemNumber,
+            railways::Railway,
+            rolling_stocks::{DccAddress, DccInterface, Epoch},
+        };
+
+        fn new_card(
+            road_number: &str,
+            series: Option<&str>,
+            with_decoder: bool,
+            dcc_interface: Option<DccInterface>,
+            dcc_address: Option<DccAddress>,
+        ) -> DepotCard {
+            DepotCard::new(
+                "E.656",
+                road_number,
+                series,
+                Some("blu/grigio"),
+                &Railway::new("FS"),
+                &Epoch::IV,
+                "ACME",
+                &ItemNumber::new("60023").unwrap(),
+                with_decoder,
+                dcc_interface,
+                dcc_address,
+                None,
+            )
+        }
+
+        fn roster_as_string(cards: &[DepotCard], owner: &str) -> String {
+            let mut output: Vec<u8> = Vec::new();
+            write_roster(&mut output, cards, owner).unwrap();
+            String::from_utf8(output).unwrap()
+        }
+
+        #[test]
+        fn it_should_write_depot_cards_as_jmri_roster() {
+            let cards = vec![
+                new_card(
+                    "E.656 210",
+                    Some("1a serie"),
+                    true,
+                    Some(DccInterface::Nem652),
+                    DccAddress::new(656).ok(),
+                ),
+                new_card("E.656 291", None, false, None, None),
+            ];
+
+            let roster = roster_as_string(&cards, "Carlo & Co.");
+            assert_eq!(include_str!("testdata/jmri/roster.xml"), roster);
+        }
+
+        #[test]
+        fn it_should_write_unique_ids_for_duplicated_road_numbers() {
+            let cards = vec![
+                new_card("E.656 210", None, true, None, None),
+                new_card("E.656 210", None, false, None, None),
+            ];
+
+            let roster = roster_as_string(&cards, "");
+            assert_eq!(
+                include_str!("testdata/jmri/roster_duplicates.xml"),
+                roster
+            );
+        }
+
+        #[test]
+        fn it_should_write_an_empty_roster() {
+            let roster = roster_as_string(&[], "");
+            assert!(roster.contains("<roster>\n  </roster>"));
+        }
+    }
+}
@@ -0,0 +1,237 @@
+//! This module contains the helpers shared by the JSON exporters.
+use itertools::Itertools;
+use serde::Serialize;
+use std::io::{self, IsTerminal, Write};
+
+use crate::domain::collecting::collections::{Collection, CollectionItem};
+
+/// The layout for the JSON output.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum JsonStyle {
+    /// Human readable output, indented with two spaces.
+    Pretty,
+
+    /// Everything on a single line, for piping into other tools.
+    Compact,
+}
+
+impl JsonStyle {
+    /// Returns the style from the `--pretty`/`--compact` flags, when none is provided
+    /// the output is pretty for an interactive terminal and compact otherwise.
+    pub fn from_flags(pretty: bool, compact: bool) -> Self {
+        if pretty {
+            JsonStyle::Pretty
+        } else if compact || !io::stdout().is_terminal() {
+            JsonStyle::Compact
+        } else {
+            JsonStyle::Pretty
+        }
+    }
+}
+
+/// Writes the value as a JSON document with the given style.
+pub fn write_json<W: Write, T: Serialize>(
+    mut writer: W,
+    value: &T,
+    style: JsonStyle,
+) -> anyhow::Result<()> {
+    match style {
+        JsonStyle::Pretty => serde_json::to_writer_pretty(&mut writer, value)?,
+        JsonStyle::Compact => serde_json::to_writer(&mut writer, value)?,
+    }
+    writeln!(writer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the values as newline delimited JSON, one compact document per line.
+pub fn write_ndjson<'a, W, T, I>(mut writer: W, values: I) -> anyhow::Result<()>
+where
+    W: Write,
+    T: Serialize + 'a,
+    I: IntoIterator<Item = &'a T>,
+{
+    for value in values {
+        serde_json::to_writer(&mut writer, value)?;
+        writeln!(writer)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The collection JSON document: a flat list of items, independent from the
+/// YAML file layout.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonCollection {
+    /// The collection description
+    pub description: String,
+    /// The collection version
+    pub version: u8,
+    /// The last modified date, as `YYYY-MM-DD HH:MM:SS`
+    pub modified_at: String,
+    /// The collection items
+    pub items: Vec<JsonCollectionItem>,
+}
+
+/// A collection item, with its catalog and purchase fields.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonCollectionItem {
+    /// The brand name
+    pub brand: String,
+    /// The item number, as printed in the brand catalog
+    pub item_number: String,
+    /// The item category symbol (like `L` for locomotives or `P` for passenger cars)
+    pub category: String,
+    /// The item description
+    pub description: String,
+    /// The epochs for the rolling stocks, comma separated
+    pub epoch: String,
+    /// The scale name (like `H0` or `N`)
+    pub scale: String,
+    /// The power method (`AC` or `DC`)
+    pub power_method: String,
+    /// The number of rolling stocks
+    pub count: u8,
+    /// The shop where the item was purchased
+    pub shop: String,
+    /// The purchase date, as `YYYY-MM-DD`
+    pub date: String,
+    /// The purchase price amount with two decimals, `null` when unknown
+    pub price: Option<String>,
+    /// The purchase price currency code, `null` when the price is unknown
+    pub currency: Option<String>,
+}
+
+impl From<&Collection> for JsonCollection {
+    fn from(collection: &Collection) -> Self {
+        JsonCollection {
+            description: collection.description().to_owned(),
+            version: collection.version(),
+            modified_at: collection
+                .modified_date()
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+            items: collection
+                .get_items()
+                .iter()
+                .map(JsonCollectionItem::from)
+                .collect(),
+        }
+    }
+}
+
+impl From<&CollectionItem> for JsonCollectionItem {
+    fn from(item: &CollectionItem) -> Self {
+        let catalog_item = item.catalog_item();
+        let purchase = item.purchased_info();
+
+        JsonCollectionItem {
+            brand: catalog_item.brand().name().to_owned(),
+            item_number: catalog_item.item_number().value().to_owned(),
+            category: catalog_item.category().to_string(),
+            description: catalog_item.description().to_owned(),
+            epoch: catalog_item.epochs().iter().join(", "),
+            scale: catalog_item.scale().name().to_owned(),
+            power_method: catalog_item.power_method().to_string(),
+            count: catalog_item.count(),
+            shop: purchase.shop().to_owned(),
+            date: purchase.purchased_date().format("%Y-%m-%d").to_string(),
+            price: purchase
+                .price()
+                .map(|p| format!("{:.2}", p.rounded().amount())),
+            currency: purchase.price().map(|p| p.currency().to_string()),
+        }
+    }
+}
+
+/// Writes the collection as a JSON document with the given style.
+pub fn write_collection<W: Write>(
+    writer: W,
+    collection: &Collection,
+    style: JsonStyle,
+) -> anyhow::Result<()> {
+    write_json(writer, &JsonCollection::from(collection), style)
+}
+
+/// Writes the collection items as newline delimited JSON, one item per line.
+pub fn write_collection_items<W: Write>(
+    writer: W,
+    collection: &Collection,
+) -> anyhow::Result<()> {
+    let items: Vec<JsonCollectionItem> = collection
+        .get_items()
+        .iter()
+        .map(JsonCollectionItem::from)
+        .collect();
+    write_ndjson(writer, &items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod json_tests {
+        use super::*;
+
+        #[derive(Serialize)]
+        struct Item {
+            brand: String,
+            count: u8,
+        }
+
+        fn new_item(brand: &str) -> Item {
+            Item {
+                brand: brand.to_owned(),
+                count: 1,
+            }
+        }
+
+        fn as_string(style: JsonStyle) -> String {
+            let mut output: Vec<u8> = Vec::new();
+            write_json(&mut output, &vec![new_item("ACME")], style).unwrap();
+            String::from_utf8(output).unwrap()
+        }
+
+        #[test]
+        fn it_should_write_pretty_json() {
+            let json = as_string(JsonStyle::Pretty);
+            assert_eq!(
+                "[\n  {\n    \"brand\": \"ACME\",\n    \"count\": 1\n  }\n]\n",
+                json
+            );
+        }
+
+        #[test]
+        fn it_should_write_compact_json() {
+            let json = as_string(JsonStyle::Compact);
+            assert_eq!("[{\"brand\":\"ACME\",\"count\":1}]\n", json);
+            assert!(!json.trim_end().contains('\n'));
+            assert!(!json.contains("  "));
+        }
+
+        #[test]
+        fn it_should_write_one_compact_document_per_line_as_ndjson() {
+            let items = vec![new_item("ACME"), new_item("Roco")];
+            let mut output: Vec<u8> = Vec::new();
+            write_ndjson(&mut output, &items).unwrap();
+
+            let ndjson = String::from_utf8(output).unwrap();
+            let lines: Vec<&str> = ndjson.lines().collect();
+            assert_eq!(
+                vec![
+                    "{\"brand\":\"ACME\",\"count\":1}",
+                    "{\"brand\":\"Roco\",\"count\":1}"
+                ],
+                lines
+            );
+        }
+
+        #[test]
+        fn it_should_prefer_the_explicit_flags() {
+            assert_eq!(JsonStyle::Pretty, JsonStyle::from_flags(true, false));
+            assert_eq!(JsonStyle::Compact, JsonStyle::from_flags(false, true));
+        }
+    }
+}
@@ -0,0 +1,5 @@
+//! The export module
+//! Contains the writers to share the collection data with other tools.
+pub mod csv;
+pub mod jmri;
+pub mod json;
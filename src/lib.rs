@@ -0,0 +1,21 @@
+//! A library to manage model railway collections and wish lists.
+//!
+//! The crate is organised in the following modules:
+//! * `domain`: the catalog and collecting domain model;
+//! * `data_source`: the loaders for the YAML collection and wish list files;
+//! * `tables`: the tabular representations used by the command line application;
+//! * `export`: the exporters to other applications formats.
+
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_derive;
+#[macro_use]
+extern crate prettytable;
+#[macro_use]
+extern crate anyhow;
+
+pub mod data_source;
+pub mod domain;
+pub mod export;
+pub mod tables;
@@ -1,64 +1,126 @@
-use prettytable::{table, Table};
+use heck::ToTitleCase;
+use prettytable::{Cell, Row, Table};
 use rust_decimal::prelude::*;
+use std::collections::BTreeMap;
+use unicode_segmentation::UnicodeSegmentation;
 
+use crate::domain::catalog::railways::Railway;
 use crate::domain::collecting::{
     collections::{
-        Collection, CollectionStats, Depot, Year, YearlyCollectionStats,
+        Collection, CollectionItem, CollectionStats, CollectionStatsByBrand,
+        CollectionStatsByRailway, CollectionStatsByScale, Depot, OtherGroups,
+        PriceDistribution, PurchasesByMonth, Roster, SalesReport, SearchMatch,
+        SpendingTimeline, TopPurchases,
     },
-    wish_lists::WishList,
+    shops::ShopStats,
+    valuations::{CollectionValuation, CurrentValueReport, ReferencePrices},
+    wish_lists::{
+        Availability, BudgetPlan, OwnedMatch, PricingStrategy, Priority,
+        WishList, WishListBudget, WishListItem, WishListStats,
+    },
+    Price,
 };
 
+/// The default max width for the descriptions in the tables.
+pub const DEFAULT_DESCRIPTION_WIDTH: usize = 50;
+
 pub trait AsTable {
-    fn to_table(self) -> Table;
+    fn to_table(&self) -> Table;
 }
 
 impl AsTable for WishList {
-    fn to_table(mut self) -> Table {
-        self.sort_items();
+    fn to_table(&self) -> Table {
+        let mut items: Vec<&WishListItem> = self.get_items().iter().collect();
+        items.sort();
+        wish_list_items_table(items, DEFAULT_DESCRIPTION_WIDTH)
+    }
+}
 
-        let mut table = Table::new();
-        table.add_row(row![
-            "#",
-            "Brand",
-            "Item number",
-            "Cat.",
-            "Priority",
-            "Scale",
-            "PM",
-            "Description",
-            "Count",
-            "Price range",
-        ]);
+/// Returns the table for the given wish list items, truncating the
+/// descriptions to `description_width` characters (0 means no truncation).
+pub fn wish_list_items_table<'a, I>(items: I, description_width: usize) -> Table
+where
+    I: IntoIterator<Item = &'a WishListItem>,
+{
+    let mut table = Table::new();
+    table.add_row(row![
+        "#",
+        "Brand",
+        "Item number",
+        "Cat.",
+        "Priority",
+        "Availability",
+        "Scale",
+        "PM",
+        "Description",
+        "Count",
+        "Price range",
+    ]);
 
-        for (ind, it) in self.get_items().iter().enumerate() {
-            let ci = it.catalog_item();
+    for (ind, it) in items.into_iter().enumerate() {
+        let ci = it.catalog_item();
 
-            let price_range = if let Some((min, max)) = it.price_range() {
-                format!("from {} to {}", min.price(), max.price())
-            } else {
-                String::from("-")
-            };
+        let price_range = if let Some((min, max)) = it.latest_price_range() {
+            format!("from {} to {}", min.price(), max.price())
+        } else {
+            String::from("-")
+        };
 
-            table.add_row(row![
-                ind + 1,
-                b -> ci.brand().name(),
-                ci.item_number(),
-                c -> ci.category(),
-                c -> it.priority().to_string(),
-                ci.scale(),
-                ci.power_method(),
-                i -> substring(ci.description()),
-                r -> ci.count(),
-                c -> price_range,
-            ]);
+        table.add_row(Row::new(vec![
+            cell!(ind + 1),
+            cell!(b -> ci.brand().name()),
+            cell!(ci.item_number()),
+            cell!(c -> ci.category()),
+            cell!(c -> it.priority()),
+            availability_cell(it.availability()),
+            cell!(ci.scale()),
+            cell!(ci.power_method()),
+            cell!(i -> truncate(ci.description(), description_width)),
+            cell!(r -> ci.count()),
+            cell!(c -> price_range),
+        ]));
+    }
+
+    table
+}
+
+/// Returns the colored cell for the availability: blue when announced, green
+/// when available, yellow when sold out and red when discontinued.
+fn availability_cell(availability: Option<Availability>) -> Cell {
+    match availability {
+        Some(availability) => {
+            let style = match availability {
+                Availability::Announced => "cFb",
+                Availability::Available => "cFg",
+                Availability::SoldOut => "cFy",
+                Availability::Discontinued => "cFr",
+            };
+            Cell::new(&availability.to_string()).style_spec(style)
         }
+        None => Cell::new("-").style_spec("c"),
+    }
+}
 
-        table
+/// Returns the table for the price observations of a wish list item, sorted
+/// by date.
+pub fn price_history_table(item: &WishListItem) -> Table {
+    let mut table = Table::new();
+    table.add_row(row!["#", "Date", "Shop", "Price"]);
+
+    for (id, it) in item.price_history().iter().enumerate() {
+        table.add_row(row![
+            c -> (id + 1).to_string(),
+            it.date().map_or(String::from("-"), |d| d.to_string()),
+            it.shop(),
+            r -> it.price(),
+        ]);
     }
+
+    table
 }
 
 impl AsTable for Depot {
-    fn to_table(self) -> Table {
+    fn to_table(&self) -> Table {
         let mut table = Table::new();
 
         table.add_row(row![
@@ -67,10 +129,14 @@ impl AsTable for Depot {
             "Road number",
             "Series",
             "Livery",
+            "Railway",
+            "Epoch",
             "Brand",
             "Item Number",
             "With decoder",
             "DCC",
+            "Address",
+            "Decoder",
         ]);
 
         for (id, card) in self.locomotives().iter().enumerate() {
@@ -82,12 +148,88 @@ impl AsTable for Depot {
                 card.road_number().to_string(),
                 card.series().unwrap_or_default(),
                 card.livery().unwrap_or_default(),
+                railway_with_country(card.railway()),
+                c -> card.epoch().to_string(),
                 card.brand().to_string(),
                 card.item_number().to_string(),
                 c -> with_dec.to_string(),
                 c -> card.dcc_interface()
                     .map(|dcc| dcc.to_string())
                     .unwrap_or_default(),
+                r -> card.dcc_address()
+                    .map(|address| address.to_string())
+                    .unwrap_or_default(),
+                card.decoder()
+                    .map(|decoder| decoder.to_string())
+                    .unwrap_or_default(),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl AsTable for Roster<'_> {
+    fn to_table(&self) -> Table {
+        let mut table = Table::new();
+
+        table.add_row(row![
+            "#",
+            "Class/type name",
+            "Road number",
+            "Railway",
+            "Epoch",
+            "Category",
+            "Livery",
+            "Length",
+        ]);
+
+        for (id, rs) in self.rolling_stocks().iter().enumerate() {
+            table.add_row(row![
+                c -> (id + 1).to_string(),
+                b -> Roster::name(rs),
+                rs.road_number().unwrap_or_default(),
+                railway_with_country(rs.railway()),
+                c -> rs.epoch().to_string(),
+                c -> rs.category(),
+                rs.livery().unwrap_or_default(),
+                r -> rs.length_over_buffer()
+                    .map(|length| length.to_string())
+                    .unwrap_or_default(),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl AsTable for TopPurchases<'_> {
+    fn to_table(&self) -> Table {
+        let mut table = Table::new();
+
+        table.add_row(row![
+            "#",
+            "Brand",
+            "Item number",
+            "Description",
+            "Shop",
+            "Date",
+            format!("Price ({})", self.currency()),
+            "%",
+        ]);
+
+        for (id, purchase) in self.purchases().iter().enumerate() {
+            let ci = purchase.item().catalog_item();
+            let pi = purchase.item().purchased_info();
+            table.add_row(row![
+                c -> (id + 1).to_string(),
+                b -> ci.brand().name(),
+                ci.item_number().value(),
+                truncate(ci.description(), DEFAULT_DESCRIPTION_WIDTH),
+                pi.shop(),
+                c -> pi.purchased_date().format("%Y-%m-%d"),
+                r -> pi.price().map(|p| amount(p.amount())).unwrap_or_default(),
+                r -> amount(purchase.percentage()),
             ]);
         }
 
@@ -96,104 +238,1100 @@ impl AsTable for Depot {
 }
 
 impl AsTable for CollectionStats {
-    fn to_table(self) -> Table {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
         let mut table = Table::new();
         table.add_row(row![
             "Year",
             "Locomotives (no.)",
-            "Locomotives (EUR)",
+            format!("Locomotives ({})", currency),
             "Trains (no.)",
-            "Trains (EUR)",
+            format!("Trains ({})", currency),
             "Passenger Cars (no.)",
-            "Passenger Cars (EUR)",
+            format!("Passenger Cars ({})", currency),
             "Freight Cars (no.)",
-            "Freight Cars (EUR)",
+            format!("Freight Cars ({})", currency),
             "Total (no.)",
-            "Total (EUR)"
+            format!("Total ({})", currency)
         ]);
 
         for s in self.values_by_year() {
             table.add_row(row![
                 s.year().to_string(),
                 r -> s.number_of_locomotives().to_string(),
-                r -> s.locomotives_value().to_string(),
+                r -> amount(s.locomotives_value()),
                 r -> s.number_of_trains().to_string(),
-                r -> s.trains_value().to_string(),
+                r -> amount(s.trains_value()),
                 r -> s.number_of_passenger_cars().to_string(),
-                r -> s.passenger_cars_value().to_string(),
+                r -> amount(s.passenger_cars_value()),
                 r -> s.number_of_freight_cars().to_string(),
-                r -> s.freight_cars_value().to_string(),
+                r -> amount(s.freight_cars_value()),
                 r -> s.number_of_rolling_stocks().to_string(),
-                r -> s.total_value().to_string(),
+                r -> amount(s.total_value()),
             ]);
         }
 
         table.add_row(row![
             "TOTAL",
             r -> self.number_of_locomotives().to_string(),
-            r -> self.locomotives_value().to_string(),
+            r -> amount(self.locomotives_value()),
             r -> self.number_of_trains().to_string(),
-            r -> self.trains_value().to_string(),
+            r -> amount(self.trains_value()),
             r -> self.number_of_passenger_cars().to_string(),
-            r -> self.passenger_cars_value().to_string(),
+            r -> amount(self.passenger_cars_value()),
             r -> self.number_of_freight_cars().to_string(),
-            r -> self.freight_cars_value().to_string(),
+            r -> amount(self.freight_cars_value()),
             r -> self.number_of_rolling_stocks().to_string(),
-            r -> self.total_value().to_string(),
+            r -> amount(self.total_value()),
         ]);
 
         table
     }
 }
 
+impl AsTable for SpendingTimeline {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
+        let mut table = Table::new();
+        table.add_row(row![
+            "Period",
+            "Items (no.)",
+            format!("Spent ({})", currency),
+            format!("Cumulative ({})", currency),
+            format!("Average price ({})", currency),
+        ]);
+
+        for entry in self.entries() {
+            table.add_row(row![
+                entry.period(),
+                r -> entry.number_of_items().to_string(),
+                r -> amount(entry.amount_spent()),
+                r -> amount(entry.cumulative_total()),
+                r -> entry.average_price().map(amount).unwrap_or_else(|| String::from("-")),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl AsTable for CollectionStatsByScale {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
+        let mut table = Table::new();
+        table.add_row(row![
+            "Scale",
+            "Ratio",
+            "Gauge (mm)",
+            "Items (no.)",
+            format!("Total ({})", currency)
+        ]);
+
+        for s in self.values() {
+            let scale = s.scale();
+            table.add_row(row![
+                b -> scale.name(),
+                r -> format!("1:{}", scale.ratio()),
+                r -> scale.gauge().map(|g| g.to_string()).unwrap_or_default(),
+                r -> s.number_of_items().to_string(),
+                r -> amount(s.total_value()),
+            ]);
+        }
+
+        if let Some(others) = self.others() {
+            table.add_row(row![
+                i -> others_label(others),
+                "",
+                "",
+                r -> others.number_of_items().to_string(),
+                r -> amount(others.total_value()),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl AsTable for CollectionStatsByRailway {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
+        let mut table = Table::new();
+        table.add_row(row![
+            "Railway",
+            "Country",
+            "Items (no.)",
+            format!("Total ({})", currency)
+        ]);
+
+        for s in self.values() {
+            table.add_row(row![
+                b -> s.railway(),
+                c -> s.country().unwrap_or_default(),
+                r -> s.number_of_items().to_string(),
+                r -> amount(s.total_value()),
+            ]);
+        }
+
+        if let Some(others) = self.others() {
+            table.add_row(row![
+                i -> others_label(others),
+                "",
+                r -> others.number_of_items().to_string(),
+                r -> amount(others.total_value()),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl AsTable for CollectionStatsByBrand {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
+        let mut table = Table::new();
+        table.add_row(row![
+            "Brand",
+            "Items (no.)",
+            format!("Total ({})", currency)
+        ]);
+
+        for s in self.values() {
+            table.add_row(row![
+                b -> s.brand(),
+                r -> s.number_of_items().to_string(),
+                r -> amount(s.total_value()),
+            ]);
+        }
+
+        if let Some(others) = self.others() {
+            table.add_row(row![
+                i -> others_label(others),
+                r -> others.number_of_items().to_string(),
+                r -> amount(others.total_value()),
+            ]);
+        }
+
+        table
+    }
+}
+
+impl AsTable for PriceDistribution {
+    fn to_table(&self) -> Table {
+        let mut table = Table::new();
+        table.add_row(row![
+            format!("Price ({})", self.currency()),
+            "Items (no.)",
+            "%",
+        ]);
+
+        for bucket in self.buckets() {
+            table.add_row(row![
+                bucket.to_string(),
+                r -> bucket.number_of_items().to_string(),
+                r -> amount(self.percentage(bucket)),
+            ]);
+        }
+
+        table
+    }
+}
+
+/// The label for the groups collapsed together (ie "Others (3)")
+fn others_label(others: &OtherGroups) -> String {
+    format!("Others ({})", others.number_of_groups())
+}
+
 impl AsTable for Collection {
-    fn to_table(mut self) -> Table {
-        self.sort_items();
+    fn to_table(&self) -> Table {
+        let mut items: Vec<&CollectionItem> = self.get_items().iter().collect();
+        items.sort();
+        collection_items_table(items, DEFAULT_DESCRIPTION_WIDTH, false)
+    }
+}
+
+/// Returns the railway name, followed by its country when known (ie "FS (IT)")
+fn railway_with_country(railway: &Railway) -> String {
+    match railway.country() {
+        Some(country) => format!("{} ({})", railway.name(), country),
+        None => railway.name().to_owned(),
+    }
+}
+
+/// Formats an amount with two decimals, rounding it like the prices
+fn amount(value: Decimal) -> String {
+    format!(
+        "{:.2}",
+        value.round_dp_with_strategy(2, RoundingStrategy::MidpointAwayFromZero)
+    )
+}
+
+/// Returns the table for the given collection items, truncating the
+/// descriptions to `description_width` characters (0 means no truncation).
+/// The storage column is included only when `show_storage` is set.
+pub fn collection_items_table<'a, I>(
+    items: I,
+    description_width: usize,
+    show_storage: bool,
+) -> Table
+where
+    I: IntoIterator<Item = &'a CollectionItem>,
+{
+    items_table(items, description_width, show_storage, None)
+}
+
+/// Returns the table for the given collection items, like `collection_items_table`,
+/// with the current value from the reference prices and its change from the
+/// price paid.
+pub fn collection_items_table_with_prices<'a, I>(
+    items: I,
+    description_width: usize,
+    show_storage: bool,
+    reference_prices: &ReferencePrices,
+) -> Table
+where
+    I: IntoIterator<Item = &'a CollectionItem>,
+{
+    items_table(
+        items,
+        description_width,
+        show_storage,
+        Some(reference_prices),
+    )
+}
+
+fn items_table<'a, I>(
+    items: I,
+    description_width: usize,
+    show_storage: bool,
+    reference_prices: Option<&ReferencePrices>,
+) -> Table
+where
+    I: IntoIterator<Item = &'a CollectionItem>,
+{
+    let mut table = Table::new();
+    let mut header = collection_items_header();
+    if show_storage {
+        header.add_cell(cell!("Storage"));
+    }
+    if reference_prices.is_some() {
+        header.add_cell(cell!("Current value"));
+        header.add_cell(cell!("Delta"));
+        header.add_cell(cell!("Change (%)"));
+    }
+    table.add_row(header);
+
+    let mut number_of_items = 0;
+    let mut count = 0u32;
+    let mut prices = Vec::new();
+    for (ind, it) in items.into_iter().enumerate() {
+        number_of_items += 1;
+        count += u32::from(it.catalog_item().count());
+        if let Some(price) = it.purchased_info().price() {
+            prices.push(price.clone());
+        }
+
+        let mut row = collection_item_row(ind, it, description_width);
+        if show_storage {
+            row.add_cell(cell!(storage(it)));
+        }
+        if let Some(reference_prices) = reference_prices {
+            match reference_prices.price_of(it.catalog_item()) {
+                Some(reference) => {
+                    row.add_cell(cell!(r -> reference));
+                    match it.value_delta(reference) {
+                        Some(delta) => {
+                            row.add_cell(signed_cell(Some(delta)));
+                            row.add_cell(signed_cell(
+                                it.value_change(reference),
+                            ));
+                        }
+                        None => {
+                            row.add_cell(cell!(r -> "-"));
+                            row.add_cell(cell!(r -> "-"));
+                        }
+                    }
+                }
+                None => {
+                    for _ in 0..3 {
+                        row.add_cell(cell!(""));
+                    }
+                }
+            }
+        }
+        table.add_row(row);
+    }
+
+    let total = Price::try_sum(prices)
+        .map(|total| total.to_string())
+        .unwrap_or_else(|_| String::from("mixed currencies"));
+    let mut footer = row![
+        "",
+        b -> "TOTAL",
+        b -> format!("{} item(s)", number_of_items),
+        "",
+        "",
+        "",
+        "",
+        rb -> count,
+        "",
+        rb -> total,
+        "",
+        "",
+    ];
+    if show_storage {
+        footer.add_cell(cell!(""));
+    }
+    if reference_prices.is_some() {
+        for _ in 0..3 {
+            footer.add_cell(cell!(""));
+        }
+    }
+    table.add_row(footer);
+
+    table
+}
+
+/// Returns the cell for a value that can be negative (ie "+12.50" or
+/// "-3.00"), the negative values are printed in red.
+fn signed_cell(value: Option<Decimal>) -> Cell {
+    match value {
+        Some(value) if value.is_sign_negative() && !value.is_zero() => {
+            Cell::new(&amount(value)).style_spec("rFr")
+        }
+        Some(value) if !value.is_zero() => {
+            Cell::new(&format!("+{}", amount(value))).style_spec("r")
+        }
+        Some(value) => Cell::new(&amount(value)).style_spec("r"),
+        None => Cell::new(""),
+    }
+}
+
+/// Returns the storage location for the item, or "-" when not assigned.
+fn storage(it: &CollectionItem) -> &str {
+    it.storage().unwrap_or("-")
+}
+
+/// Returns the table to find the collection items, with only their
+/// storage location next to the item identification.
+pub fn locate_table(items: &[&CollectionItem]) -> Table {
+    let mut table = Table::new();
+    table.add_row(row!["#", "Brand", "Item number", "Description", "Storage"]);
+
+    for (ind, it) in items.iter().enumerate() {
+        let ci = it.catalog_item();
+        table.add_row(row![
+            ind + 1,
+            b -> ci.brand().name(),
+            ci.item_number(),
+            i -> truncate(ci.description(), DEFAULT_DESCRIPTION_WIDTH),
+            storage(it),
+        ]);
+    }
+
+    table
+}
+
+/// Returns the table for the search results, with the collection items
+/// layout plus the field matching the search.
+pub fn search_results_table(
+    matches: &[SearchMatch],
+    description_width: usize,
+) -> Table {
+    let mut table = Table::new();
+
+    let mut header = collection_items_header();
+    header.add_cell(cell!("Match"));
+    table.add_row(header);
 
+    for (ind, it) in matches.iter().enumerate() {
+        let mut row = collection_item_row(ind, it.item(), description_width);
+        row.add_cell(cell!(i -> it.field()));
+        table.add_row(row);
+    }
+
+    table
+}
+
+fn collection_items_header() -> Row {
+    row![
+        "#",
+        "Brand",
+        "Item number",
+        "Scale",
+        "PM",
+        "Cat.",
+        "Description",
+        "Count",
+        "Added",
+        "Price",
+        "Shop",
+        "Condition"
+    ]
+}
+
+fn collection_item_row(
+    ind: usize,
+    it: &CollectionItem,
+    description_width: usize,
+) -> Row {
+    let ci = it.catalog_item();
+    let purchase = it.purchased_info();
+
+    row![
+        ind + 1,
+        b -> ci.brand().name(),
+        ci.item_number(),
+        ci.scale(),
+        ci.power_method(),
+        c -> ci.category(),
+        i -> truncate(ci.description(), description_width),
+        r -> ci.count(),
+        purchase.purchased_date().format("%Y-%m-%d").to_string(),
+        r -> purchase.price().map(|p| p.to_string()).unwrap_or_else(|| String::from("-")),
+        purchase.shop(),
+        purchase.condition().map(|c| c.to_string()).unwrap_or_default(),
+    ]
+}
+
+impl AsTable for WishListBudget {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
+        let mut pricings = vec![PricingStrategy::Min, PricingStrategy::Max];
+        if self.pricing() == PricingStrategy::Average {
+            pricings.push(PricingStrategy::Average);
+        }
+        let mut table = Table::new();
+        let mut header = row!["Priority"];
+        for pricing in &pricings {
+            let title = format!(
+                "{} ({})",
+                pricing.to_string().to_title_case(),
+                currency
+            );
+            if *pricing == self.pricing() {
+                header.add_cell(Cell::new(&title).style_spec("b"));
+            } else {
+                header.add_cell(Cell::new(&title));
+            }
+        }
+        table.add_row(header);
+
+        let mut totals = vec![Decimal::ZERO; pricings.len()];
+        for priority in Priority::values() {
+            let mut row = row![priority.to_string()];
+            for (pricing, total) in pricings.iter().zip(totals.iter_mut()) {
+                let value = self.by_priority_with(priority, *pricing);
+                *total += value;
+                row.add_cell(Cell::new(&amount(value)).style_spec("r"));
+            }
+            table.add_row(row);
+        }
+
+        let mut footer = row![b -> "TOTAL"];
+        for total in totals {
+            footer.add_cell(Cell::new(&amount(total)).style_spec("rb"));
+        }
+        table.add_row(footer);
+
+        table
+    }
+}
+
+impl AsTable for BudgetPlan<'_> {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
         let mut table = Table::new();
         table.add_row(row![
             "#",
             "Brand",
             "Item number",
-            "Scale",
-            "PM",
-            "Cat.",
+            "Priority",
             "Description",
-            "Count",
-            "Added",
-            "Price",
-            "Shop"
+            format!("Price, {} ({})", self.pricing(), currency),
+            "Fits",
         ]);
 
-        for (ind, it) in self.get_items().iter().enumerate() {
+        let fitting = self
+            .fitting()
+            .iter()
+            .map(|(it, price)| (*it, Some(*price), "yes"));
+        let not_fitting = self
+            .not_fitting()
+            .iter()
+            .map(|(it, price)| (*it, Some(*price), "no"));
+        let unknown = self
+            .unknown_price()
+            .iter()
+            .map(|it| (*it, None, "unknown price"));
+        for (id, (it, price, fits)) in
+            fitting.chain(not_fitting).chain(unknown).enumerate()
+        {
             let ci = it.catalog_item();
-            let purchase = it.purchased_info();
+            table.add_row(row![
+                c -> (id + 1).to_string(),
+                b -> ci.brand().name(),
+                ci.item_number().value(),
+                c -> it.priority().to_string(),
+                truncate(ci.description(), DEFAULT_DESCRIPTION_WIDTH),
+                r -> price.map_or(String::from("-"), amount),
+                c -> fits,
+            ]);
+        }
+
+        table.add_row(
+            row!["", b -> "LIMIT", "", "", "", rb -> amount(self.limit()), ""],
+        );
+        table.add_row(
+            row!["", b -> "SPENT", "", "", "", rb -> amount(self.spent()), ""],
+        );
+        table.add_row(row!["", b -> "REMAINDER", "", "", "", rb -> amount(self.remainder()), ""]);
+
+        table
+    }
+}
+
+impl AsTable for WishListStats {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
+        let mut table = Table::new();
+        table.add_row(row![
+            "",
+            "Items (no.)",
+            format!("Min ({})", currency),
+            format!("Max ({})", currency)
+        ]);
+
+        table.add_row(row![b -> "By category", "", "", ""]);
+        for (category, stats) in self.by_category() {
+            table.add_row(row![
+                format!("{:?}", category).to_title_case(),
+                r -> stats.number_of_items().to_string(),
+                r -> amount(stats.min_budget()),
+                r -> amount(stats.max_budget()),
+            ]);
+        }
+
+        table.add_row(row![b -> "By priority", "", "", ""]);
+        for (priority, stats) in self.by_priority() {
+            table.add_row(row![
+                priority.to_string(),
+                r -> stats.number_of_items().to_string(),
+                r -> amount(stats.min_budget()),
+                r -> amount(stats.max_budget()),
+            ]);
+        }
+
+        let totals = self.totals();
+        table.add_row(row![
+            b -> "TOTAL",
+            rb -> totals.number_of_items().to_string(),
+            rb -> amount(totals.min_budget()),
+            rb -> amount(totals.max_budget()),
+        ]);
+
+        table
+    }
+}
+
+impl AsTable for CollectionValuation {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
+        let mut table = Table::new();
+        table.add_row(row![
+            "Category",
+            "Items (no.)",
+            format!("Value ({})", currency)
+        ]);
 
+        let mut number_of_items = 0;
+        for it in self.by_category() {
+            number_of_items += it.number_of_items();
             table.add_row(row![
-                ind + 1,
+                format!("{:?}", it.category()).to_title_case(),
+                r -> it.number_of_items().to_string(),
+                r -> amount(it.value()),
+            ]);
+        }
+
+        table.add_row(row![
+            b -> "TOTAL",
+            rb -> number_of_items.to_string(),
+            rb -> amount(self.total_value()),
+        ]);
+
+        table
+    }
+}
+
+impl AsTable for ShopStats {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
+        let with_dates =
+            self.values().iter().any(|it| it.first_purchase().is_some());
+        let mut table = Table::new();
+
+        let mut header = row![
+            "Shop",
+            "Items (no.)",
+            format!("Total ({})", currency),
+            format!("Average ({})", currency),
+        ];
+        if with_dates {
+            header.add_cell(cell!("First purchase"));
+            header.add_cell(cell!("Last purchase"));
+        }
+        table.add_row(header);
+
+        for it in self.values() {
+            let mut row = row![
+                b -> it.shop(),
+                r -> it.number_of_items().to_string(),
+                r -> amount(it.total()),
+                r -> it.average_price().map(amount).unwrap_or_default(),
+            ];
+            if with_dates {
+                for date in [it.first_purchase(), it.last_purchase()] {
+                    row.add_cell(cell!(c -> date
+                        .map(|d| d.format("%Y-%m-%d").to_string())
+                        .unwrap_or_default()));
+                }
+            }
+            table.add_row(row);
+        }
+
+        table
+    }
+}
+
+impl AsTable for CurrentValueReport<'_> {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
+        let mut table = Table::new();
+        table.add_row(row![
+            "#",
+            "Brand",
+            "Item number",
+            "Description",
+            format!("Paid ({})", currency),
+            format!("Current value ({})", currency),
+            format!("Difference ({})", currency),
+            "Current value from",
+        ]);
+
+        for (id, it) in self.items().iter().enumerate() {
+            let ci = it.item().catalog_item();
+            table.add_row(row![
+                c -> (id + 1).to_string(),
                 b -> ci.brand().name(),
-                ci.item_number(),
-                ci.scale(),
-                ci.power_method(),
-                c -> ci.category(),
-                i -> substring(ci.description()),
-                r -> ci.count(),
-                purchase.purchased_date().format("%Y-%m-%d").to_string(),
-                r -> purchase.price(),
-                purchase.shop(),
+                ci.item_number().value(),
+                truncate(ci.description(), DEFAULT_DESCRIPTION_WIDTH),
+                r -> amount(it.paid()),
+                r -> amount(it.current_value()),
+                r -> amount(it.difference()),
+                if it.has_reference_price() { "reference price" } else { "price paid" },
             ]);
         }
 
+        table.add_row(row![
+            "",
+            b -> "TOTAL",
+            "",
+            "",
+            rb -> amount(self.paid_total()),
+            rb -> amount(self.current_total()),
+            rb -> amount(self.difference()),
+            "",
+        ]);
+
+        table
+    }
+}
+
+impl AsTable for SalesReport<'_> {
+    fn to_table(&self) -> Table {
+        let currency = self.currency();
+        let mut table = Table::new();
+        table.add_row(row![
+            "#",
+            "Brand",
+            "Item number",
+            "Description",
+            "Purchased",
+            format!("Paid ({})", currency),
+            "Sold",
+            format!("Sold price ({})", currency),
+            "Buyer",
+            format!("Gain/loss ({})", currency),
+        ]);
+
+        for (id, it) in self.sales().iter().enumerate() {
+            let ci = it.catalog_item();
+            let purchase = it.purchased_info();
+            let sold = it.sold_info().expect("the sales must be sold items");
+            let mut row = row![
+                c -> (id + 1).to_string(),
+                b -> ci.brand().name(),
+                ci.item_number().value(),
+                truncate(ci.description(), DEFAULT_DESCRIPTION_WIDTH),
+                purchase.purchased_date(),
+                r -> purchase.price().map_or(String::from("-"), |p| amount(p.amount())),
+                sold.sold_date(),
+                r -> sold.price().map_or(String::from("-"), |p| amount(p.amount())),
+                sold.buyer().unwrap_or("-"),
+            ];
+            row.add_cell(signed_cell(it.realized_gain()));
+            table.add_row(row);
+        }
+
+        let mut footer = row!["", b -> "TOTAL", "", "", "", "", "", "", ""];
+        footer.add_cell(signed_cell(Some(self.realized_gain())));
+        table.add_row(footer);
+
         table
     }
 }
 
-fn substring(s: &str) -> String {
-    if s.len() < 50 {
-        s.to_owned()
-    } else {
-        let mut output = s[0..47].to_owned();
-        output.push_str("...");
-        output
+/// Returns the table for the realized gains or losses, by year of sale.
+pub fn sales_by_year_table(report: &SalesReport) -> Table {
+    let currency = report.currency();
+    let mut table = Table::new();
+    table.add_row(row![
+        "Year",
+        "Items (no.)",
+        format!("Paid ({})", currency),
+        format!("Sold ({})", currency),
+        format!("Gain/loss ({})", currency),
+    ]);
+
+    for it in report.by_year() {
+        let mut row = row![
+            c -> it.year(),
+            r -> it.number_of_items(),
+            r -> amount(it.purchase_total()),
+            r -> amount(it.sold_total()),
+        ];
+        row.add_cell(signed_cell(Some(it.realized_gain())));
+        table.add_row(row);
+    }
+
+    let mut footer = row![
+        b -> "TOTAL",
+        rb -> report.len(),
+        "",
+        "",
+    ];
+    footer.add_cell(signed_cell(Some(report.realized_gain())));
+    table.add_row(footer);
+
+    table
+}
+
+/// Returns the table for the wish list items already owned, with both the wish
+/// list and the collection details.
+pub fn owned_matches_table(matches: &[OwnedMatch]) -> Table {
+    let mut table = Table::new();
+    table.add_row(row![
+        "#",
+        "Brand",
+        "Item number",
+        "Description",
+        "Priority",
+        "Match",
+        "Owned item",
+        "Owned description",
+        "Purchased",
+        "Shop",
+    ]);
+
+    for (id, it) in matches.iter().enumerate() {
+        let ci = it.item().catalog_item();
+        let owned = it.owned().catalog_item();
+        let purchase = it.owned().purchased_info();
+        let matched_by = match it.locomotive().and_then(|rs| rs.road_number()) {
+            Some(road_number) => format!("road number {}", road_number),
+            None => String::from("item number"),
+        };
+        table.add_row(row![
+            c -> (id + 1).to_string(),
+            b -> ci.brand().name(),
+            ci.item_number().value(),
+            truncate(ci.description(), DEFAULT_DESCRIPTION_WIDTH),
+            c -> it.item().priority().to_string(),
+            matched_by,
+            format!("{} {}", owned.brand().name(), owned.item_number()),
+            truncate(owned.description(), DEFAULT_DESCRIPTION_WIDTH),
+            purchase.purchased_date(),
+            purchase.shop(),
+        ]);
+    }
+
+    table
+}
+
+/// Returns the table for the collection items under warranty.
+pub fn warranties_table(items: &[&CollectionItem]) -> Table {
+    let mut table = Table::new();
+    table.add_row(row![
+        "#",
+        "Brand",
+        "Item number",
+        "Description",
+        "Shop",
+        "Purchased",
+        "Receipt",
+        "Expires",
+    ]);
+
+    for (ind, it) in items.iter().enumerate() {
+        let ci = it.catalog_item();
+        let purchase = it.purchased_info();
+
+        table.add_row(row![
+            ind + 1,
+            b -> ci.brand().name(),
+            ci.item_number(),
+            i -> truncate(ci.description(), DEFAULT_DESCRIPTION_WIDTH),
+            purchase.shop(),
+            purchase.purchased_date().format("%Y-%m-%d"),
+            purchase.receipt().unwrap_or_default(),
+            purchase
+                .warranty_expiry()
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+        ]);
+    }
+
+    table
+}
+
+/// Returns the table with the tags used in the collection, and the number
+/// of items for each one.
+pub fn tags_table(tags: &BTreeMap<String, usize>) -> Table {
+    let mut table = Table::new();
+    table.add_row(row!["#", "Tag", "Items"]);
+
+    for (ind, (tag, number_of_items)) in tags.iter().enumerate() {
+        table.add_row(row![ind + 1, b -> tag, r -> number_of_items]);
+    }
+
+    table
+}
+
+/// Returns the detail view for a collection item, including the fields
+/// (like the notes) too wide for the items table.
+pub fn collection_item_details_table(it: &CollectionItem) -> Table {
+    let ci = it.catalog_item();
+    let purchase = it.purchased_info();
+
+    let mut table = Table::new();
+    table.add_row(row![b -> "Brand", ci.brand().name()]);
+    table.add_row(row![b -> "Item number", ci.item_number()]);
+    table.add_row(row![b -> "Description", ci.description()]);
+    table.add_row(row![b -> "Scale", ci.scale()]);
+    table.add_row(row![b -> "Power method", ci.power_method()]);
+    table.add_row(row![b -> "Category", ci.category()]);
+    table.add_row(row![b -> "Count", ci.count()]);
+    table.add_row(row![b -> "Purchased", purchase]);
+    table.add_row(row![
+        b -> "Condition",
+        purchase.condition().map(|c| c.to_string()).unwrap_or_default()
+    ]);
+    table.add_row(row![b -> "Storage", storage(it)]);
+    table.add_row(row![b -> "Tags", it.tags().join(", ")]);
+    table.add_row(row![b -> "Notes", it.notes().unwrap_or_default()]);
+
+    table
+}
+
+/// What the heatmap cells are measuring.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HeatmapMetric {
+    /// The amount spent in the month
+    Spend,
+
+    /// The number of items purchased in the month
+    Count,
+}
+
+/// The shades for the heatmap cells, from no purchases to the maximum.
+const HEATMAP_SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct",
+    "Nov", "Dec",
+];
+
+/// Returns the year by month grid for the purchases, with each cell shaded
+/// in proportion to the highest monthly value.
+pub fn heatmap_table(
+    purchases: &PurchasesByMonth,
+    metric: HeatmapMetric,
+) -> Table {
+    let max = heatmap_max(purchases, metric);
+
+    let mut table = Table::new();
+    let mut header = row!["Year"];
+    for month in MONTHS {
+        header.add_cell(cell!(c -> month));
+    }
+    table.add_row(header);
+
+    for year in purchases.years() {
+        let mut row = row![b -> year];
+        for month in 1..=12 {
+            let value = purchases
+                .get(year, month)
+                .map(|it| {
+                    heatmap_value(
+                        it.number_of_items(),
+                        it.total_value(),
+                        metric,
+                    )
+                })
+                .unwrap_or_default();
+            let shade = heatmap_shade(value, max).to_string().repeat(3);
+            row.add_cell(cell!(c -> shade));
+        }
+        table.add_row(row);
+    }
+
+    table
+}
+
+/// Returns the legend for the heatmap shades.
+pub fn heatmap_legend(
+    purchases: &PurchasesByMonth,
+    metric: HeatmapMetric,
+) -> String {
+    let max = heatmap_max(purchases, metric);
+    let max = match metric {
+        HeatmapMetric::Spend => format!("{} {}", max, purchases.currency()),
+        HeatmapMetric::Count => format!("{} item(s)", max),
+    };
+
+    format!(
+        "'{}' none, '{}' up to 25%, '{}' up to 50%, '{}' up to 75%, '{}' up to 100% of {}",
+        HEATMAP_SHADES[0],
+        HEATMAP_SHADES[1],
+        HEATMAP_SHADES[2],
+        HEATMAP_SHADES[3],
+        HEATMAP_SHADES[4],
+        max
+    )
+}
+
+fn heatmap_max(purchases: &PurchasesByMonth, metric: HeatmapMetric) -> Decimal {
+    heatmap_value(
+        purchases.max_number_of_items(),
+        purchases.max_total_value(),
+        metric,
+    )
+}
+
+fn heatmap_value(
+    number_of_items: u16,
+    total_value: Decimal,
+    metric: HeatmapMetric,
+) -> Decimal {
+    match metric {
+        HeatmapMetric::Spend => total_value,
+        HeatmapMetric::Count => Decimal::from(number_of_items),
+    }
+}
+
+fn heatmap_shade(value: Decimal, max: Decimal) -> char {
+    if value <= Decimal::ZERO || max <= Decimal::ZERO {
+        return HEATMAP_SHADES[0];
+    }
+
+    let levels = Decimal::from(HEATMAP_SHADES.len() - 1);
+    let level = (value * levels / max).ceil().to_usize().unwrap_or_default();
+    HEATMAP_SHADES[level.clamp(1, HEATMAP_SHADES.len() - 1)]
+}
+
+/// Truncates the string to at most `width` characters, replacing the last ones
+/// with "..." when it is too long. A `width` of 0 means no truncation.
+///
+/// The characters are counted as grapheme clusters, so that accented letters
+/// are never split.
+pub fn truncate(s: &str, width: usize) -> String {
+    const ELLIPSIS: &str = "...";
+
+    if width == 0 || s.graphemes(true).count() <= width {
+        return s.to_owned();
+    }
+
+    let take = width.saturating_sub(ELLIPSIS.len());
+    let mut output: String = s.graphemes(true).take(take).collect();
+    output.push_str(ELLIPSIS);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod heatmap_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_shade_the_cells_in_proportion_to_the_max() {
+            let max = Decimal::from(100);
+            assert_eq!(' ', heatmap_shade(Decimal::ZERO, max));
+            assert_eq!('░', heatmap_shade(Decimal::from(1), max));
+            assert_eq!('░', heatmap_shade(Decimal::from(25), max));
+            assert_eq!('▒', heatmap_shade(Decimal::from(26), max));
+            assert_eq!('▓', heatmap_shade(Decimal::from(75), max));
+            assert_eq!('█', heatmap_shade(Decimal::from(100), max));
+        }
+
+        #[test]
+        fn it_should_not_shade_anything_without_purchases() {
+            assert_eq!(' ', heatmap_shade(Decimal::ZERO, Decimal::ZERO));
+        }
+    }
+
+    mod truncate_tests {
+        use super::*;
+
+        #[test]
+        fn it_should_leave_short_strings_unchanged() {
+            assert_eq!("E.656 210", truncate("E.656 210", 50));
+            assert_eq!("12345", truncate("12345", 5));
+        }
+
+        #[test]
+        fn it_should_truncate_long_strings() {
+            assert_eq!("123...", truncate("1234567", 6));
+        }
+
+        #[test]
+        fn it_should_never_truncate_when_the_width_is_zero() {
+            let s =
+                "Locomotiva elettrica E.656 210 – 1ª serie, livrea d'origine";
+            assert_eq!(s, truncate(s, 0));
+        }
+
+        #[test]
+        fn it_should_truncate_multi_byte_strings_at_the_boundary() {
+            // "ª" is two bytes long, and it sits right at the cut point
+            assert_eq!(
+                "carrozza di 1ª...",
+                truncate("carrozza di 1ª classe", 17)
+            );
+            assert_eq!(
+                "carrozza di 1...",
+                truncate("carrozza di 1ª classe", 16)
+            );
+            assert_eq!("àèìòù", truncate("àèìòù", 5));
+            assert_eq!("àè...", truncate("àèìòùà", 5));
+        }
+
+        #[test]
+        fn it_should_not_split_grapheme_clusters() {
+            // "e" followed by the combining acute accent
+            let s = "cafe\u{301} cafe\u{301}";
+            assert_eq!("cafe\u{301}...", truncate(s, 7));
+        }
     }
 }
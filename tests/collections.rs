@@ -0,0 +1,1044 @@
+use chrono::NaiveDate;
+use railists::data_source::DataSource;
+use railists::domain::catalog::{
+    categories::LocomotiveType,
+    railways::Railway,
+    rolling_stocks::{
+        Control, DccInterface, Epoch, LengthOverBuffer, RollingStockBuilder,
+    },
+};
+use railists::domain::collecting::collections::{
+    Collection, CollectionDiff, CollectionItem, CollectionStats,
+    CollectionStatsByBrand, CollectionStatsByScale, Condition, Depot,
+    PurchasesByMonth, SalesReport, TopPurchases,
+};
+use railists::domain::collecting::valuations::{
+    CurrentValueReport, InsuranceReport, ReferencePrices,
+};
+use railists::domain::collecting::{Currency, Price};
+use railists::export::{self, csv::PriceColumns, json::JsonStyle};
+use railists::tables::{self, AsTable, HeatmapMetric};
+use rust_decimal::Decimal;
+use std::fs;
+
+fn load_collection() -> Collection {
+    DataSource::new("tests/data/collection.yaml")
+        .collection()
+        .expect("Unable to load collection")
+}
+
+#[test]
+fn it_should_load_collections_from_yaml_files() {
+    let collection = load_collection();
+    assert_eq!("My collection", collection.description());
+    assert_eq!(1, collection.version());
+    assert_eq!(3, collection.len());
+}
+
+#[test]
+fn it_should_load_the_same_rolling_stocks_built_with_the_builder() {
+    let collection = load_collection();
+
+    let expected = RollingStockBuilder::locomotive()
+        .with_class_name("E.656")
+        .with_road_number("E.656 210")
+        .with_series("1a serie")
+        .with_railway(Railway::new("FS"))
+        .with_epoch(Epoch::IV)
+        .with_locomotive_type(LocomotiveType::ElectricLocomotive)
+        .with_length_over_buffer(LengthOverBuffer::new(210))
+        .with_livery("blu/grigio")
+        .with_control(Control::DccReady)
+        .with_dcc_interface(DccInterface::Nem652)
+        .build()
+        .unwrap();
+
+    let item = collection.get(0).unwrap();
+    assert_eq!(&vec![expected], item.rolling_stocks());
+}
+
+#[test]
+fn it_should_load_collections_from_yaml_strings() {
+    let collection = Collection::from_yaml_str(
+        r#"
+version: 2
+description: "In memory"
+modifiedAt: "2023-10-01 10:00:00"
+elements:
+  - brand: ACME
+    itemNumber: "60023"
+    description: "Locomotiva elettrica E.656 210"
+    powerMethod: DC
+    scale: H0
+    count: 1
+    rollingStocks:
+      - typeName: E.656
+        roadNumber: E.656 210
+        railway: FS
+        epoch: IV
+        category: LOCOMOTIVE
+        subCategory: ELECTRIC_LOCOMOTIVE
+    purchaseInfo:
+      date: "2019-05-02"
+      price: "229,00"
+      shop: "Treni&Treni"
+"#,
+    )
+    .expect("Unable to load collection");
+
+    assert_eq!("In memory", collection.description());
+    assert_eq!(2, collection.version());
+    assert_eq!(1, collection.len());
+}
+
+#[test]
+fn it_should_load_collections_from_readers() {
+    let contents = std::fs::read("tests/data/collection.yaml").unwrap();
+    let collection = Collection::from_yaml_reader(contents.as_slice()).unwrap();
+    assert_eq!(3, collection.len());
+}
+
+#[test]
+fn it_should_fail_to_load_collections_with_a_blank_item_number() {
+    let result =
+        DataSource::new("tests/data/blank_item_number.yaml").collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid item ACME"));
+    assert!(error.contains("Item number cannot blank"));
+}
+
+#[test]
+fn it_should_load_rolling_stocks_without_the_optional_fields() {
+    let collection = DataSource::new("tests/data/absent_optional_fields.yaml")
+        .collection()
+        .expect("Unable to load collection");
+    assert_eq!(2, collection.len());
+}
+
+#[test]
+fn it_should_fail_to_load_rolling_stocks_with_an_invalid_control() {
+    let result =
+        DataSource::new("tests/data/invalid_control.yaml").collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid item ACME 60023"));
+    assert!(error.contains("Invalid control 'DCC_REDY'"));
+}
+
+#[test]
+fn it_should_fail_to_load_rolling_stocks_with_an_invalid_service_level() {
+    let result =
+        DataSource::new("tests/data/invalid_service_level.yaml").collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid item Roco 45123"));
+    assert!(error.contains("Invalid serviceLevel '4cl'"));
+}
+
+#[test]
+fn it_should_fail_to_load_rolling_stocks_with_a_zero_length() {
+    let result = DataSource::new("tests/data/invalid_length.yaml").collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid item ACME 60023"));
+    assert!(error.contains("Invalid length over buffer"));
+}
+
+#[test]
+fn it_should_fail_to_load_rolling_stocks_with_an_unknown_category() {
+    let result =
+        DataSource::new("tests/data/invalid_category.yaml").collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid item ACME 60023"));
+    assert!(error.contains("Invalid category for rolling stock E.656"));
+    assert!(error.contains("Invalid category 'AIRPLANE'"));
+    assert!(error.contains("LOCOMOTIVE, TRAIN, PASSENGER_CAR, FREIGHT_CAR"));
+}
+
+#[test]
+fn it_should_fail_to_load_the_categories_in_the_command_line_format() {
+    let yaml = fs::read_to_string("tests/data/collection.yaml")
+        .unwrap()
+        .replace("category: LOCOMOTIVE", "category: locomotives");
+    let result = Collection::from_yaml_str(&yaml);
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid category 'locomotives'"));
+}
+
+#[test]
+fn it_should_fail_to_load_locomotives_with_an_invalid_sub_category() {
+    let result =
+        DataSource::new("tests/data/invalid_locomotive_type.yaml").collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid item ACME 60023"));
+    assert!(error.contains(
+        "Invalid subCategory 'MAGLEV_LOCOMOTIVE' for locomotive E.656"
+    ));
+}
+
+#[test]
+fn it_should_fail_to_load_passenger_cars_with_an_unknown_sub_category() {
+    let dir = std::env::temp_dir().join("railists-passenger-car-type");
+    std::fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("coaches.yaml");
+    let contents = std::fs::read_to_string("tests/data/coaches.yaml")
+        .unwrap()
+        .replace("OPEN_COACH", "PARLOR_CAR");
+    std::fs::write(&filename, contents).unwrap();
+
+    let result = DataSource::new(filename.to_str().unwrap()).collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid subCategory 'PARLOR_CAR'"));
+}
+
+#[test]
+fn it_should_fail_to_load_items_with_an_invalid_power_method() {
+    let dir = std::env::temp_dir().join("railists-collection-power-method");
+    std::fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("locomotives.yaml");
+    let contents = std::fs::read_to_string("tests/data/locomotives.yaml")
+        .unwrap()
+        .replacen("powerMethod: DC", "powerMethod: 3-rail", 1);
+    std::fs::write(&filename, contents).unwrap();
+
+    let result = DataSource::new(filename.to_str().unwrap()).collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid item ACME 60023"));
+    assert!(error.contains("Invalid power method '3-rail'"));
+}
+
+#[test]
+fn it_should_load_purchase_dates_in_every_accepted_format() {
+    let collection = DataSource::new("tests/data/date_formats.yaml")
+        .collection()
+        .expect("Unable to load collection");
+
+    let mut dates: Vec<NaiveDate> = collection
+        .get_items()
+        .iter()
+        .map(|it| *it.purchased_info().purchased_date())
+        .collect();
+    dates.sort();
+    assert_eq!(
+        vec![
+            NaiveDate::from_ymd_opt(2019, 5, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2020, 1, 10).unwrap(),
+            NaiveDate::from_ymd_opt(2020, 3, 10).unwrap(),
+        ],
+        dates
+    );
+}
+
+#[test]
+fn it_should_fail_to_load_purchase_dates_in_other_formats() {
+    let result =
+        DataSource::new("tests/data/invalid_purchase_date.yaml").collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid purchase info for item ACME 60023"));
+    assert!(error.contains(
+        "Invalid purchase date '05-02-2019' (expected YYYY-MM-DD, DD/MM/YYYY or DD.MM.YYYY)"
+    ));
+}
+
+#[test]
+fn it_should_load_the_collection_from_a_reader() {
+    let contents =
+        std::fs::read_to_string("tests/data/collection.yaml").unwrap();
+
+    let collection = DataSource::new(DataSource::STDIN)
+        .collection_from_reader(std::io::Cursor::new(contents))
+        .unwrap();
+
+    assert_eq!(3, collection.len());
+}
+
+#[test]
+fn it_should_report_the_standard_input_for_validation_errors() {
+    let contents = std::fs::read_to_string("tests/data/collection.yaml")
+        .unwrap()
+        .replacen("price: \"229,00\"", "price: \"abc\"", 1);
+
+    let result = DataSource::new(DataSource::STDIN)
+        .with_strict(true)
+        .collection_from_reader(contents.as_bytes());
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("validation error(s) in standard input"));
+}
+
+#[test]
+fn it_should_calculate_the_collection_stats() {
+    let stats = CollectionStats::from_collection(&load_collection()).unwrap();
+    assert_eq!(Decimal::new(42450, 2), stats.total_value());
+    assert_eq!(2, stats.totals().number_of_locomotives());
+    assert_eq!(1, stats.totals().number_of_passenger_cars());
+
+    let by_scale =
+        CollectionStatsByScale::from_collection(&load_collection()).unwrap();
+    assert_eq!(1, by_scale.values().len());
+    assert_eq!(3, by_scale.values()[0].number_of_items());
+}
+
+#[test]
+fn it_should_load_the_decoder_info_into_the_depot() {
+    let locomotives = DataSource::new("tests/data/locomotives.yaml")
+        .collection()
+        .unwrap();
+    let depot = Depot::from_collection(&locomotives);
+
+    let card = depot
+        .locomotives()
+        .iter()
+        .find(|it| it.class_name() == "D.141")
+        .unwrap();
+    assert!(card.with_decoder());
+    assert_eq!("ESU LokPilot 5", card.decoder().unwrap().to_string());
+
+    let table = depot.to_table().to_string();
+    assert!(table.contains("Decoder"));
+    assert!(table.contains("ESU LokPilot 5"));
+}
+
+#[test]
+fn it_should_collapse_the_brands_outside_the_top_n_in_a_single_row() {
+    let contents =
+        std::fs::read_to_string("tests/data/collection.yaml").unwrap();
+    let start = contents.find("  - brand:").unwrap();
+    let end = start + 1 + contents[start + 1..].find("  - brand:").unwrap();
+    let lima = contents[start..end].replacen("brand: ACME", "brand: Lima", 1);
+    let collection =
+        Collection::from_yaml_str(&format!("{}{}", contents, lima)).unwrap();
+
+    let mut stats =
+        CollectionStatsByBrand::from_collection(&collection).unwrap();
+    assert_eq!(4, stats.values().len());
+
+    stats.keep_top(2);
+    let table = stats.to_table();
+
+    // the header, the two groups with the highest value and the others
+    assert_eq!(4, table.len());
+    assert!(table.to_string().contains("Others (2)"));
+}
+
+#[test]
+fn it_should_merge_the_collections_split_across_files() {
+    let mut collection = DataSource::new("tests/data/locomotives.yaml")
+        .collection()
+        .unwrap();
+    let coaches = DataSource::new("tests/data/coaches.yaml")
+        .collection()
+        .unwrap();
+    let modified_date = *coaches.modified_date();
+
+    let report = collection.merge(coaches);
+    assert!(report.duplicates().is_empty());
+    assert_eq!(3, collection.len());
+    assert_eq!(2, collection.version());
+    assert_eq!(&modified_date, collection.modified_date());
+}
+
+#[test]
+fn it_should_sum_the_stats_for_the_merged_collections() {
+    let locomotives = DataSource::new("tests/data/locomotives.yaml")
+        .collection()
+        .unwrap();
+    let coaches = DataSource::new("tests/data/coaches.yaml")
+        .collection()
+        .unwrap();
+
+    let locomotives_stats =
+        CollectionStats::from_collection(&locomotives).unwrap();
+    let coaches_stats = CollectionStats::from_collection(&coaches).unwrap();
+
+    let mut merged = locomotives;
+    merged.merge(coaches);
+    let stats = CollectionStats::from_collection(&merged).unwrap();
+
+    assert_eq!(
+        locomotives_stats.total_value() + coaches_stats.total_value(),
+        stats.total_value()
+    );
+    assert_eq!(
+        locomotives_stats.size() + coaches_stats.size(),
+        stats.size()
+    );
+    assert_eq!(
+        locomotives_stats.totals().number_of_locomotives()
+            + coaches_stats.totals().number_of_locomotives(),
+        stats.totals().number_of_locomotives()
+    );
+    assert_eq!(
+        locomotives_stats.totals().number_of_passenger_cars()
+            + coaches_stats.totals().number_of_passenger_cars(),
+        stats.totals().number_of_passenger_cars()
+    );
+    assert_eq!(
+        CollectionStats::from_collection(&load_collection())
+            .unwrap()
+            .total_value(),
+        stats.total_value()
+    );
+}
+
+#[test]
+fn it_should_calculate_the_stats_in_the_collection_currency() {
+    let collection = DataSource::new("tests/data/us_dollars.yaml")
+        .collection()
+        .expect("Unable to load collection");
+
+    let stats = CollectionStats::from_collection(&collection).unwrap();
+    assert_eq!(Currency::USD, stats.currency());
+    assert_eq!(Decimal::new(27400, 2), stats.total_value());
+
+    let table = stats.to_table().to_string();
+    assert!(table.contains("Total (USD)"));
+    assert!(!table.contains("EUR"));
+}
+
+#[test]
+fn it_should_refuse_the_stats_for_collections_in_mixed_currencies() {
+    let collection = DataSource::new("tests/data/mixed_currencies.yaml")
+        .collection()
+        .expect("Unable to load collection");
+
+    let result = CollectionStats::from_collection(&collection);
+    assert_eq!(
+        "Cannot sum prices in different currencies (USD and GBP)",
+        result.unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn it_should_load_items_without_price_and_their_condition() {
+    let collection = DataSource::new("tests/data/gifts.yaml")
+        .collection()
+        .expect("Unable to load collection");
+
+    let gift = collection
+        .get_items()
+        .iter()
+        .find(|it| it.purchased_info().price().is_none())
+        .expect("an item without price");
+    assert_eq!(Some(Condition::Used), gift.purchased_info().condition());
+}
+
+#[test]
+fn it_should_load_the_item_notes_and_tags() {
+    let collection = DataSource::new("tests/data/gifts.yaml")
+        .collection()
+        .expect("Unable to load collection");
+
+    let gift = collection
+        .get_items()
+        .iter()
+        .find(|it| it.has_tag("gift"))
+        .expect("an item tagged as gift");
+    assert_eq!(Some("Missing the original box"), gift.notes());
+    assert_eq!(&vec![String::from("Gift"), String::from("FS")], gift.tags());
+
+    let tags = collection.tags();
+    assert_eq!(Some(&1), tags.get("gift"));
+    assert_eq!(Some(&1), tags.get("fs"));
+}
+
+#[test]
+fn it_should_show_the_storage_location_or_a_dash() {
+    let collection = DataSource::new("tests/data/gifts.yaml")
+        .collection()
+        .expect("Unable to load collection");
+
+    let items: Vec<&CollectionItem> = collection.get_items().iter().collect();
+    assert_eq!(Some("Box 12"), items[0].storage());
+    assert_eq!(None, items[1].storage());
+
+    let table = tables::locate_table(&items).to_string();
+    assert!(table.contains("Box 12"));
+    assert!(table.contains(" - "));
+
+    let table =
+        tables::collection_items_table(items.clone(), 50, true).to_string();
+    assert!(table.contains("Storage"));
+    let table = tables::collection_items_table(items, 50, false).to_string();
+    assert!(!table.contains("Storage"));
+}
+
+#[test]
+fn it_should_exclude_the_items_without_price_from_the_stats_values() {
+    let collection = DataSource::new("tests/data/gifts.yaml")
+        .collection()
+        .expect("Unable to load collection");
+
+    let stats = CollectionStats::from_collection(&collection).unwrap();
+    assert_eq!(2, stats.size());
+    assert_eq!(1, stats.number_of_items_without_price());
+    assert_eq!(Decimal::new(22900, 2), stats.total_value());
+    assert_eq!(1, stats.number_of_passenger_cars());
+    assert_eq!(Decimal::ZERO, stats.passenger_cars_value());
+}
+
+#[test]
+fn it_should_leave_the_csv_price_empty_for_items_without_price() {
+    let collection = DataSource::new("tests/data/gifts.yaml")
+        .collection()
+        .expect("Unable to load collection");
+
+    let mut output = Vec::new();
+    export::csv::write_collection(
+        &mut output,
+        &collection,
+        PriceColumns::Split,
+    )
+    .unwrap();
+
+    let mut rdr = csv::Reader::from_reader(output.as_slice());
+    let record = rdr
+        .records()
+        .map(|it| it.unwrap())
+        .find(|it| it.get(1) == Some("45123"))
+        .unwrap();
+    assert_eq!(Some(""), record.get(10));
+    assert_eq!(Some(""), record.get(11));
+}
+
+#[test]
+fn it_should_fail_to_load_invalid_conditions() {
+    let result =
+        DataSource::new("tests/data/invalid_condition.yaml").collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid purchase info for item Roco 45123"));
+    assert!(error.contains("Invalid condition 'BROKEN'"));
+}
+
+#[test]
+fn it_should_build_the_depot() {
+    let depot = Depot::from_collection(&load_collection());
+    assert_eq!(2, depot.len());
+}
+
+#[test]
+fn it_should_export_the_collection_as_csv() {
+    let mut output = Vec::new();
+    export::csv::write_collection(
+        &mut output,
+        &load_collection(),
+        PriceColumns::Split,
+    )
+    .unwrap();
+
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(4, output.lines().count());
+    assert!(output.starts_with("Brand,ItemNumber,Category"));
+}
+
+#[test]
+fn it_should_compare_the_price_paid_with_the_wish_list_prices() {
+    let dir = std::env::temp_dir().join("railists-market-value");
+    std::fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("wish_list.yaml");
+    let contents = std::fs::read_to_string("tests/data/wish_list.yaml")
+        .unwrap()
+        .replacen("\"45124\"", "\"45123\"", 1);
+    std::fs::write(&filename, contents).unwrap();
+
+    let wish_list = DataSource::new(filename.to_str().unwrap())
+        .wish_list()
+        .expect("Unable to load the wishlist");
+    let collection = load_collection();
+    let reference_prices = ReferencePrices::from_wish_list(&wish_list);
+    let report =
+        CurrentValueReport::from_collection(&collection, &reference_prices)
+            .unwrap();
+
+    assert_eq!(Decimal::new(42450, 2), report.paid_total());
+    assert_eq!(Decimal::new(42400, 2), report.current_total());
+    assert_eq!(Decimal::new(-50, 2), report.difference());
+    assert_eq!(
+        1,
+        report
+            .items()
+            .iter()
+            .filter(|it| it.has_reference_price())
+            .count()
+    );
+}
+
+#[test]
+fn it_should_show_the_value_change_with_the_reference_prices() {
+    let dir = std::env::temp_dir().join("railists-value-delta");
+    std::fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("wish_list.yaml");
+    let contents = std::fs::read_to_string("tests/data/wish_list.yaml")
+        .unwrap()
+        .replacen("\"45124\"", "\"45123\"", 1);
+    std::fs::write(&filename, contents).unwrap();
+
+    let wish_list = DataSource::new(filename.to_str().unwrap())
+        .wish_list()
+        .expect("Unable to load the wishlist");
+    let reference_prices = ReferencePrices::from_wish_list(&wish_list);
+    let collection = load_collection();
+
+    let table = tables::collection_items_table_with_prices(
+        collection.get_items(),
+        50,
+        false,
+        &reference_prices,
+    )
+    .to_string();
+    assert!(table.contains("Change (%)"));
+    assert!(table.contains("-0.50"));
+    assert!(table.contains("-1.10"));
+}
+
+#[test]
+fn it_should_load_the_replacement_values() {
+    let collection = load_collection();
+
+    let item = collection.get(0).unwrap();
+    assert_eq!(
+        Some(&"260,00".parse::<Price>().unwrap()),
+        item.replacement_value()
+    );
+    assert_eq!(None, collection.get(1).unwrap().replacement_value());
+}
+
+#[test]
+fn it_should_fail_to_load_invalid_replacement_values() {
+    let dir = std::env::temp_dir().join("railists-replacement-value");
+    std::fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("collection.yaml");
+    let contents = std::fs::read_to_string("tests/data/collection.yaml")
+        .unwrap()
+        .replacen(
+            "replacementValue: \"260,00\"",
+            "replacementValue: \"-1\"",
+            1,
+        );
+    std::fs::write(&filename, contents).unwrap();
+
+    let result = DataSource::new(filename.to_str().unwrap()).collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid replacement value for item ACME 60023"));
+    assert!(error.contains("cannot be negative"));
+}
+
+fn load_collection_with_sold_item(dir: &str, sold_date: &str) -> Collection {
+    let dir = std::env::temp_dir().join(dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("collection.yaml");
+    let sold_info = format!(
+        "replacementValue: \"260,00\"\n    soldInfo:\n      date: \"{}\"\n      price: \"250,00\"\n      buyer: \"Mario\"",
+        sold_date
+    );
+    let contents = std::fs::read_to_string("tests/data/collection.yaml")
+        .unwrap()
+        .replacen("replacementValue: \"260,00\"", &sold_info, 1);
+    std::fs::write(&filename, contents).unwrap();
+
+    DataSource::new(filename.to_str().unwrap())
+        .collection()
+        .unwrap()
+}
+
+#[test]
+fn it_should_exclude_the_sold_items_from_the_stats() {
+    let collection =
+        load_collection_with_sold_item("railists-sold-stats", "2021-03-01");
+    assert!(collection.get(0).unwrap().is_sold());
+
+    let stats = CollectionStats::from_collection(&collection).unwrap();
+    assert_eq!(Decimal::new(19550, 2), stats.total_value());
+    assert_eq!(1, stats.totals().number_of_locomotives());
+
+    let history = CollectionStats::from_purchase_history(&collection).unwrap();
+    assert_eq!(Decimal::new(42450, 2), history.total_value());
+    assert_eq!(2, history.totals().number_of_locomotives());
+
+    assert_eq!(2, collection.without_sold().len());
+}
+
+#[test]
+fn it_should_calculate_the_realized_gains_for_the_sold_items() {
+    let collection =
+        load_collection_with_sold_item("railists-sold-report", "2021-03-01");
+
+    let report = SalesReport::from_collection(&collection).unwrap();
+    assert_eq!(1, report.len());
+    assert_eq!(Decimal::from(21), report.realized_gain());
+    assert_eq!(1, report.by_year().len());
+    assert_eq!(Decimal::from(250), report.by_year()[0].sold_total());
+
+    let table = report.to_table().to_string();
+    assert!(table.contains("Mario"));
+    assert!(table.contains("+21.00"));
+    let by_year = tables::sales_by_year_table(&report).to_string();
+    assert!(by_year.contains("2021"));
+}
+
+#[test]
+fn it_should_fail_to_load_items_sold_before_their_purchase() {
+    let dir = std::env::temp_dir().join("railists-sold-before-purchase");
+    std::fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("collection.yaml");
+    let contents = std::fs::read_to_string("tests/data/collection.yaml")
+        .unwrap()
+        .replacen(
+            "replacementValue: \"260,00\"",
+            "soldInfo:\n      date: \"2018-01-01\"",
+            1,
+        );
+    std::fs::write(&filename, contents).unwrap();
+
+    let result = DataSource::new(filename.to_str().unwrap()).collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid sold info for item ACME 60023"));
+    assert!(error.contains("cannot be before the purchase date"));
+}
+
+#[test]
+fn it_should_find_the_differences_between_two_collection_files() {
+    let dir = std::env::temp_dir().join("railists-collection-diff");
+    std::fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("collection.yaml");
+    let contents = std::fs::read_to_string("tests/data/collection.yaml")
+        .unwrap()
+        .replacen("price: \"229,00\"", "price: \"199,00\"", 1)
+        .replacen("\"52444\"", "\"52445\"", 1);
+    std::fs::write(&filename, contents).unwrap();
+
+    let old = load_collection();
+    let new = DataSource::new(filename.to_str().unwrap())
+        .collection()
+        .unwrap();
+
+    let diff = CollectionDiff::between(&old, &new);
+
+    assert_eq!(1, diff.added().len());
+    assert_eq!(
+        "52445",
+        diff.added()[0].catalog_item().item_number().value()
+    );
+    assert_eq!(1, diff.removed().len());
+    assert_eq!(
+        "52444",
+        diff.removed()[0].catalog_item().item_number().value()
+    );
+    assert_eq!(1, diff.changed().len());
+    let changes = diff.changed()[0].changes();
+    assert_eq!(1, changes.len());
+    assert_eq!("price: 229.00 EUR -> 199.00 EUR", changes[0].to_string());
+}
+
+#[test]
+fn it_should_export_the_insurance_report_as_csv() {
+    let collection = load_collection();
+    let report = InsuranceReport::from_collection(&collection, None).unwrap();
+
+    let mut output = Vec::new();
+    export::csv::write_insurance_report(&mut output, &report).unwrap();
+
+    let mut rdr = csv::Reader::from_reader(output.as_slice());
+    let records: Vec<csv::StringRecord> =
+        rdr.records().map(|it| it.unwrap()).collect();
+    assert_eq!(4, records.len());
+    assert_eq!(Some("E.656 210"), records[0].get(3));
+    assert_eq!(Some("260.00"), records[0].get(6));
+    assert_eq!(Some("45.50"), records[1].get(6));
+
+    let total = records.last().unwrap();
+    assert_eq!(Some("TOTAL"), total.get(0));
+    assert_eq!(Some("424.50"), total.get(5));
+    assert_eq!(Some("455.50"), total.get(6));
+}
+
+#[test]
+fn it_should_export_the_top_purchases_as_csv() {
+    let collection = load_collection();
+    let top = TopPurchases::from_collection(&collection, 2, &[]).unwrap();
+
+    let mut output = Vec::new();
+    export::csv::write_top_purchases(&mut output, &top).unwrap();
+
+    let mut rdr = csv::Reader::from_reader(output.as_slice());
+    assert_eq!(
+        vec![
+            "Brand",
+            "ItemNumber",
+            "Description",
+            "Shop",
+            "Date",
+            "Amount",
+            "Currency",
+            "Percentage",
+        ],
+        rdr.headers().unwrap().iter().collect::<Vec<_>>()
+    );
+    assert_eq!(2, rdr.records().count());
+}
+
+#[test]
+fn it_should_export_epoch_scale_and_power_method_as_csv_columns() {
+    let mut output = Vec::new();
+    export::csv::write_collection(
+        &mut output,
+        &load_collection(),
+        PriceColumns::Split,
+    )
+    .unwrap();
+
+    let mut rdr = csv::Reader::from_reader(output.as_slice());
+    let header = rdr.headers().unwrap().clone();
+    assert_eq!(
+        vec![
+            "Brand",
+            "ItemNumber",
+            "Category",
+            "Description",
+            "Epoch",
+            "Scale",
+            "PowerMethod",
+            "Shop",
+            "Date",
+            "Count",
+            "Amount",
+            "Currency"
+        ],
+        header.iter().collect::<Vec<&str>>()
+    );
+
+    let record = rdr.records().next().unwrap().unwrap();
+    assert_eq!(Some("IV"), record.get(4));
+    assert_eq!(Some("H0"), record.get(5));
+    assert_eq!(Some("DC"), record.get(6));
+}
+
+#[test]
+fn it_should_export_amount_and_currency_as_separate_csv_columns() {
+    let mut output = Vec::new();
+    export::csv::write_collection(
+        &mut output,
+        &load_collection(),
+        PriceColumns::Split,
+    )
+    .unwrap();
+
+    let mut rdr = csv::Reader::from_reader(output.as_slice());
+    let header = rdr.headers().unwrap().clone();
+    assert_eq!(Some("Amount"), header.get(10));
+    assert_eq!(Some("Currency"), header.get(11));
+
+    let record = rdr.records().next().unwrap().unwrap();
+    assert_eq!(Some("229.00"), record.get(10));
+    assert_eq!(Some("EUR"), record.get(11));
+}
+
+#[test]
+fn it_should_export_the_combined_price_as_one_csv_column() {
+    let mut output = Vec::new();
+    export::csv::write_collection(
+        &mut output,
+        &load_collection(),
+        PriceColumns::Combined,
+    )
+    .unwrap();
+
+    let mut rdr = csv::Reader::from_reader(output.as_slice());
+    assert_eq!(Some("Price"), rdr.headers().unwrap().get(10));
+    let record = rdr.records().next().unwrap().unwrap();
+    assert_eq!(Some("229.00 EUR"), record.get(10));
+}
+
+#[test]
+fn it_should_export_the_numeric_price_without_currency() {
+    let mut output = Vec::new();
+    export::csv::write_collection(
+        &mut output,
+        &load_collection(),
+        PriceColumns::Numeric,
+    )
+    .unwrap();
+
+    let mut rdr = csv::Reader::from_reader(output.as_slice());
+    assert_eq!(Some("Price"), rdr.headers().unwrap().get(10));
+    let prices: Vec<String> = rdr
+        .records()
+        .map(|it| it.unwrap().get(10).unwrap().to_owned())
+        .collect();
+    assert_eq!(vec!["229.00", "45.50", "150"], prices);
+}
+
+#[test]
+fn it_should_export_the_collection_as_json() {
+    let mut output = Vec::new();
+    export::json::write_collection(
+        &mut output,
+        &load_collection(),
+        JsonStyle::Pretty,
+    )
+    .unwrap();
+
+    let json = String::from_utf8(output).unwrap();
+    assert!(json.starts_with("{\n  \"description\": \"My collection\","));
+
+    let document: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let items = document["items"].as_array().unwrap();
+    assert_eq!(3, items.len());
+    assert_eq!("ACME", items[0]["brand"]);
+    assert_eq!("60023", items[0]["itemNumber"]);
+    assert_eq!("IV", items[0]["epoch"]);
+    assert_eq!("H0", items[0]["scale"]);
+    assert_eq!("DC", items[0]["powerMethod"]);
+    assert_eq!("2019-05-02", items[0]["date"]);
+    assert_eq!("229.00", items[0]["price"]);
+    assert_eq!("EUR", items[0]["currency"]);
+}
+
+#[test]
+fn it_should_export_the_collection_items_as_ndjson() {
+    let mut output = Vec::new();
+    export::json::write_collection_items(&mut output, &load_collection())
+        .unwrap();
+
+    let ndjson = String::from_utf8(output).unwrap();
+    let lines: Vec<&str> = ndjson.lines().collect();
+    assert_eq!(3, lines.len());
+
+    let item: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+    assert_eq!("60023", item["itemNumber"]);
+}
+
+#[test]
+fn it_should_render_the_same_collection_more_than_once() {
+    let collection = load_collection();
+
+    let table = collection.to_table();
+    assert_eq!(5, table.len());
+    assert_eq!(5, collection.to_table().len());
+
+    let mut output = Vec::new();
+    export::csv::write_collection(
+        &mut output,
+        &collection,
+        PriceColumns::Split,
+    )
+    .unwrap();
+    assert_eq!(4, String::from_utf8(output).unwrap().lines().count());
+}
+
+#[test]
+fn it_should_add_the_totals_footer_to_the_collection_table() {
+    let collection = load_collection();
+
+    let table = collection.to_table();
+    let footer = table.get_row(table.len() - 1).unwrap();
+    assert_eq!("TOTAL", footer.get_cell(1).unwrap().get_content());
+    assert_eq!("3 item(s)", footer.get_cell(2).unwrap().get_content());
+    assert_eq!("3", footer.get_cell(7).unwrap().get_content());
+    assert_eq!("424.50 EUR", footer.get_cell(9).unwrap().get_content());
+
+    let items: Vec<_> = collection
+        .get_items()
+        .iter()
+        .filter(|it| it.catalog_item().brand().name() == "Roco")
+        .collect();
+    let table = tables::collection_items_table(items, 50, false);
+    let footer = table.get_row(table.len() - 1).unwrap();
+    assert_eq!("1 item(s)", footer.get_cell(2).unwrap().get_content());
+    assert_eq!("1", footer.get_cell(7).unwrap().get_content());
+}
+
+#[test]
+fn it_should_render_a_heatmap_row_for_each_year() {
+    let purchases =
+        PurchasesByMonth::from_collection(&load_collection()).unwrap();
+    assert_eq!(vec![2019, 2020], purchases.years());
+
+    let table = tables::heatmap_table(&purchases, HeatmapMetric::Spend);
+    assert_eq!(3, table.len());
+
+    let years: Vec<String> = (1..table.len())
+        .map(|i| table.get_row(i).unwrap().get_cell(0).unwrap().get_content())
+        .collect();
+    assert_eq!(vec!["2019", "2020"], years);
+}
+
+#[test]
+fn it_should_report_the_missing_collection_file() {
+    let result = DataSource::new("tests/data/missing.yaml").collection();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.starts_with(
+        "Unable to load the collection from tests/data/missing.yaml"
+    ));
+}
+
+#[test]
+fn it_should_load_valid_collections_in_strict_mode() {
+    let collection = DataSource::new("tests/data/collection.yaml")
+        .with_strict(true)
+        .collection()
+        .unwrap();
+    assert_eq!(3, collection.len());
+}
+
+#[test]
+fn it_should_report_every_validation_error_in_strict_mode() {
+    let result = DataSource::new("tests/data/invalid_collection.yaml")
+        .with_strict(true)
+        .collection();
+
+    let error = result.unwrap_err().to_string();
+    let expected = DataSource::new("tests/data/invalid_collection.yaml")
+        .validate_collection()
+        .unwrap();
+    assert!(
+        error.starts_with(&format!("{} validation error(s)", expected.len()))
+    );
+    for e in expected {
+        assert!(error.contains(&e.to_string()));
+    }
+}
+
+#[test]
+fn it_should_find_no_validation_errors_in_valid_collections() {
+    let errors = DataSource::new("tests/data/collection.yaml")
+        .validate_collection()
+        .unwrap();
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn it_should_collect_all_the_validation_errors() {
+    let errors = DataSource::new("tests/data/invalid_collection.yaml")
+        .validate_collection()
+        .unwrap();
+
+    let fields: Vec<(usize, &str)> =
+        errors.iter().map(|e| (e.index(), e.field())).collect();
+    assert_eq!(
+        vec![
+            (1, "itemNumber"),
+            (1, "powerMethod"),
+            (1, "scale"),
+            (1, "purchaseInfo.date"),
+            (1, "purchaseInfo.price"),
+            (1, "rollingStocks[0].epoch"),
+            (1, "rollingStocks[0].subCategory"),
+            (1, "rollingStocks[0].dccInterface"),
+            (2, "rollingStocks[0].length"),
+            (2, "rollingStocks[0].serviceLevel"),
+        ],
+        fields
+    );
+}
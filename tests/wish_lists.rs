@@ -0,0 +1,330 @@
+use railists::data_source::DataSource;
+use railists::domain::collecting::{
+    collections::PurchasedInfo,
+    wish_lists::{Availability, WishListBudget},
+    Price,
+};
+use railists::tables::{self, AsTable};
+
+use chrono::NaiveDate;
+use std::fs;
+use std::path::PathBuf;
+
+fn copy_fixtures(name: &str) -> (PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("railists-{}", name));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+
+    let wish_list = dir.join("wish_list.yaml");
+    let collection = dir.join("collection.yaml");
+    fs::copy("tests/data/wish_list.yaml", &wish_list).unwrap();
+    fs::copy("tests/data/collection.yaml", &collection).unwrap();
+    (wish_list, collection)
+}
+
+fn new_purchased_info() -> PurchasedInfo {
+    PurchasedInfo::new(
+        "Treni&Treni",
+        NaiveDate::from_ymd_opt(2024, 3, 2).unwrap(),
+        "229,00".parse::<Price>().unwrap(),
+    )
+}
+
+#[test]
+fn it_should_move_purchased_items_into_the_collection() {
+    let (wish_list, collection) = copy_fixtures("purchase");
+    let wish_list = DataSource::new(wish_list.to_str().unwrap());
+    let collection = DataSource::new(collection.to_str().unwrap());
+
+    wish_list
+        .purchase(&collection, "ACME", "60456", &new_purchased_info())
+        .unwrap();
+
+    let wish_list = wish_list.wish_list().unwrap();
+    assert_eq!(2, wish_list.version());
+    assert_eq!(2, wish_list.get_items().len());
+
+    let collection = collection.collection().unwrap();
+    assert_eq!(2, collection.version());
+    assert_eq!(4, collection.len());
+
+    let item = collection.get(3).unwrap();
+    assert_eq!("60456", item.catalog_item().item_number().value());
+    assert_eq!("Treni&Treni", item.purchased_info().shop());
+}
+
+#[test]
+fn it_should_fail_to_purchase_items_not_in_the_wish_list() {
+    let (wish_list, collection) = copy_fixtures("purchase-not-found");
+    let before = fs::read_to_string(&wish_list).unwrap();
+
+    let result = DataSource::new(wish_list.to_str().unwrap()).purchase(
+        &DataSource::new(collection.to_str().unwrap()),
+        "ACME",
+        "99999",
+        &new_purchased_info(),
+    );
+
+    assert!(result.is_err());
+    assert_eq!(before, fs::read_to_string(&wish_list).unwrap());
+}
+
+#[test]
+fn it_should_leave_the_wish_list_untouched_when_the_collection_write_fails() {
+    let (wish_list, collection) = copy_fixtures("purchase-write-error");
+    let before = fs::read_to_string(&wish_list).unwrap();
+
+    // the temporary file for the collection cannot be created
+    fs::create_dir(collection.with_extension("yaml.tmp")).unwrap();
+
+    let result = DataSource::new(wish_list.to_str().unwrap()).purchase(
+        &DataSource::new(collection.to_str().unwrap()),
+        "ACME",
+        "60456",
+        &new_purchased_info(),
+    );
+
+    assert!(result.is_err());
+    assert_eq!(before, fs::read_to_string(&wish_list).unwrap());
+}
+
+#[test]
+fn it_should_leave_the_collection_untouched_when_the_wish_list_write_fails() {
+    let (wish_list, collection) = copy_fixtures("purchase-wish-list-error");
+    let before = fs::read_to_string(&collection).unwrap();
+
+    // the temporary file for the wish list cannot be created
+    fs::create_dir(wish_list.with_extension("yaml.tmp")).unwrap();
+
+    let result = DataSource::new(wish_list.to_str().unwrap()).purchase(
+        &DataSource::new(collection.to_str().unwrap()),
+        "ACME",
+        "60456",
+        &new_purchased_info(),
+    );
+
+    assert!(result.is_err());
+    assert_eq!(before, fs::read_to_string(&collection).unwrap());
+    assert!(!collection.with_extension("yaml.tmp").exists());
+}
+
+#[test]
+fn it_should_load_the_wish_list_modified_date() {
+    let wish_list = DataSource::new("tests/data/wish_list.yaml")
+        .wish_list()
+        .unwrap();
+
+    assert_eq!("My wish list", wish_list.name());
+    assert_eq!(
+        "2023-10-01 10:00:00",
+        wish_list
+            .modified_date()
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string()
+    );
+}
+
+#[test]
+fn it_should_fail_to_load_wish_lists_with_malformed_modified_date() {
+    let dir = std::env::temp_dir().join("railists-modified-at");
+    fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("wish_list.yaml");
+    let contents = fs::read_to_string("tests/data/wish_list.yaml")
+        .unwrap()
+        .replace("2023-10-01 10:00:00", "yesterday");
+    fs::write(&filename, contents).unwrap();
+
+    let result = DataSource::new(filename.to_str().unwrap()).wish_list();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains(filename.to_str().unwrap()));
+    assert!(error.contains("Invalid modifiedAt value 'yesterday'"));
+}
+
+#[test]
+fn it_should_render_the_budget_table_with_the_grand_total() {
+    let wish_list = DataSource::new("tests/data/wish_list.yaml")
+        .wish_list()
+        .unwrap();
+
+    let table = WishListBudget::from_wish_list(&wish_list)
+        .unwrap()
+        .to_table();
+    assert_eq!(5, table.len());
+
+    let total = table.get_row(4).unwrap();
+    assert_eq!("TOTAL", total.get_cell(0).unwrap().get_content());
+    assert_eq!("255.00", total.get_cell(1).unwrap().get_content());
+    assert_eq!("274.00", total.get_cell(2).unwrap().get_content());
+}
+
+#[test]
+fn it_should_find_the_wish_list_items_already_owned() {
+    let dir = std::env::temp_dir().join("railists-already-owned");
+    fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("wish_list.yaml");
+    let contents = fs::read_to_string("tests/data/wish_list.yaml")
+        .unwrap()
+        .replacen("\"45124\"", "\"45123\"", 1);
+    fs::write(&filename, contents).unwrap();
+
+    let wish_list = DataSource::new(filename.to_str().unwrap())
+        .wish_list()
+        .unwrap();
+    let collection = DataSource::new("tests/data/collection.yaml")
+        .collection()
+        .unwrap();
+
+    let matches = wish_list.already_owned(&collection, false);
+    assert_eq!(1, matches.len());
+    assert_eq!(
+        "45123",
+        matches[0].owned().catalog_item().item_number().value()
+    );
+
+    let table = tables::owned_matches_table(&matches).to_string();
+    assert!(table.contains("item number"));
+    assert!(table.contains("Roco 45123"));
+}
+
+fn write_wish_list_with_price_dates(name: &str, date: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("railists-{}", name));
+    fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("wish_list.yaml");
+    let contents = fs::read_to_string("tests/data/wish_list.yaml")
+        .unwrap()
+        .replacen(
+            "price: \"229,00\"",
+            &format!(
+                "price: \"229,00\"\n        date: \"{}\"\n      - shop: \"Treni&Treni\"\n        price: \"199,00\"\n        date: \"2023-09-01\"",
+                date
+            ),
+            1,
+        );
+    fs::write(&filename, contents).unwrap();
+    filename
+}
+
+#[test]
+fn it_should_load_the_price_history() {
+    let filename =
+        write_wish_list_with_price_dates("price-history", "2023-05-01");
+
+    let wish_list = DataSource::new(filename.to_str().unwrap())
+        .wish_list()
+        .unwrap();
+
+    let item = &wish_list.find_by_item_number("60456")[0];
+    assert_eq!(3, item.prices().len());
+    assert_eq!(2, item.latest_price_per_shop().len());
+
+    let (min, max) = item.latest_price_range().unwrap();
+    assert_eq!("199.00 EUR", min.price().to_string());
+    assert_eq!("210.00 EUR", max.price().to_string());
+
+    let (first, latest) = item.price_trend().unwrap();
+    assert_eq!("210.00 EUR", first.price().to_string());
+    assert_eq!("199.00 EUR", latest.price().to_string());
+
+    let table = tables::price_history_table(item).to_string();
+    assert!(table.contains("2023-05-01"));
+    assert!(table.contains("2023-09-01"));
+}
+
+#[test]
+fn it_should_fail_to_load_invalid_price_dates() {
+    let filename =
+        write_wish_list_with_price_dates("invalid-price-date", "yesterday");
+
+    let result = DataSource::new(filename.to_str().unwrap()).wish_list();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid prices for item ACME 60456"));
+    assert!(error.contains("Invalid price date 'yesterday'"));
+}
+
+fn write_wish_list_with_availability(
+    name: &str,
+    availability: &str,
+) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("railists-{}", name));
+    fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("wish_list.yaml");
+    let contents = fs::read_to_string("tests/data/wish_list.yaml")
+        .unwrap()
+        .replacen(
+            "priority: HIGH",
+            &format!("priority: HIGH\n    availability: {}", availability),
+            1,
+        );
+    fs::write(&filename, contents).unwrap();
+    filename
+}
+
+#[test]
+fn it_should_load_the_availability_and_leave_sold_out_items_out_of_the_budget()
+{
+    let filename =
+        write_wish_list_with_availability("availability", "SOLD_OUT");
+
+    let wish_list = DataSource::new(filename.to_str().unwrap())
+        .wish_list()
+        .unwrap();
+    let item = &wish_list.find_by_item_number("60456")[0];
+    assert_eq!(Some(Availability::SoldOut), item.availability());
+
+    let budget = WishListBudget::from_wish_list(&wish_list).unwrap();
+    assert_eq!(1, budget.unavailable().number_of_items());
+    assert_eq!("45.00", budget.budget().to_string());
+
+    let table = tables::wish_list_items_table(wish_list.get_items(), 50);
+    assert_eq!(
+        "SOLD_OUT",
+        table.get_row(1).unwrap().get_cell(5).unwrap().get_content()
+    );
+}
+
+#[test]
+fn it_should_fail_to_load_invalid_availability_values() {
+    let filename =
+        write_wish_list_with_availability("invalid-availability", "GONE");
+
+    let result = DataSource::new(filename.to_str().unwrap()).wish_list();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("Invalid availability 'GONE'"));
+}
+
+#[test]
+fn it_should_report_the_field_and_value_for_invalid_priorities() {
+    let dir = std::env::temp_dir().join("railists-invalid-priority");
+    fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("wish_list.yaml");
+    let contents = fs::read_to_string("tests/data/wish_list.yaml")
+        .unwrap()
+        .replacen("priority: HIGH", "priority: URGENT", 1);
+    fs::write(&filename, contents).unwrap();
+
+    let result = DataSource::new(filename.to_str().unwrap()).wish_list();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("elements[0]"));
+    assert!(error.contains("Invalid priority 'URGENT'"));
+}
+
+#[test]
+fn it_should_report_the_element_for_invalid_power_methods() {
+    let dir = std::env::temp_dir().join("railists-wish-list-power-method");
+    fs::create_dir_all(&dir).unwrap();
+    let filename = dir.join("wish_list.yaml");
+    let contents = fs::read_to_string("tests/data/wish_list.yaml")
+        .unwrap()
+        .replacen("powerMethod: DC", "powerMethod: 3-rail", 1);
+    fs::write(&filename, contents).unwrap();
+
+    let result = DataSource::new(filename.to_str().unwrap()).wish_list();
+
+    let error = format!("{:#}", result.unwrap_err());
+    assert!(error.contains("elements[0]"));
+    assert!(error.contains("Invalid power method '3-rail'"));
+}